@@ -0,0 +1,33 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use video_buffer::{convert_swizzle, Swizzle};
+
+const WIDTH: usize = 1920;
+const HEIGHT: usize = 1080;
+
+/// One pixel at a time, as the conversion worked before the vector paths
+fn prgb_to_rgba_scalar(src: &[u8], dst: &mut [u8]) {
+    for (src_pixel, dst_pixel) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+        dst_pixel[0] = src_pixel[1];
+        dst_pixel[1] = src_pixel[2];
+        dst_pixel[2] = src_pixel[3];
+        dst_pixel[3] = src_pixel[0];
+    }
+}
+
+fn bench_prgb_to_rgba(c: &mut Criterion) {
+    let src: Vec<u8> = (0..=255).cycle().take(WIDTH * HEIGHT * 4).collect();
+    let mut dst = vec![0u8; src.len()];
+
+    let mut group = c.benchmark_group("prgb_to_rgba_1080p");
+    group.throughput(Throughput::Bytes(src.len() as u64));
+    group.bench_function("scalar", |b| {
+        b.iter(|| prgb_to_rgba_scalar(black_box(&src), black_box(&mut dst)))
+    });
+    group.bench_function("simd", |b| {
+        b.iter(|| convert_swizzle(black_box(&src), black_box(&mut dst), Swizzle::PRGB_TO_RGBA))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_prgb_to_rgba);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use video_buffer::{
+    run_headless, DisplayBackend, DisplayPresenter, FnRenderer, PixelFormat, TripleBuffer,
+    VideoBufferError,
+};
+
+const WIDTH: u32 = 1920;
+const HEIGHT: u32 = 1080;
+
+/// Accepts every frame without displaying it, so only the presenter's own work is measured
+struct NullBackend;
+
+impl DisplayBackend for NullBackend {
+    const FORMAT: PixelFormat = PixelFormat::Rgba8;
+
+    fn init(&mut self, _width: u32, _height: u32) -> Result<(), VideoBufferError> {
+        Ok(())
+    }
+
+    fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        black_box(frame);
+        Ok(())
+    }
+}
+
+fn bench_present(c: &mut Criterion) {
+    let mut group = c.benchmark_group("present_1080p");
+    group.throughput(Throughput::Elements(1));
+
+    for (name, source_format) in [
+        ("convert_prgb8", PixelFormat::Prgb8),
+        ("passthrough_rgba8", PixelFormat::Rgba8),
+    ] {
+        let buffer = TripleBuffer::new(WIDTH, HEIGHT, source_format);
+        let mut presenter =
+            DisplayPresenter::new(NullBackend, WIDTH, HEIGHT, source_format).unwrap();
+
+        let mut now_ms = 0.0;
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                buffer.commit_render();
+                now_ms += 1.0;
+                presenter.present_at(&buffer, now_ms).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Whole render, convert and present pipeline, with a renderer that only fills the frame
+fn bench_pipeline(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pipeline_1080p");
+    group.throughput(Throughput::Elements(1));
+
+    let mut renderer = FnRenderer::new(PixelFormat::Prgb8, |frame, _, _, _| frame.fill(255));
+    group.bench_function("fill_prgb8", |b| {
+        b.iter_custom(|frames| {
+            run_headless(&mut renderer, NullBackend, WIDTH, HEIGHT, frames).unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_present, bench_pipeline);
+criterion_main!(benches);
@@ -11,7 +11,6 @@ use winit::event_loop::{ActiveEventLoop, EventLoop};
 use winit::window::{Window, WindowAttributes, WindowId};
 
 struct RaqoteRenderer {
-    frame_count: u64,
     last_time: Instant,
     fps: f64,
     font: Arc<font_kit::font::Font>,
@@ -26,7 +25,6 @@ impl RaqoteRenderer {
             .unwrap();
 
         Self {
-            frame_count: 0,
             last_time: Instant::now(),
             fps: 0.0,
             font: Arc::new(font),
@@ -35,9 +33,11 @@ impl RaqoteRenderer {
 }
 
 impl Renderer for RaqoteRenderer {
-    const FORMAT: PixelFormat = PixelFormat::Prgb8;
+    fn format(&self) -> PixelFormat {
+        PixelFormat::Prgb8
+    }
 
-    fn render(&mut self, frame: &mut [u8], width: u32, height: u32) {
+    fn render(&mut self, frame: &mut [u8], width: u32, height: u32, frame_no: u64) {
         // Update FPS
         let now = Instant::now();
         let delta = now.duration_since(self.last_time).as_secs_f64();
@@ -50,7 +50,7 @@ impl Renderer for RaqoteRenderer {
 
         dt.clear(SolidSource::from_unpremultiplied_argb(255, 20, 20, 30));
 
-        let x = ((self.frame_count as f32 * 3.0) % width as f32) as f32;
+        let x = ((frame_no as f32 * 3.0) % width as f32) as f32;
         let y = height as f32 / 2.0;
 
         let mut pb = PathBuilder::new();
@@ -64,7 +64,7 @@ impl Renderer for RaqoteRenderer {
         );
 
         // Draw FPS and frame counter text
-        let text = format!("FPS: {:.0}  Frames: {}", self.fps, self.frame_count);
+        let text = format!("FPS: {:.0}  Frames: {}", self.fps, frame_no);
         dt.draw_text(
             &self.font,
             16.0,
@@ -76,8 +76,6 @@ impl Renderer for RaqoteRenderer {
 
         let src = dt.get_data();
         frame.copy_from_slice(bytemuck::cast_slice(src));
-
-        self.frame_count += 1;
     }
 }
 
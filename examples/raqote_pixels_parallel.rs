@@ -15,7 +15,6 @@ use winit::event_loop::{ActiveEventLoop, EventLoop};
 use winit::window::{Window, WindowAttributes, WindowId};
 
 struct RaqoteRenderer {
-    frame_count: u64,
     last_time: Instant,
     fps: f64,
     font: Arc<font_kit::font::Font>,
@@ -30,7 +29,6 @@ impl RaqoteRenderer {
             .unwrap();
 
         Self {
-            frame_count: 0,
             last_time: Instant::now(),
             fps: 0.0,
             font: Arc::new(font),
@@ -39,9 +37,11 @@ impl RaqoteRenderer {
 }
 
 impl Renderer for RaqoteRenderer {
-    const FORMAT: PixelFormat = PixelFormat::Prgb8;
+    fn format(&self) -> PixelFormat {
+        PixelFormat::Prgb8
+    }
 
-    fn render(&mut self, frame: &mut [u8], width: u32, height: u32) {
+    fn render(&mut self, frame: &mut [u8], width: u32, height: u32, frame_no: u64) {
         // Update FPS
         let now = Instant::now();
         let delta = now.duration_since(self.last_time).as_secs_f64();
@@ -53,7 +53,7 @@ impl Renderer for RaqoteRenderer {
         let mut dt = DrawTarget::new(width as i32, height as i32);
         dt.clear(SolidSource::from_unpremultiplied_argb(255, 20, 20, 30));
 
-        let x = ((self.frame_count as f32 * 3.0) % width as f32) as f32;
+        let x = ((frame_no as f32 * 3.0) % width as f32) as f32;
         let y = height as f32 / 2.0;
 
         let mut pb = PathBuilder::new();
@@ -66,10 +66,7 @@ impl Renderer for RaqoteRenderer {
             &DrawOptions::new(),
         );
 
-        let text = format!(
-            "FPS: {:.0}  Frames: {} (Parallel)",
-            self.fps, self.frame_count
-        );
+        let text = format!("FPS: {:.0}  Frames: {} (Parallel)", self.fps, frame_no);
         dt.draw_text(
             &self.font,
             16.0,
@@ -80,7 +77,6 @@ impl Renderer for RaqoteRenderer {
         );
 
         frame.copy_from_slice(bytemuck::cast_slice(dt.get_data()));
-        self.frame_count += 1;
     }
 }
 
@@ -90,7 +86,6 @@ struct App {
     buffer: Option<Arc<TripleBuffer>>,
     worker: Option<thread::JoinHandle<()>>,
     stop_tx: Option<std::sync::mpsc::Sender<()>>,
-    start_time: Instant,
 }
 
 impl App {
@@ -101,7 +96,6 @@ impl App {
             buffer: None,
             worker: None,
             stop_tx: None,
-            start_time: Instant::now(),
         }
     }
 }
@@ -134,6 +128,7 @@ impl ApplicationHandler for App {
         let (tx, rx) = channel();
         let worker = thread::spawn(move || {
             let mut renderer = RaqoteRenderer::new();
+            let mut frame_no: u64 = 0;
             let target = std::time::Duration::from_secs_f64(1.0 / 120.0);
 
             loop {
@@ -142,8 +137,9 @@ impl ApplicationHandler for App {
                 }
 
                 let start = Instant::now();
-                renderer.render(&mut buffer_clone.render_buffer(), 800, 600);
+                renderer.render(&mut buffer_clone.render_buffer(), 800, 600, frame_no);
                 buffer_clone.commit_render();
+                frame_no = frame_no.wrapping_add(1);
 
                 if let Some(sleep) = target.checked_sub(start.elapsed()) {
                     thread::sleep(sleep);
@@ -182,8 +178,11 @@ impl ApplicationHandler for App {
                 if let (Some(ref buffer), Some(ref mut presenter)) =
                     (&self.buffer, &mut self.presenter)
                 {
-                    let now_ms = self.start_time.elapsed().as_secs_f64() * 1000.0;
-                    presenter.present(buffer, now_ms).unwrap();
+                    // Check for a committed frame without blocking the event loop; the
+                    // redraw requested below checks again on the next pass
+                    if buffer.wait_for_frame(std::time::Duration::ZERO) {
+                        presenter.present(buffer).unwrap();
+                    }
                 }
 
                 self.window.as_ref().unwrap().request_redraw();
@@ -1,8 +1,7 @@
 use std::cell::RefCell;
-use std::collections::VecDeque;
 use std::rc::Rc;
 use video_buffer::backends::WasmCanvasBackend;
-use video_buffer::{DisplayPresenter, FrameQueue, PixelFormat};
+use video_buffer::{DisplayPresenter, FrameQueue, FrameScheduler, PixelFormat};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, Worker};
@@ -10,6 +9,8 @@ use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, Worker};
 const NUM_WORKERS: usize = 6;
 const MAX_QUEUED_FRAMES: usize = 20; // Maximum pre-rendered frames to keep
 
+type AnimationFrameCallback = Rc<RefCell<Option<Closure<dyn FnMut()>>>>;
+
 struct WasmApp {
     // Presentation layer (handles timing, conversion, and canvas blitting)
     presenter: DisplayPresenter<WasmCanvasBackend>,
@@ -17,13 +18,11 @@ struct WasmApp {
     // Frame queue from workers (pre-rendered frames waiting to be displayed)
     frame_queue: FrameQueue,
 
+    // Decides which frames to request from workers, paced to the display rate
+    frame_scheduler: FrameScheduler,
+
     workers: Vec<Worker>,
     workers_ready: usize,
-    next_render_frame: u64, // Next frame number to request from workers
-
-    // FPS tracking
-    frame_times: VecDeque<f64>, // Timestamps of actual presented frames
-    fps: f64,                   // Measured display FPS
 
     browser_frame_counter: u32,
 
@@ -65,11 +64,9 @@ impl WasmApp {
         Ok(Self {
             presenter,
             frame_queue: FrameQueue::new(MAX_QUEUED_FRAMES),
+            frame_scheduler: FrameScheduler::new(MAX_QUEUED_FRAMES),
             workers,
             workers_ready: 0,
-            next_render_frame: 0,
-            frame_times: VecDeque::new(),
-            fps: 0.0,
             browser_frame_counter: 0,
             width,
             height,
@@ -122,11 +119,10 @@ impl WasmApp {
                                 frame_no_val.as_f64(),
                                 buffer_val.dyn_into::<js_sys::Uint8Array>(),
                             ) {
-                                let mut buffer = vec![0u8; array.length() as usize];
+                                let mut app = app_clone.borrow_mut();
+                                let mut buffer = app.frame_queue.acquire(array.length() as usize);
                                 array.copy_to(&mut buffer);
-                                app_clone
-                                    .borrow_mut()
-                                    .on_frame_ready(frame_no as u64, buffer);
+                                app.on_frame_ready(frame_no as u64, buffer);
                             }
                         }
                     }
@@ -157,12 +153,18 @@ impl WasmApp {
     }
 
     fn request_frames(&mut self) {
-        // Only request frames if we don't have too many in flight
-        // next_render_frame - next_display_frame tells us how many frames are requested/queued total
-        while (self.next_render_frame - self.frame_queue.next_frame_number())
-            < MAX_QUEUED_FRAMES as u64
-        {
-            let frame_no = self.next_render_frame;
+        // Keep at most MAX_QUEUED_FRAMES requested/queued, but don't request them faster
+        // than the display is actually presenting them
+        let Ok(now) = self.presenter.now_ms() else {
+            return;
+        };
+        let fps = self.presenter.fps_estimate();
+        let frames =
+            self.frame_scheduler
+                .frames_to_request_rate_limited(&self.frame_queue, fps, now);
+
+        let end = frames.end;
+        for frame_no in frames {
             let worker_id = (frame_no as usize) % NUM_WORKERS;
 
             // Send render request to worker
@@ -171,15 +173,15 @@ impl WasmApp {
                 ("frame_no", JsValue::from_f64(frame_no as f64)),
                 ("width", JsValue::from_f64(self.width as f64)),
                 ("height", JsValue::from_f64(self.height as f64)),
-                ("fps", JsValue::from_f64(self.fps)),
+                ("fps", JsValue::from_f64(fps)),
             ]);
 
             if let Err(e) = self.workers[worker_id].post_message(&request_obj) {
                 web_sys::console::error_1(&format!("Failed to post message: {:?}", e).into());
+                // Request the rest again later, so the queue doesn't stall on a gap
+                self.frame_scheduler.cancel(frame_no..end);
                 break;
             }
-
-            self.next_render_frame += 1;
         }
     }
 
@@ -187,19 +189,15 @@ impl WasmApp {
         self.browser_frame_counter += 1;
 
         // Limit the display rate by skipping frames
-        if self.browser_frame_counter % 2 != 0 {
+        if !self.browser_frame_counter.is_multiple_of(2) {
             return Ok(false);
         }
 
-        let now = js_sys::Date::now();
-
         // Try to get the next frame from the queue
         if let Some(buffer) = self.frame_queue.pop_ready() {
-            let presented = self.presenter.present_frame(&buffer, now)?;
-
-            if presented {
-                self.update_fps(now);
-            }
+            let presented = self.presenter.present_frame(&buffer);
+            self.frame_queue.release(buffer);
+            let presented = presented?;
 
             // Request more frames to keep queue filled
             self.request_frames();
@@ -209,19 +207,6 @@ impl WasmApp {
             Ok(false) // No frame available yet
         }
     }
-
-    fn update_fps(&mut self, now: f64) {
-        self.frame_times.push_back(now);
-        if self.frame_times.len() > 60 {
-            self.frame_times.pop_front();
-        }
-        if self.frame_times.len() >= 2 {
-            let time_span = self.frame_times.back().unwrap() - self.frame_times.front().unwrap();
-            if time_span > 0.0 {
-                self.fps = (self.frame_times.len() as f64 - 1.0) / (time_span / 1000.0);
-            }
-        }
-    }
 }
 
 #[wasm_bindgen]
@@ -253,8 +238,7 @@ pub fn start_custom(canvas_id: &str, width: u32, height: u32) -> Result<(), JsVa
 
 fn start_render_loop(app: Rc<RefCell<WasmApp>>) {
     // Enables the closure to schedule itself recursively
-    let present_and_reschedule: Rc<RefCell<Option<Closure<dyn FnMut()>>>> =
-        Rc::new(RefCell::new(None));
+    let present_and_reschedule: AnimationFrameCallback = Rc::new(RefCell::new(None));
     let present_and_reschedule_handle = Rc::clone(&present_and_reschedule);
     let app_handle = Rc::clone(&app);
 
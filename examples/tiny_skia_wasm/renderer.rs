@@ -2,6 +2,7 @@ use crate::sprite::Airplane;
 use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
 use fontdue::Font;
 use tiny_skia::{Color, Pixmap, PixmapMut, PixmapPaint, Transform};
+use video_buffer::{PixelBufferMut, PixelFormat};
 
 pub struct TinySkiaRenderer {
     font: Font,
@@ -20,13 +21,11 @@ impl TinySkiaRenderer {
         let mut sprite = Pixmap::decode_png(airplane_data).expect("Failed to load airplane.png");
 
         // Apply additional transparency effect by double-premultiplying alpha
-        let data = sprite.data_mut();
-        for i in 0..(data.len() / 4) {
-            let idx = i * 4;
-            let a = data[idx] as f32 / 255.0;
-            data[idx + 1] = (data[idx + 1] as f32 * a) as u8; // R
-            data[idx + 2] = (data[idx + 2] as f32 * a) as u8; // G
-            data[idx + 3] = (data[idx + 3] as f32 * a) as u8; // B
+        for pixel in sprite.data_mut().chunks_exact_mut(4) {
+            let a = pixel[3] as f32 / 255.0;
+            for channel in &mut pixel[..3] {
+                *channel = (*channel as f32 * a) as u8;
+            }
         }
 
         // Pre-render 120 rotated versions (every 3 degrees)
@@ -75,7 +74,16 @@ impl TinySkiaRenderer {
         }
     }
 
+    /// Byte layout of the pixmaps drawn into and of the frames `render_to_rgba` writes
+    ///
+    /// tiny-skia pixmaps hold premultiplied RGBA.
+    pub(crate) fn format(&self) -> PixelFormat {
+        PixelFormat::Rgba8
+    }
+
     fn draw_text(&self, pixmap: &mut PixmapMut, text: &str, x: f32, y: f32, size: f32) {
+        let (width, height) = (pixmap.width(), pixmap.height());
+        let mut pixels = PixelBufferMut::new(pixmap.data_mut(), width, height, self.format());
         let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
         layout.reset(&LayoutSettings::default());
         layout.append(&[&self.font], &TextStyle::new(text, size, 0));
@@ -91,21 +99,10 @@ impl TinySkiaRenderer {
                 let glyph_x = (x + glyph.x + (i % metrics.width) as f32) as i32;
                 let glyph_y = (y + glyph.y + (i / metrics.width) as f32) as i32;
 
-                if glyph_x >= 0
-                    && glyph_x < pixmap.width() as i32
-                    && glyph_y >= 0
-                    && glyph_y < pixmap.height() as i32
+                if glyph_x >= 0 && glyph_x < width as i32 && glyph_y >= 0 && glyph_y < height as i32
                 {
-                    let idx = (glyph_y as usize * pixmap.width() as usize + glyph_x as usize) * 4;
-                    let data = pixmap.data_mut();
-
-                    if idx + 3 < data.len() {
-                        let alpha_f = alpha as f32 / 255.0;
-                        data[idx] = 255;
-                        data[idx + 1] = (255.0 * alpha_f) as u8;
-                        data[idx + 2] = (255.0 * alpha_f) as u8;
-                        data[idx + 3] = (255.0 * alpha_f) as u8;
-                    }
+                    // Opaque gray, i.e. white text blended onto the black background
+                    pixels.set_rgba(glyph_x as u32, glyph_y as u32, alpha, alpha, alpha, 255);
                 }
             }
         }
@@ -119,7 +116,7 @@ impl TinySkiaRenderer {
         frame_no: u64,
         fps: f64,
     ) {
-        // Render to ARGB premultiplied first
+        // Render to premultiplied RGBA first
         let mut pixmap = Pixmap::new(width, height).expect("Failed to create pixmap");
         let mut pixmap_mut = pixmap.as_mut();
 
@@ -140,15 +137,8 @@ impl TinySkiaRenderer {
         );
         self.draw_text(&mut pixmap_mut, &text, 10.0, height as f32 - 36.0, 20.0);
 
-        // Convert ARGB premultiplied to RGBA for canvas
-        // Simple channel swap
-        let argb_data = pixmap.data();
-        for i in 0..(frame.len() / 4) {
-            let idx = i * 4;
-            frame[idx] = argb_data[idx + 1]; // R
-            frame[idx + 1] = argb_data[idx + 2]; // G
-            frame[idx + 2] = argb_data[idx + 3]; // B
-            frame[idx + 3] = argb_data[idx]; // A
-        }
+        // The pixmap is already RGBA, and opaque thanks to the black fill, so its
+        // premultiplied pixels equal the straight alpha the canvas expects
+        frame.copy_from_slice(pixmap.data());
     }
 }
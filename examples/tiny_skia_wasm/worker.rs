@@ -8,7 +8,7 @@ mod sprite;
 use renderer::TinySkiaRenderer;
 
 thread_local! {
-    static RENDERER: RefCell<Option<TinySkiaRenderer>> = RefCell::new(None);
+    static RENDERER: RefCell<Option<TinySkiaRenderer>> = const { RefCell::new(None) };
 }
 
 /// Helper function to extract f64 values from JS objects with a default fallback
@@ -0,0 +1,188 @@
+use crate::PixelFormat;
+
+/// Averages a run of frames to reduce noise from stochastic renderers
+///
+/// Frames are summed per channel into an `f32` buffer until the caller resets the
+/// accumulator, typically when the scene or camera changes.
+pub struct Accumulator {
+    format: PixelFormat,
+    sums: Vec<f32>,
+    frames: u32,
+}
+
+impl Accumulator {
+    pub fn new(width: u32, height: u32, format: PixelFormat) -> Self {
+        let pixels = width as usize * height as usize;
+        Self {
+            format,
+            sums: vec![0.0; pixels * channel_count(format)],
+            frames: 0,
+        }
+    }
+
+    /// Number of frames averaged since the last reset
+    pub fn frame_count(&self) -> u32 {
+        self.frames
+    }
+
+    /// Discard all accumulated frames
+    pub fn reset(&mut self) {
+        self.sums.fill(0.0);
+        self.frames = 0;
+    }
+
+    /// Add a frame in the accumulator's format to the running sum
+    pub fn add(&mut self, frame: &[u8]) {
+        assert_eq!(
+            frame.len() / self.format.bytes_per_pixel() * channel_count(self.format),
+            self.sums.len(),
+            "frame has the wrong size"
+        );
+
+        match self.format {
+            PixelFormat::Rgba8
+            | PixelFormat::Prgb8
+            | PixelFormat::Bgra8
+            | PixelFormat::Argb8
+            | PixelFormat::Abgr8
+            | PixelFormat::Rgb8
+            | PixelFormat::Bgr8
+            | PixelFormat::Gray8
+            | PixelFormat::I420 => {
+                for (sum, &value) in self.sums.iter_mut().zip(frame) {
+                    *sum += value as f32;
+                }
+            }
+            PixelFormat::Rgb565 => {
+                for (sums, pixel) in self.sums.chunks_exact_mut(3).zip(frame.chunks_exact(2)) {
+                    let packed = u16::from_le_bytes([pixel[0], pixel[1]]);
+                    sums[0] += (packed >> 11) as f32;
+                    sums[1] += ((packed >> 5) & 0x3f) as f32;
+                    sums[2] += (packed & 0x1f) as f32;
+                }
+            }
+            PixelFormat::A2Rgb10 => {
+                for (sums, pixel) in self.sums.chunks_exact_mut(4).zip(frame.chunks_exact(4)) {
+                    let packed = u32::from_le_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+                    sums[0] += (packed & 0x3ff) as f32;
+                    sums[1] += ((packed >> 10) & 0x3ff) as f32;
+                    sums[2] += ((packed >> 20) & 0x3ff) as f32;
+                    sums[3] += (packed >> 30) as f32;
+                }
+            }
+            PixelFormat::Rgba16 => {
+                for (sum, bytes) in self.sums.iter_mut().zip(frame.chunks_exact(2)) {
+                    *sum += u16::from_ne_bytes([bytes[0], bytes[1]]) as f32;
+                }
+            }
+        }
+        self.frames += 1;
+    }
+
+    /// Write the rounded average of the accumulated frames into `dst`
+    ///
+    /// Leaves `dst` untouched if no frames have been added.
+    pub fn average_into(&self, dst: &mut [u8]) {
+        if self.frames == 0 {
+            return;
+        }
+        let scale = 1.0 / self.frames as f32;
+
+        match self.format {
+            PixelFormat::Rgba8
+            | PixelFormat::Prgb8
+            | PixelFormat::Bgra8
+            | PixelFormat::Argb8
+            | PixelFormat::Abgr8
+            | PixelFormat::Rgb8
+            | PixelFormat::Bgr8
+            | PixelFormat::Gray8
+            | PixelFormat::I420 => {
+                for (value, &sum) in dst.iter_mut().zip(&self.sums) {
+                    *value = (sum * scale).round() as u8;
+                }
+            }
+            PixelFormat::Rgb565 => {
+                for (pixel, sums) in dst.chunks_exact_mut(2).zip(self.sums.chunks_exact(3)) {
+                    let average = |sum: f32| (sum * scale).round() as u16;
+                    let packed =
+                        (average(sums[0]) << 11) | (average(sums[1]) << 5) | average(sums[2]);
+                    pixel.copy_from_slice(&packed.to_le_bytes());
+                }
+            }
+            PixelFormat::A2Rgb10 => {
+                for (pixel, sums) in dst.chunks_exact_mut(4).zip(self.sums.chunks_exact(4)) {
+                    let average = |sum: f32| (sum * scale).round() as u32;
+                    let packed = (average(sums[3]) << 30)
+                        | (average(sums[2]) << 20)
+                        | (average(sums[1]) << 10)
+                        | average(sums[0]);
+                    pixel.copy_from_slice(&packed.to_le_bytes());
+                }
+            }
+            PixelFormat::Rgba16 => {
+                for (bytes, &sum) in dst.chunks_exact_mut(2).zip(&self.sums) {
+                    let average = (sum * scale).round() as u16;
+                    bytes.copy_from_slice(&average.to_ne_bytes());
+                }
+            }
+        }
+    }
+}
+
+/// Number of separately averaged channels per pixel
+fn channel_count(format: PixelFormat) -> usize {
+    match format {
+        PixelFormat::Rgba8
+        | PixelFormat::Prgb8
+        | PixelFormat::Bgra8
+        | PixelFormat::Argb8
+        | PixelFormat::Abgr8
+        | PixelFormat::A2Rgb10
+        | PixelFormat::Rgba16 => 4,
+        PixelFormat::Rgb565 | PixelFormat::Rgb8 | PixelFormat::Bgr8 => 3,
+        PixelFormat::Gray8 | PixelFormat::I420 => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_black_and_white_average_to_mid_gray() {
+        let mut accumulator = Accumulator::new(2, 1, PixelFormat::Rgba8);
+        accumulator.add(&[0, 0, 0, 255, 0, 0, 0, 255]);
+        accumulator.add(&[255, 255, 255, 255, 255, 255, 255, 255]);
+
+        let mut out = vec![0u8; 8];
+        accumulator.average_into(&mut out);
+
+        assert_eq!(accumulator.frame_count(), 2);
+        assert_eq!(out, vec![128, 128, 128, 255, 128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn test_reset_discards_history() {
+        let mut accumulator = Accumulator::new(1, 1, PixelFormat::Rgba8);
+        accumulator.add(&[200, 200, 200, 255]);
+        accumulator.reset();
+        accumulator.add(&[10, 20, 30, 255]);
+
+        let mut out = [0u8; 4];
+        accumulator.average_into(&mut out);
+        assert_eq!(out, [10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn test_rgb565_averages_per_channel() {
+        let mut accumulator = Accumulator::new(1, 1, PixelFormat::Rgb565);
+        accumulator.add(&0xf800u16.to_le_bytes()); // red
+        accumulator.add(&0x001fu16.to_le_bytes()); // blue
+
+        let mut out = [0u8; 2];
+        accumulator.average_into(&mut out);
+        // 31/2 rounds to 16 for both red and blue
+        assert_eq!(u16::from_le_bytes(out), (16 << 11) | 16);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::yuv::rgba8_to_yuv420;
+use crate::{DisplayBackend, PixelFormat, VideoBufferError};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+/// Container written by a `FileRecorderBackend`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordingFormat {
+    /// Headerless RGBA8 frames back to back, readable with `RawFrameFile`
+    RawRgba,
+    /// YUV4MPEG2 stream of 4:2:0 frames, played back at `fps` frames per second
+    Y4m { fps: u32 },
+}
+
+/// Headless backend that appends every presented frame to a file
+///
+/// Useful for regression tests and rendering demo clips: `init` creates the file and
+/// writes the stream header, each present appends one frame. Writes are buffered, so
+/// call `finish` to flush them and see any error.
+pub struct FileRecorderBackend {
+    path: PathBuf,
+    format: RecordingFormat,
+    writer: Option<BufWriter<File>>,
+    size: (u32, u32),
+    yuv: Vec<u8>,
+    frames_written: u64,
+}
+
+impl FileRecorderBackend {
+    pub fn new(path: impl Into<PathBuf>, format: RecordingFormat) -> Self {
+        Self {
+            path: path.into(),
+            format,
+            writer: None,
+            size: (0, 0),
+            yuv: Vec::new(),
+            frames_written: 0,
+        }
+    }
+
+    /// Number of frames appended since `init`
+    pub fn frames_written(&self) -> u64 {
+        self.frames_written
+    }
+
+    /// Flush buffered frames to disk
+    pub fn finish(&mut self) -> Result<(), VideoBufferError> {
+        let writer = self
+            .writer
+            .as_mut()
+            .ok_or(VideoBufferError::NotInitialized)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl DisplayBackend for FileRecorderBackend {
+    const FORMAT: PixelFormat = PixelFormat::Rgba8;
+
+    fn init(&mut self, width: u32, height: u32) -> Result<(), VideoBufferError> {
+        if let RecordingFormat::Y4m { fps: 0 } = self.format {
+            return Err(VideoBufferError::InitFailed(
+                "y4m frame rate must be positive".to_string(),
+            ));
+        }
+
+        let mut writer = BufWriter::new(File::create(&self.path)?);
+        if let RecordingFormat::Y4m { fps } = self.format {
+            writeln!(
+                writer,
+                "YUV4MPEG2 W{width} H{height} F{fps}:1 Ip A1:1 C420jpeg"
+            )?;
+            self.yuv = vec![0; PixelFormat::I420.buffer_size(width, height)];
+        }
+
+        self.writer = Some(writer);
+        self.size = (width, height);
+        self.frames_written = 0;
+        Ok(())
+    }
+
+    fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        let writer = self
+            .writer
+            .as_mut()
+            .ok_or(VideoBufferError::NotInitialized)?;
+        let (width, height) = self.size;
+        let expected = Self::FORMAT.buffer_size(width, height);
+        if frame.len() != expected {
+            return Err(VideoBufferError::SizeMismatch {
+                expected,
+                actual: frame.len(),
+            });
+        }
+
+        match self.format {
+            RecordingFormat::RawRgba => writer.write_all(frame)?,
+            RecordingFormat::Y4m { .. } => {
+                let luma_len = (width * height) as usize;
+                let (y_plane, chroma) = self.yuv.split_at_mut(luma_len);
+                let (u_plane, v_plane) = chroma.split_at_mut(chroma.len() / 2);
+                rgba8_to_yuv420(frame, y_plane, u_plane, v_plane, width, height);
+
+                writer.write_all(b"FRAME\n")?;
+                writer.write_all(&self.yuv)?;
+            }
+        }
+
+        self.frames_written += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DisplayPresenter;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("video-buffer-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_raw_frames_are_concatenated() {
+        let path = temp_path("recording.raw");
+        let backend = FileRecorderBackend::new(&path, RecordingFormat::RawRgba);
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8).unwrap();
+
+        presenter.present_frame_at(&[1, 2, 3, 4], 0.0).unwrap();
+        presenter.present_frame_at(&[5, 6, 7, 8], 100.0).unwrap();
+        presenter.backend_mut().finish().unwrap();
+
+        assert_eq!(presenter.backend().frames_written(), 2);
+        assert_eq!(std::fs::read(&path).unwrap(), [1, 2, 3, 4, 5, 6, 7, 8]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_tee_records_straight_alpha() {
+        use crate::backends::{memory, MemoryBackend, RecordFormat, TeeBackend};
+
+        let path = temp_path("straight.raw");
+        let tee = TeeBackend::with_record_format(
+            MemoryBackend::<memory::Prgb8>::new(),
+            FileRecorderBackend::new(&path, RecordingFormat::RawRgba),
+            RecordFormat::Straight,
+        );
+        let mut presenter = DisplayPresenter::new(tee, 2, 1, PixelFormat::Prgb8).unwrap();
+
+        presenter
+            .present_frame_at(&[128, 128, 64, 0, 255, 10, 20, 30], 0.0)
+            .unwrap();
+        presenter.backend_mut().second_mut().finish().unwrap();
+
+        assert_eq!(
+            std::fs::read(&path).unwrap(),
+            [255, 128, 0, 128, 10, 20, 30, 255]
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_y4m_header_and_frames() {
+        let path = temp_path("recording.y4m");
+        let mut backend = FileRecorderBackend::new(&path, RecordingFormat::Y4m { fps: 30 });
+        backend.init(3, 1).unwrap();
+        backend.present(&[255; 12]).unwrap();
+        backend.finish().unwrap();
+
+        let mut expected = b"YUV4MPEG2 W3 H1 F30:1 Ip A1:1 C420jpeg\nFRAME\n".to_vec();
+        expected.extend_from_slice(&[235, 235, 235, 128, 128, 128, 128]);
+        assert_eq!(std::fs::read(&path).unwrap(), expected);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_present_without_init_fails() {
+        let mut backend = FileRecorderBackend::new(temp_path("unused"), RecordingFormat::RawRgba);
+        let result = backend.present(&[0; 4]);
+        assert!(matches!(result, Err(VideoBufferError::NotInitialized)));
+    }
+}
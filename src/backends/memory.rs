@@ -0,0 +1,143 @@
+use crate::{DisplayBackend, PixelFormat, VideoBufferError};
+use std::marker::PhantomData;
+
+/// Pixel format accepted by a `MemoryBackend`, chosen with one of the marker types
+/// in this module
+pub trait MemoryFormat {
+    const FORMAT: PixelFormat;
+}
+
+macro_rules! memory_formats {
+    ($($format:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("Makes a `MemoryBackend` accept `PixelFormat::", stringify!($format), "` frames")]
+            #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+            pub struct $format;
+
+            impl MemoryFormat for $format {
+                const FORMAT: PixelFormat = PixelFormat::$format;
+            }
+        )*
+    };
+}
+
+memory_formats!(Rgba8, Prgb8, Rgb565, Rgb8, Bgra8, A2Rgb10, Gray8, Bgr8, Argb8, Abgr8, Rgba16);
+
+/// Headless backend that keeps the last presented frame in memory
+///
+/// Useful for asserting on exact presenter output in tests or capturing frames for
+/// golden-image comparison, e.g. `MemoryBackend::<memory::Prgb8>::new()`.
+pub struct MemoryBackend<F: MemoryFormat = Rgba8> {
+    last_frame: Vec<u8>,
+    present_count: u64,
+    frame_numbers: Vec<u64>,
+    size: Option<(u32, u32)>,
+    format: PhantomData<F>,
+}
+
+impl<F: MemoryFormat> MemoryBackend<F> {
+    pub fn new() -> Self {
+        Self {
+            last_frame: Vec::new(),
+            present_count: 0,
+            frame_numbers: Vec::new(),
+            size: None,
+            format: PhantomData,
+        }
+    }
+
+    /// Bytes of the most recently presented frame, empty before the first present
+    pub fn last_frame(&self) -> &[u8] {
+        &self.last_frame
+    }
+
+    /// Number of frames presented so far
+    pub fn present_count(&self) -> u64 {
+        self.present_count
+    }
+
+    /// Frame numbers passed to `present_numbered`, in the order they were presented
+    pub fn frame_numbers(&self) -> &[u64] {
+        &self.frame_numbers
+    }
+
+    /// Size passed to `init`, if it has been called
+    pub fn size(&self) -> Option<(u32, u32)> {
+        self.size
+    }
+}
+
+impl<F: MemoryFormat> DisplayBackend for MemoryBackend<F> {
+    const FORMAT: PixelFormat = F::FORMAT;
+
+    fn init(&mut self, width: u32, height: u32) -> Result<(), VideoBufferError> {
+        self.size = Some((width, height));
+        Ok(())
+    }
+
+    fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        self.last_frame.clear();
+        self.last_frame.extend_from_slice(frame);
+        self.present_count += 1;
+        Ok(())
+    }
+
+    fn present_numbered(&mut self, frame_no: u64, frame: &[u8]) -> Result<(), VideoBufferError> {
+        self.frame_numbers.push(frame_no);
+        self.present(frame)
+    }
+}
+
+impl<F: MemoryFormat> Default for MemoryBackend<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DisplayPresenter, FrameQueue};
+
+    #[test]
+    fn test_records_converted_frames() {
+        let backend = MemoryBackend::<Rgba8>::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Prgb8).unwrap();
+
+        presenter
+            .present_frame_at(&[255, 10, 20, 30], 10.0)
+            .unwrap();
+
+        assert_eq!(presenter.backend().size(), Some((1, 1)));
+        assert_eq!(presenter.backend().present_count(), 1);
+        assert_eq!(presenter.backend().last_frame(), &[10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn test_records_frame_numbers_in_queue_drain_order() {
+        let mut presenter =
+            DisplayPresenter::new(<MemoryBackend>::new(), 2, 2, PixelFormat::Rgba8).unwrap();
+        let mut queue = FrameQueue::new(8);
+
+        for frame_no in [2, 0, 3, 1] {
+            queue.push(frame_no, vec![frame_no as u8; 16]);
+        }
+
+        let mut now_ms = 100.0;
+        while let Some((frame_no, frame)) = queue.pop_ready_numbered() {
+            presenter
+                .present_numbered_frame(frame_no, &frame, now_ms)
+                .unwrap();
+            now_ms += 16.0;
+        }
+
+        assert_eq!(presenter.backend().frame_numbers(), &[0, 1, 2, 3]);
+        assert_eq!(presenter.backend().last_frame(), &[3u8; 16]);
+    }
+
+    #[test]
+    fn test_format_marker_selects_backend_format() {
+        assert_eq!(MemoryBackend::<Rgb565>::FORMAT, PixelFormat::Rgb565);
+        assert_eq!(<MemoryBackend>::FORMAT, PixelFormat::Rgba8);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{DisplayBackend, PixelFormat, VideoBufferError};
+use minifb::{Window, WindowOptions};
+
+/// Dependency-light backend that opens its own window with `minifb` and blits into it
+///
+/// The window is created by `init` at the frame size and pumps its events on every
+/// present; poll `is_open` to find out when the user has closed it.
+pub struct MinifbBackend {
+    title: String,
+    options: WindowOptions,
+    window: Option<Window>,
+    pixels: Vec<u32>,
+    width: usize,
+    height: usize,
+}
+
+impl MinifbBackend {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            options: WindowOptions::default(),
+            window: None,
+            pixels: Vec::new(),
+            width: 0,
+            height: 0,
+        }
+    }
+
+    /// Window options such as resizability or scaling, used when `init` opens the window
+    pub fn with_window_options(mut self, options: WindowOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Whether the window is open; `false` before `init` and once the user closed it
+    pub fn is_open(&self) -> bool {
+        self.window.as_ref().is_some_and(|window| window.is_open())
+    }
+
+    /// The underlying window, e.g. for keyboard and mouse input
+    pub fn window(&self) -> Option<&Window> {
+        self.window.as_ref()
+    }
+}
+
+impl DisplayBackend for MinifbBackend {
+    /// minifb takes 0RGB words, which are BGRA bytes on little-endian machines
+    const FORMAT: PixelFormat = PixelFormat::Bgra8;
+
+    fn init(&mut self, width: u32, height: u32) -> Result<(), VideoBufferError> {
+        let window = Window::new(&self.title, width as usize, height as usize, self.options)
+            .map_err(|e| {
+                VideoBufferError::InitFailed(format!("Failed to open minifb window: {}", e))
+            })?;
+
+        self.window = Some(window);
+        (self.width, self.height) = (width as usize, height as usize);
+        self.pixels = vec![0; self.width * self.height];
+        Ok(())
+    }
+
+    fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        let window = self
+            .window
+            .as_mut()
+            .ok_or(VideoBufferError::NotInitialized)?;
+        if frame.len() != self.pixels.len() * 4 {
+            return Err(VideoBufferError::SizeMismatch {
+                expected: self.pixels.len() * 4,
+                actual: frame.len(),
+            });
+        }
+
+        for (pixel, bytes) in self.pixels.iter_mut().zip(frame.chunks_exact(4)) {
+            *pixel = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]);
+        }
+
+        window
+            .update_with_buffer(&self.pixels, self.width, self.height)
+            .map_err(|e| VideoBufferError::PresentFailed(format!("Present failed: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_creation() {
+        let backend = MinifbBackend::new("video-buffer");
+        assert!(backend.window().is_none());
+        assert!(!backend.is_open());
+    }
+
+    #[test]
+    fn test_present_without_init_fails() {
+        let mut backend = MinifbBackend::new("video-buffer");
+        let frame = vec![0u8; 640 * 480 * 4];
+        let result = backend.present(&frame);
+        assert!(matches!(result, Err(VideoBufferError::NotInitialized)));
+    }
+}
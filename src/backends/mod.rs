@@ -1,11 +1,41 @@
+pub mod memory;
+pub mod tee;
+
+pub use memory::MemoryBackend;
+pub use tee::{RecordFormat, TeeBackend};
+
 #[cfg(feature = "pixels-backend")]
 pub mod pixels;
 
 #[cfg(feature = "pixels-backend")]
 pub use pixels::PixelsBackend;
 
+#[cfg(feature = "softbuffer-backend")]
+pub mod softbuffer;
+
+#[cfg(feature = "softbuffer-backend")]
+pub use softbuffer::SoftbufferBackend;
+
+#[cfg(feature = "minifb-backend")]
+pub mod minifb;
+
+#[cfg(feature = "minifb-backend")]
+pub use minifb::MinifbBackend;
+
+#[cfg(feature = "wgpu-backend")]
+pub mod wgpu;
+
+#[cfg(feature = "wgpu-backend")]
+pub use self::wgpu::WgpuTextureBackend;
+
+#[cfg(feature = "file-recorder")]
+pub mod file_recorder;
+
+#[cfg(feature = "file-recorder")]
+pub use file_recorder::{FileRecorderBackend, RecordingFormat};
+
 #[cfg(feature = "wasm-canvas-backend")]
 pub mod wasm_canvas;
 
 #[cfg(feature = "wasm-canvas-backend")]
-pub use wasm_canvas::WasmCanvasBackend;
+pub use wasm_canvas::{WasmCanvasBackend, WasmOffscreenCanvasBackend};
@@ -1,14 +1,41 @@
-use crate::{DisplayBackend, PixelFormat, VideoBufferError};
-use pixels::{Pixels, SurfaceTexture};
+use crate::{DisplayBackend, PixelFormat, Rect, VideoBufferError};
+use pixels::wgpu::{Maintain, PresentMode, SurfaceError, TextureFormat};
+use pixels::{Pixels, PixelsBuilder, SurfaceTexture, TextureError};
 use winit::window::Window;
 
+/// Presents through `pixels` into a winit window
+///
+/// # Surface loss
+///
+/// When rendering fails because the surface was lost or is outdated (`SurfaceError::Lost`
+/// or `SurfaceError::Outdated`, e.g. after a GPU switch or display hot-plug), the surface
+/// is reconfigured at its last known size and the same frame is rendered once more. Only
+/// if that retry fails too does the present return `PresentFailed`. Other errors, such as
+/// timeouts or running out of memory, fail right away.
 pub struct PixelsBackend<'win> {
     pixels: Option<Pixels<'win>>,
+    vsync: bool,
+    width: u32,
+    /// Size the surface was last configured at, used to reconfigure it after a loss
+    surface_size: (u32, u32),
 }
 
 impl<'win> PixelsBackend<'win> {
     pub fn new() -> Self {
-        Self { pixels: None }
+        Self {
+            pixels: None,
+            vsync: false,
+            width: 0,
+            surface_size: (0, 0),
+        }
+    }
+
+    /// Use `PresentMode::Fifo` so presenting waits for the display's vertical blank
+    ///
+    /// Must be set before `init_with_window()`.
+    pub fn with_vsync(mut self, vsync: bool) -> Self {
+        self.vsync = vsync;
+        self
     }
 
     pub fn init_with_window(
@@ -20,12 +47,90 @@ impl<'win> PixelsBackend<'win> {
         let window_size = window.inner_size();
         let surface_texture = SurfaceTexture::new(window_size.width, window_size.height, window);
 
-        let pixels = Pixels::new(width, height, surface_texture)
+        let mut builder = PixelsBuilder::new(width, height, surface_texture);
+        if self.vsync {
+            builder = builder.present_mode(PresentMode::Fifo);
+        }
+        let pixels = builder
+            .build()
             .map_err(|e| VideoBufferError::InitFailed(format!("Failed to create Pixels: {}", e)))?;
 
         self.pixels = Some(pixels);
+        self.width = width;
+        self.surface_size = (window_size.width, window_size.height);
+        Ok(())
+    }
+
+    /// Resize the window surface, e.g. from a winit `WindowEvent::Resized` handler
+    ///
+    /// Only the surface changes: frames keep the size given to `init_with_window`, and
+    /// `pixels` scales them to fit the new surface.
+    pub fn resize_surface(&mut self, width: u32, height: u32) -> Result<(), VideoBufferError> {
+        let pixels = self
+            .pixels
+            .as_mut()
+            .ok_or(VideoBufferError::NotInitialized)?;
+
+        pixels.resize_surface(width, height).map_err(|e| {
+            VideoBufferError::PresentFailed(format!("Failed to resize surface: {}", e))
+        })?;
+        self.surface_size = (width, height);
         Ok(())
     }
+
+    /// Let `fill` write an RGBA8 frame straight into the `pixels` frame buffer, then render it
+    ///
+    /// Skips the copy `present` makes, for renderers that already produce RGBA8.
+    pub fn present_in_place<F: FnOnce(&mut [u8])>(
+        &mut self,
+        fill: F,
+    ) -> Result<(), VideoBufferError> {
+        let pixels = self
+            .pixels
+            .as_mut()
+            .ok_or(VideoBufferError::NotInitialized)?;
+
+        fill(pixels.frame_mut());
+
+        render(pixels, self.surface_size)
+    }
+}
+
+/// Render the frame buffer, reconfiguring a lost or outdated surface and retrying once
+fn render(pixels: &mut Pixels<'_>, (width, height): (u32, u32)) -> Result<(), VideoBufferError> {
+    render_with_retry(
+        pixels,
+        |pixels| pixels.render(),
+        |pixels| pixels.resize_surface(width, height),
+    )
+}
+
+/// The retry behind `render`, with rendering and reconfiguring passed in so it can be
+/// tested without a GPU
+fn render_with_retry<T>(
+    target: &mut T,
+    render: impl Fn(&T) -> Result<(), pixels::Error>,
+    reconfigure: impl FnOnce(&mut T) -> Result<(), TextureError>,
+) -> Result<(), VideoBufferError> {
+    match render(target) {
+        Err(pixels::Error::Surface(SurfaceError::Lost | SurfaceError::Outdated)) => {
+            reconfigure(target).map_err(|e| {
+                VideoBufferError::PresentFailed(format!(
+                    "Failed to reconfigure lost surface: {}",
+                    e
+                ))
+            })?;
+            render(target).map_err(|e| {
+                VideoBufferError::PresentFailed(format!(
+                    "Render failed after reconfiguring the surface: {}",
+                    e
+                ))
+            })
+        }
+        result => {
+            result.map_err(|e| VideoBufferError::PresentFailed(format!("Render failed: {}", e)))
+        }
+    }
 }
 
 impl<'win> DisplayBackend for PixelsBackend<'win> {
@@ -42,6 +147,26 @@ impl<'win> DisplayBackend for PixelsBackend<'win> {
         ))
     }
 
+    /// Format of the texture `pixels` uploads frames into
+    fn surface_format(&self) -> Option<PixelFormat> {
+        match self.pixels.as_ref()?.texture().format() {
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => Some(PixelFormat::Rgba8),
+            _ => None,
+        }
+    }
+
+    /// Sets the color `pixels` draws around the scaled frame (the letterbox area)
+    fn clear(&mut self, color: [u8; 4]) -> Result<(), VideoBufferError> {
+        let pixels = self
+            .pixels
+            .as_mut()
+            .ok_or(VideoBufferError::NotInitialized)?;
+
+        let [r, g, b, a] = color.map(|c| c as f64 / 255.0);
+        pixels.clear_color(pixels::wgpu::Color { r, g, b, a });
+        Ok(())
+    }
+
     fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
         let pixels = self
             .pixels
@@ -49,12 +174,54 @@ impl<'win> DisplayBackend for PixelsBackend<'win> {
             .ok_or(VideoBufferError::NotInitialized)?;
 
         let pixels_frame = pixels.frame_mut();
+        if pixels_frame.len() != frame.len() {
+            return Err(VideoBufferError::SizeMismatch {
+                expected: pixels_frame.len(),
+                actual: frame.len(),
+            });
+        }
         pixels_frame.copy_from_slice(frame);
 
-        pixels
-            .render()
-            .map_err(|e| VideoBufferError::PresentFailed(format!("Render failed: {}", e)))?;
+        render(pixels, self.surface_size)
+    }
 
+    /// Copies only the rows of `rect` into the `pixels` frame buffer before rendering
+    ///
+    /// The rest of the frame buffer still holds the previous frame. `pixels` uploads the
+    /// whole texture either way, so this saves the CPU-side copy.
+    fn present_region(&mut self, frame: &[u8], rect: Rect) -> Result<(), VideoBufferError> {
+        let stride = Self::FORMAT.stride(self.width);
+        let pixels = self
+            .pixels
+            .as_mut()
+            .ok_or(VideoBufferError::NotInitialized)?;
+
+        let pixels_frame = pixels.frame_mut();
+        if pixels_frame.len() != frame.len() {
+            return Err(VideoBufferError::SizeMismatch {
+                expected: pixels_frame.len(),
+                actual: frame.len(),
+            });
+        }
+        for y in rect.y..rect.y + rect.height {
+            let row = Self::FORMAT.pixel_offset(rect.x, y, stride)
+                ..Self::FORMAT.pixel_offset(rect.x + rect.width, y, stride);
+            pixels_frame[row.clone()].copy_from_slice(&frame[row]);
+        }
+
+        render(pixels, self.surface_size)
+    }
+
+    /// Renders, then waits for the GPU to finish the submitted work
+    ///
+    /// With `with_vsync(true)` the Fifo swap chain also throttles each present to the
+    /// display refresh, so a render loop calling this is paced by the monitor.
+    fn present_blocking(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        self.present(frame)?;
+
+        if let Some(pixels) = &self.pixels {
+            let _ = pixels.device().poll(Maintain::Wait);
+        }
         Ok(())
     }
 }
@@ -68,11 +235,80 @@ impl<'win> Default for PixelsBackend<'win> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::Cell;
+
+    /// Surface whose first renders fail with `error`
+    struct FakeSurface {
+        error: SurfaceError,
+        failures_left: Cell<u32>,
+        renders: Cell<u32>,
+        reconfigures: u32,
+    }
+
+    impl FakeSurface {
+        fn new(error: SurfaceError, failures: u32) -> Self {
+            Self {
+                error,
+                failures_left: Cell::new(failures),
+                renders: Cell::new(0),
+                reconfigures: 0,
+            }
+        }
+
+        fn render(&self) -> Result<(), pixels::Error> {
+            self.renders.set(self.renders.get() + 1);
+            match self.failures_left.get() {
+                0 => Ok(()),
+                n => {
+                    self.failures_left.set(n - 1);
+                    Err(pixels::Error::Surface(self.error.clone()))
+                }
+            }
+        }
+
+        fn render_with_retry(&mut self) -> Result<(), VideoBufferError> {
+            render_with_retry(self, FakeSurface::render, |surface| {
+                surface.reconfigures += 1;
+                Ok(())
+            })
+        }
+    }
+
+    #[test]
+    fn test_lost_and_outdated_surfaces_are_reconfigured_and_retried() {
+        for error in [SurfaceError::Lost, SurfaceError::Outdated] {
+            let mut surface = FakeSurface::new(error, 1);
+
+            assert!(surface.render_with_retry().is_ok());
+            assert_eq!((surface.renders.get(), surface.reconfigures), (2, 1));
+        }
+    }
+
+    #[test]
+    fn test_surface_lost_again_after_retry_fails() {
+        let mut surface = FakeSurface::new(SurfaceError::Lost, 2);
+
+        let result = surface.render_with_retry();
+
+        assert!(matches!(result, Err(VideoBufferError::PresentFailed(_))));
+        assert_eq!((surface.renders.get(), surface.reconfigures), (2, 1));
+    }
+
+    #[test]
+    fn test_other_surface_errors_fail_without_retry() {
+        let mut surface = FakeSurface::new(SurfaceError::Timeout, 1);
+
+        let result = surface.render_with_retry();
+
+        assert!(matches!(result, Err(VideoBufferError::PresentFailed(_))));
+        assert_eq!((surface.renders.get(), surface.reconfigures), (1, 0));
+    }
 
     #[test]
     fn test_backend_creation() {
         let backend = PixelsBackend::new();
         assert!(backend.pixels.is_none());
+        assert!(!backend.vsync);
     }
 
     #[test]
@@ -89,4 +325,18 @@ mod tests {
         let result = backend.present(&frame);
         assert!(matches!(result, Err(VideoBufferError::NotInitialized)));
     }
+
+    #[test]
+    fn test_present_in_place_without_init_fails() {
+        let mut backend = PixelsBackend::new();
+        let result = backend.present_in_place(|_| panic!("no frame buffer to fill"));
+        assert!(matches!(result, Err(VideoBufferError::NotInitialized)));
+    }
+
+    #[test]
+    fn test_resize_surface_without_init_fails() {
+        let mut backend = PixelsBackend::new();
+        let result = backend.resize_surface(800, 600);
+        assert!(matches!(result, Err(VideoBufferError::NotInitialized)));
+    }
 }
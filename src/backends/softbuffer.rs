@@ -0,0 +1,133 @@
+use crate::{DisplayBackend, PixelFormat, VideoBufferError};
+use softbuffer::{Context, Surface};
+use std::num::NonZeroU32;
+use winit::window::Window;
+
+/// CPU-only backend that blits frames into a window with `softbuffer`
+///
+/// The surface is sized to the frame, not the window, so frames are shown unscaled
+/// in the window's top-left corner. Frames must be in a one-byte-per-channel format
+/// such as `Rgba8` or `Rgb8`; presenters for other source formats fail to build.
+pub struct SoftbufferBackend<'win> {
+    surface: Option<Surface<&'win Window, &'win Window>>,
+}
+
+impl<'win> SoftbufferBackend<'win> {
+    pub fn new() -> Self {
+        Self { surface: None }
+    }
+
+    pub fn init_with_window(
+        &mut self,
+        width: u32,
+        height: u32,
+        window: &'win Window,
+    ) -> Result<(), VideoBufferError> {
+        let (Some(width), Some(height)) = (NonZeroU32::new(width), NonZeroU32::new(height)) else {
+            return Err(VideoBufferError::InitFailed(format!(
+                "Invalid surface size {}x{}",
+                width, height
+            )));
+        };
+
+        let context = Context::new(window).map_err(|e| {
+            VideoBufferError::InitFailed(format!("Failed to create softbuffer context: {}", e))
+        })?;
+        let mut surface = Surface::new(&context, window).map_err(|e| {
+            VideoBufferError::InitFailed(format!("Failed to create softbuffer surface: {}", e))
+        })?;
+        surface.resize(width, height).map_err(|e| {
+            VideoBufferError::InitFailed(format!("Failed to size softbuffer surface: {}", e))
+        })?;
+
+        self.surface = Some(surface);
+        Ok(())
+    }
+}
+
+impl<'win> DisplayBackend for SoftbufferBackend<'win> {
+    const FORMAT: PixelFormat = PixelFormat::Bgra8;
+
+    fn init(&mut self, _width: u32, _height: u32) -> Result<(), VideoBufferError> {
+        // Init is idempotent - if already initialized via init_with_window(), do nothing
+        if self.surface.is_some() {
+            return Ok(());
+        }
+
+        Err(VideoBufferError::InitFailed(
+            "SoftbufferBackend requires init_with_window() to be called before use".to_string(),
+        ))
+    }
+
+    fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        let surface = self
+            .surface
+            .as_mut()
+            .ok_or(VideoBufferError::NotInitialized)?;
+
+        let mut buffer = surface
+            .buffer_mut()
+            .map_err(|e| VideoBufferError::PresentFailed(format!("Buffer lock failed: {}", e)))?;
+        if frame.len() != buffer.len() * 4 {
+            return Err(VideoBufferError::SizeMismatch {
+                expected: buffer.len() * 4,
+                actual: frame.len(),
+            });
+        }
+
+        for (pixel, bytes) in buffer.iter_mut().zip(frame.chunks_exact(4)) {
+            // softbuffer expects 0RGB words, so the alpha byte must stay clear
+            *pixel = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]);
+        }
+
+        buffer
+            .present()
+            .map_err(|e| VideoBufferError::PresentFailed(format!("Present failed: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+impl<'win> Default for SoftbufferBackend<'win> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DisplayPresenter;
+
+    #[test]
+    fn test_formats_without_bgra8_conversion_are_rejected() {
+        for format in [
+            PixelFormat::Gray8,
+            PixelFormat::Rgb565,
+            PixelFormat::Rgba16,
+            PixelFormat::A2Rgb10,
+        ] {
+            // Rejected before the backend, which has no window here, is initialized
+            let result = DisplayPresenter::new(SoftbufferBackend::new(), 2, 2, format);
+            let Err(VideoBufferError::InitFailed(message)) = result else {
+                panic!("{:?} frames were accepted", format);
+            };
+            assert!(message.contains("can't be converted"), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_init_without_window_fails() {
+        let mut backend = SoftbufferBackend::new();
+        let result = backend.init(640, 480);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_present_without_init_fails() {
+        let mut backend = SoftbufferBackend::new();
+        let frame = vec![0u8; 640 * 480 * 4];
+        let result = backend.present(&frame);
+        assert!(matches!(result, Err(VideoBufferError::NotInitialized)));
+    }
+}
@@ -0,0 +1,279 @@
+use crate::convert::{convert, unpremultiply_rgba8};
+use crate::{DisplayBackend, PixelFormat, Rect, VideoBufferError};
+
+/// Frames handed to the second (recording) backend of a `TeeBackend`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// The same bytes the first backend gets
+    #[default]
+    Display,
+    /// Straight-alpha RGBA8, unpremultiplied from the first backend's premultiplied frames
+    ///
+    /// Recorders and image encoders usually want straight alpha even when the display
+    /// composites premultiplied pixels.
+    Straight,
+}
+
+/// Backend that forwards every call to two backends, e.g. a window and a recorder
+///
+/// By default both backends must take the same `FORMAT`, so each frame is converted
+/// once. With `RecordFormat::Straight` the second backend takes `Rgba8` and gets its
+/// own unpremultiplied copy of every frame. A failure in one backend doesn't stop the
+/// other from being called; the returned error names the backend(s) that failed.
+pub struct TeeBackend<A: DisplayBackend, B: DisplayBackend> {
+    first: A,
+    second: B,
+    record_format: RecordFormat,
+    record_buffer: Vec<u8>,
+}
+
+impl<A: DisplayBackend, B: DisplayBackend> TeeBackend<A, B> {
+    /// Panics if the backends declare different formats
+    pub fn new(first: A, second: B) -> Self {
+        Self::with_record_format(first, second, RecordFormat::Display)
+    }
+
+    /// Panics if the second backend can't take frames in `record_format`
+    pub fn with_record_format(first: A, second: B, record_format: RecordFormat) -> Self {
+        match record_format {
+            RecordFormat::Display => assert_eq!(
+                A::FORMAT,
+                B::FORMAT,
+                "TeeBackend needs both backends to take the same format"
+            ),
+            RecordFormat::Straight => assert_eq!(
+                B::FORMAT,
+                PixelFormat::Rgba8,
+                "straight-alpha recording needs an Rgba8 second backend"
+            ),
+        }
+
+        Self {
+            first,
+            second,
+            record_format,
+            record_buffer: Vec::new(),
+        }
+    }
+
+    pub fn record_format(&self) -> RecordFormat {
+        self.record_format
+    }
+
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    pub fn first_mut(&mut self) -> &mut A {
+        &mut self.first
+    }
+
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    pub fn second_mut(&mut self) -> &mut B {
+        &mut self.second
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+/// `frame` as the second backend takes it, converted into `buffer` if needed
+fn record_frame<'a>(
+    frame: &'a [u8],
+    display_format: PixelFormat,
+    record_format: RecordFormat,
+    buffer: &'a mut Vec<u8>,
+) -> &'a [u8] {
+    match record_format {
+        RecordFormat::Display => frame,
+        RecordFormat::Straight => {
+            let pixels = frame.len() / display_format.bytes_per_pixel();
+            buffer.resize(PixelFormat::Rgba8.bytes_per_pixel() * pixels, 0);
+            convert(frame, buffer, display_format, PixelFormat::Rgba8);
+            unpremultiply_rgba8(buffer);
+            buffer
+        }
+    }
+}
+
+/// Merge the results of calling both backends into one
+fn combine(
+    first: Result<(), VideoBufferError>,
+    second: Result<(), VideoBufferError>,
+    wrap: fn(String) -> VideoBufferError,
+) -> Result<(), VideoBufferError> {
+    match (first, second) {
+        (Ok(()), Ok(())) => Ok(()),
+        (Err(e), Ok(())) => Err(wrap(format!("first backend: {}", e))),
+        (Ok(()), Err(e)) => Err(wrap(format!("second backend: {}", e))),
+        (Err(a), Err(b)) => Err(wrap(format!("first backend: {}; second backend: {}", a, b))),
+    }
+}
+
+impl<A: DisplayBackend, B: DisplayBackend> DisplayBackend for TeeBackend<A, B> {
+    const FORMAT: PixelFormat = A::FORMAT;
+
+    fn init(&mut self, width: u32, height: u32) -> Result<(), VideoBufferError> {
+        let first = self.first.init(width, height);
+        let second = self.second.init(width, height);
+        combine(first, second, VideoBufferError::InitFailed)
+    }
+
+    fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        let first = self.first.present(frame);
+        let recorded = record_frame(
+            frame,
+            A::FORMAT,
+            self.record_format,
+            &mut self.record_buffer,
+        );
+        let second = self.second.present(recorded);
+        combine(first, second, VideoBufferError::PresentFailed)
+    }
+
+    /// The first surface that doesn't match the format its backend is fed, if any
+    fn surface_format(&self) -> Option<PixelFormat> {
+        let first = self.first.surface_format();
+        match first {
+            Some(format) if format != Self::FORMAT => first,
+            _ => match self.second.surface_format() {
+                Some(format) if format != B::FORMAT => Some(format),
+                _ => first,
+            },
+        }
+    }
+
+    fn clear(&mut self, color: [u8; 4]) -> Result<(), VideoBufferError> {
+        let first = self.first.clear(color);
+        let second = self.second.clear(color);
+        combine(first, second, VideoBufferError::PresentFailed)
+    }
+
+    fn present_blocking(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        let first = self.first.present_blocking(frame);
+        let recorded = record_frame(
+            frame,
+            A::FORMAT,
+            self.record_format,
+            &mut self.record_buffer,
+        );
+        let second = self.second.present_blocking(recorded);
+        combine(first, second, VideoBufferError::PresentFailed)
+    }
+
+    fn present_numbered(&mut self, frame_no: u64, frame: &[u8]) -> Result<(), VideoBufferError> {
+        let first = self.first.present_numbered(frame_no, frame);
+        let recorded = record_frame(
+            frame,
+            A::FORMAT,
+            self.record_format,
+            &mut self.record_buffer,
+        );
+        let second = self.second.present_numbered(frame_no, recorded);
+        combine(first, second, VideoBufferError::PresentFailed)
+    }
+
+    fn present_region(&mut self, frame: &[u8], rect: Rect) -> Result<(), VideoBufferError> {
+        let first = self.first.present_region(frame, rect);
+        let recorded = record_frame(
+            frame,
+            A::FORMAT,
+            self.record_format,
+            &mut self.record_buffer,
+        );
+        let second = self.second.present_region(recorded, rect);
+        combine(first, second, VideoBufferError::PresentFailed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::{memory, MemoryBackend};
+    use crate::DisplayPresenter;
+
+    /// Rgba8 backend whose presents always fail
+    struct FailingBackend;
+
+    impl DisplayBackend for FailingBackend {
+        const FORMAT: PixelFormat = PixelFormat::Rgba8;
+
+        fn init(&mut self, _width: u32, _height: u32) -> Result<(), VideoBufferError> {
+            Ok(())
+        }
+
+        fn present(&mut self, _frame: &[u8]) -> Result<(), VideoBufferError> {
+            Err(VideoBufferError::PresentFailed("surface lost".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_presents_to_both_backends() {
+        let tee = TeeBackend::new(
+            MemoryBackend::<memory::Rgba8>::new(),
+            <MemoryBackend>::new(),
+        );
+        let mut presenter = DisplayPresenter::new(tee, 1, 1, PixelFormat::Prgb8).unwrap();
+
+        presenter.present_frame_at(&[255, 1, 2, 3], 0.0).unwrap();
+
+        let tee = presenter.backend();
+        assert_eq!(tee.first().last_frame(), &[1, 2, 3, 255]);
+        assert_eq!(tee.second().last_frame(), &[1, 2, 3, 255]);
+    }
+
+    #[test]
+    fn test_failure_still_presents_other_backend() {
+        let mut tee = TeeBackend::new(FailingBackend, MemoryBackend::<memory::Rgba8>::new());
+        tee.init(1, 1).unwrap();
+
+        let err = tee.present(&[1, 2, 3, 4]).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Present failed: first backend: Present failed: surface lost"
+        );
+        assert_eq!(tee.second().present_count(), 1);
+    }
+
+    #[test]
+    fn test_straight_record_format_unpremultiplies_second_backend() {
+        let tee = TeeBackend::with_record_format(
+            MemoryBackend::<memory::Prgb8>::new(),
+            <MemoryBackend>::new(),
+            RecordFormat::Straight,
+        );
+        let mut presenter = DisplayPresenter::new(tee, 2, 1, PixelFormat::Prgb8).unwrap();
+
+        // 50% alpha orange, premultiplied, and a transparent pixel with stray color
+        let frame = [128, 128, 64, 0, 0, 9, 9, 9];
+        presenter.present_frame_at(&frame, 0.0).unwrap();
+
+        let tee = presenter.backend();
+        assert_eq!(tee.first().last_frame(), &frame);
+        assert_eq!(tee.second().last_frame(), &[255, 128, 0, 128, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Rgba8 second backend")]
+    fn test_straight_record_format_needs_rgba8() {
+        TeeBackend::with_record_format(
+            MemoryBackend::<memory::Prgb8>::new(),
+            MemoryBackend::<memory::Bgra8>::new(),
+            RecordFormat::Straight,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "same format")]
+    fn test_mismatched_formats_panic() {
+        TeeBackend::new(
+            MemoryBackend::<memory::Rgba8>::new(),
+            MemoryBackend::<memory::Bgra8>::new(),
+        );
+    }
+}
@@ -1,54 +1,300 @@
+use crate::convert::unpremultiply_rgba8;
 use crate::{DisplayBackend, PixelFormat, VideoBufferError};
-use wasm_bindgen::Clamped;
-use web_sys::{CanvasRenderingContext2d, ImageData};
+use wasm_bindgen::{Clamped, JsValue};
+use web_sys::{CanvasRenderingContext2d, ImageData, OffscreenCanvasRenderingContext2d};
 
 /// Display backend for WASM using HTML Canvas 2D context
 ///
 /// This backend blits RGBA8 pixel data directly to a canvas element
 /// using the Canvas 2D API's ImageData and putImageData methods.
+/// ImageData expects straight (non-premultiplied) alpha; renderers producing
+/// premultiplied output should enable `with_unpremultiply`.
 pub struct WasmCanvasBackend {
-    ctx: CanvasRenderingContext2d,
-    width: u32,
-    height: u32,
+    blitter: CanvasBlitter<CanvasRenderingContext2d>,
 }
 
 impl WasmCanvasBackend {
     /// Create a new WasmCanvasBackend with the given 2D rendering context
     pub fn new(ctx: CanvasRenderingContext2d) -> Self {
         Self {
-            ctx,
-            width: 0,
-            height: 0,
+            blitter: CanvasBlitter::new(ctx),
         }
     }
+
+    /// Unpremultiply frames before upload, for renderers that output premultiplied alpha
+    pub fn with_unpremultiply(mut self, enabled: bool) -> Self {
+        self.blitter.unpremultiply = enabled;
+        self
+    }
+
+    /// Reset the whole canvas to transparent before each frame is drawn
+    ///
+    /// `put_image_data` replaces the pixels it covers, so this only matters where the
+    /// canvas is larger than the frame, e.g. after a resize. A presenter clear color set
+    /// with `with_clear_color` takes precedence.
+    pub fn with_clear_before_present(mut self, enabled: bool) -> Self {
+        self.blitter.clear_before_present = enabled;
+        self
+    }
+
+    /// Draw frames with their top-left corner at (`x`, `y`) on the canvas
+    ///
+    /// Lets several backends composite onto one canvas; leave `with_clear_before_present`
+    /// off for those, as it clears the whole canvas. Presenting fails with
+    /// `PresentFailed` if the frame doesn't fit the canvas at this offset.
+    pub fn with_offset(mut self, x: f64, y: f64) -> Self {
+        self.blitter.offset = Some((x, y));
+        self
+    }
 }
 
 impl DisplayBackend for WasmCanvasBackend {
     const FORMAT: PixelFormat = PixelFormat::Rgba8;
 
     fn init(&mut self, width: u32, height: u32) -> Result<(), VideoBufferError> {
+        self.blitter.init(width, height);
+        Ok(())
+    }
+
+    /// `put_image_data` always takes non-premultiplied RGBA
+    fn surface_format(&self) -> Option<PixelFormat> {
+        Some(PixelFormat::Rgba8)
+    }
+
+    fn clear(&mut self, color: [u8; 4]) -> Result<(), VideoBufferError> {
+        self.blitter.clear(color);
+        Ok(())
+    }
+
+    fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        self.blitter.present(frame)
+    }
+}
+
+/// `WasmCanvasBackend` for an `OffscreenCanvas`, so a worker can present on its own
+///
+/// Get the context from a canvas handed over with `transferControlToOffscreen`; frames
+/// then reach the page without a round trip through the main thread.
+pub struct WasmOffscreenCanvasBackend {
+    blitter: CanvasBlitter<OffscreenCanvasRenderingContext2d>,
+}
+
+impl WasmOffscreenCanvasBackend {
+    /// Create a new WasmOffscreenCanvasBackend with the given 2D rendering context
+    pub fn new(ctx: OffscreenCanvasRenderingContext2d) -> Self {
+        Self {
+            blitter: CanvasBlitter::new(ctx),
+        }
+    }
+
+    /// Unpremultiply frames before upload, for renderers that output premultiplied alpha
+    pub fn with_unpremultiply(mut self, enabled: bool) -> Self {
+        self.blitter.unpremultiply = enabled;
+        self
+    }
+
+    /// Reset the whole canvas to transparent before each frame is drawn
+    ///
+    /// See `WasmCanvasBackend::with_clear_before_present`.
+    pub fn with_clear_before_present(mut self, enabled: bool) -> Self {
+        self.blitter.clear_before_present = enabled;
+        self
+    }
+
+    /// Draw frames with their top-left corner at (`x`, `y`) on the canvas
+    ///
+    /// See `WasmCanvasBackend::with_offset`.
+    pub fn with_offset(mut self, x: f64, y: f64) -> Self {
+        self.blitter.offset = Some((x, y));
+        self
+    }
+}
+
+impl DisplayBackend for WasmOffscreenCanvasBackend {
+    const FORMAT: PixelFormat = PixelFormat::Rgba8;
+
+    fn init(&mut self, width: u32, height: u32) -> Result<(), VideoBufferError> {
+        self.blitter.init(width, height);
+        Ok(())
+    }
+
+    /// `put_image_data` always takes non-premultiplied RGBA
+    fn surface_format(&self) -> Option<PixelFormat> {
+        Some(PixelFormat::Rgba8)
+    }
+
+    fn clear(&mut self, color: [u8; 4]) -> Result<(), VideoBufferError> {
+        self.blitter.clear(color);
+        Ok(())
+    }
+
+    fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        self.blitter.present(frame)
+    }
+}
+
+/// The calls the blitter needs from either kind of 2D context
+trait Context2d {
+    fn canvas_size(&self) -> Option<(u32, u32)>;
+    fn fill_area(&self, style: &str, width: f64, height: f64);
+    fn clear_area(&self, width: f64, height: f64);
+    fn put_image(&self, image_data: &ImageData, x: f64, y: f64) -> Result<(), JsValue>;
+}
+
+impl Context2d for CanvasRenderingContext2d {
+    fn canvas_size(&self) -> Option<(u32, u32)> {
+        self.canvas()
+            .map(|canvas| (canvas.width(), canvas.height()))
+    }
+
+    fn fill_area(&self, style: &str, width: f64, height: f64) {
+        self.set_fill_style_str(style);
+        self.fill_rect(0.0, 0.0, width, height);
+    }
+
+    fn clear_area(&self, width: f64, height: f64) {
+        self.clear_rect(0.0, 0.0, width, height);
+    }
+
+    fn put_image(&self, image_data: &ImageData, x: f64, y: f64) -> Result<(), JsValue> {
+        self.put_image_data(image_data, x, y)
+    }
+}
+
+impl Context2d for OffscreenCanvasRenderingContext2d {
+    fn canvas_size(&self) -> Option<(u32, u32)> {
+        let canvas = self.canvas();
+        Some((canvas.width(), canvas.height()))
+    }
+
+    fn fill_area(&self, style: &str, width: f64, height: f64) {
+        self.set_fill_style_str(style);
+        self.fill_rect(0.0, 0.0, width, height);
+    }
+
+    fn clear_area(&self, width: f64, height: f64) {
+        self.clear_rect(0.0, 0.0, width, height);
+    }
+
+    fn put_image(&self, image_data: &ImageData, x: f64, y: f64) -> Result<(), JsValue> {
+        self.put_image_data(image_data, x, y)
+    }
+}
+
+/// Present logic shared by the on-screen and offscreen canvas backends
+struct CanvasBlitter<C> {
+    ctx: C,
+    width: u32,
+    height: u32,
+    unpremultiply: bool,
+    clear_before_present: bool,
+    /// Where frames are drawn; `None` draws at the origin without a bounds check
+    offset: Option<(f64, f64)>,
+    /// `clear` already prepared the canvas for the next present
+    cleared: bool,
+    staging: Vec<u8>,
+}
+
+impl<C: Context2d> CanvasBlitter<C> {
+    fn new(ctx: C) -> Self {
+        Self {
+            ctx,
+            width: 0,
+            height: 0,
+            unpremultiply: false,
+            clear_before_present: false,
+            offset: None,
+            cleared: false,
+            staging: Vec::new(),
+        }
+    }
+
+    fn init(&mut self, width: u32, height: u32) {
         self.width = width;
         self.height = height;
-        Ok(())
+    }
+
+    fn canvas_size(&self) -> (u32, u32) {
+        self.ctx.canvas_size().unwrap_or((self.width, self.height))
+    }
+
+    fn clear(&mut self, color: [u8; 4]) {
+        let (width, height) = self.canvas_size();
+        let [r, g, b, a] = color;
+
+        self.ctx.clear_area(width as f64, height as f64);
+        self.ctx.fill_area(
+            &format!("rgba({}, {}, {}, {})", r, g, b, a as f64 / 255.0),
+            width as f64,
+            height as f64,
+        );
+        self.cleared = true;
     }
 
     fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        let expected = PixelFormat::Rgba8.buffer_size(self.width, self.height);
+        if frame.len() != expected {
+            return Err(VideoBufferError::SizeMismatch {
+                expected,
+                actual: frame.len(),
+            });
+        }
+
+        if let Some(offset) = self.offset {
+            check_fits(offset, (self.width, self.height), self.canvas_size())?;
+        }
+
+        if self.clear_before_present && !self.cleared {
+            let (width, height) = self.canvas_size();
+            self.ctx.clear_area(width as f64, height as f64);
+        }
+        self.cleared = false;
+
+        let frame = if self.unpremultiply {
+            self.staging.clear();
+            self.staging.extend_from_slice(frame);
+            unpremultiply_rgba8(&mut self.staging);
+            &self.staging[..]
+        } else {
+            frame
+        };
+
         let image_data =
             ImageData::new_with_u8_clamped_array_and_sh(Clamped(frame), self.width, self.height)
                 .map_err(|e| {
                     VideoBufferError::PresentFailed(format!("Failed to create ImageData: {:?}", e))
                 })?;
 
-        self.ctx
-            .put_image_data(&image_data, 0.0, 0.0)
-            .map_err(|e| {
-                VideoBufferError::PresentFailed(format!("Failed to put ImageData: {:?}", e))
-            })?;
+        let (x, y) = self.offset.unwrap_or((0.0, 0.0));
+        self.ctx.put_image(&image_data, x, y).map_err(|e| {
+            VideoBufferError::PresentFailed(format!("Failed to put ImageData: {:?}", e))
+        })?;
 
         Ok(())
     }
 }
 
+/// Fail before `put_image_data` when a `frame`-sized image at `offset` leaves the canvas
+fn check_fits(
+    offset: (f64, f64),
+    frame: (u32, u32),
+    canvas: (u32, u32),
+) -> Result<(), VideoBufferError> {
+    let (x, y) = offset;
+    let fits = x >= 0.0
+        && y >= 0.0
+        && x + frame.0 as f64 <= canvas.0 as f64
+        && y + frame.1 as f64 <= canvas.1 as f64;
+    if fits {
+        return Ok(());
+    }
+
+    Err(VideoBufferError::PresentFailed(format!(
+        "{}x{} frame at ({}, {}) doesn't fit the {}x{} canvas",
+        frame.0, frame.1, x, y, canvas.0, canvas.1
+    )))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -56,5 +302,15 @@ mod tests {
     #[test]
     fn test_backend_format() {
         assert_eq!(WasmCanvasBackend::FORMAT, PixelFormat::Rgba8);
+        assert_eq!(WasmOffscreenCanvasBackend::FORMAT, PixelFormat::Rgba8);
+    }
+
+    #[test]
+    fn test_offset_must_fit_canvas() {
+        assert!(check_fits((0.0, 0.0), (320, 240), (320, 240)).is_ok());
+        assert!(check_fits((320.0, 240.0), (320, 240), (640, 480)).is_ok());
+        assert!(check_fits((320.5, 0.0), (320, 240), (640, 480)).is_err());
+        assert!(check_fits((0.0, 241.0), (320, 240), (640, 480)).is_err());
+        assert!(check_fits((-1.0, 0.0), (320, 240), (640, 480)).is_err());
     }
 }
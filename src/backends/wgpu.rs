@@ -0,0 +1,145 @@
+use crate::{DisplayBackend, PixelFormat, Rect, VideoBufferError};
+use wgpu::{
+    Device, Extent3d, ImageCopyTexture, ImageDataLayout, Maintain, Origin3d, Queue, Texture,
+    TextureAspect, TextureFormat, TextureUsages,
+};
+
+/// Uploads frames into a texture owned by an existing wgpu device
+///
+/// Nothing is drawn: the app samples the texture in its own render passes. Uploads are
+/// queued with `Queue::write_texture` and reach the GPU with the app's next submit.
+pub struct WgpuTextureBackend<'gpu> {
+    device: &'gpu Device,
+    queue: &'gpu Queue,
+    texture: &'gpu Texture,
+    initialized: bool,
+}
+
+impl<'gpu> WgpuTextureBackend<'gpu> {
+    /// Upload into `texture`, which must be `Rgba8Unorm` or `Rgba8UnormSrgb` with `COPY_DST` usage
+    pub fn new(device: &'gpu Device, queue: &'gpu Queue, texture: &'gpu Texture) -> Self {
+        Self {
+            device,
+            queue,
+            texture,
+            initialized: false,
+        }
+    }
+
+    /// The texture frames are uploaded into
+    pub fn texture(&self) -> &'gpu Texture {
+        self.texture
+    }
+
+    /// Upload a frame whose rows are `stride` bytes apart, e.g. straight from a padded
+    /// `TripleBuffer`, without packing it first
+    pub fn present_padded(&mut self, frame: &[u8], stride: usize) -> Result<(), VideoBufferError> {
+        let (width, height) = (self.texture.width(), self.texture.height());
+        if stride < width as usize * 4 {
+            return Err(VideoBufferError::PresentFailed(format!(
+                "Stride {} is smaller than a {}-pixel row",
+                stride, width
+            )));
+        }
+        self.write(frame, stride, Rect::new(0, 0, width, height))
+    }
+
+    fn write(&self, frame: &[u8], stride: usize, rect: Rect) -> Result<(), VideoBufferError> {
+        if !self.initialized {
+            return Err(VideoBufferError::NotInitialized);
+        }
+
+        let height = self.texture.height();
+        let expected = stride * height as usize;
+        if frame.len() != expected {
+            return Err(VideoBufferError::SizeMismatch {
+                expected,
+                actual: frame.len(),
+            });
+        }
+
+        let offset = PixelFormat::Rgba8.pixel_offset(rect.x, rect.y, stride);
+        self.queue.write_texture(
+            ImageCopyTexture {
+                texture: self.texture,
+                mip_level: 0,
+                origin: Origin3d {
+                    x: rect.x,
+                    y: rect.y,
+                    z: 0,
+                },
+                aspect: TextureAspect::All,
+            },
+            &frame[offset..],
+            ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(stride as u32),
+                rows_per_image: None,
+            },
+            Extent3d {
+                width: rect.width,
+                height: rect.height,
+                depth_or_array_layers: 1,
+            },
+        );
+        Ok(())
+    }
+}
+
+impl<'gpu> DisplayBackend for WgpuTextureBackend<'gpu> {
+    const FORMAT: PixelFormat = PixelFormat::Rgba8;
+
+    fn init(&mut self, width: u32, height: u32) -> Result<(), VideoBufferError> {
+        let texture = self.texture;
+        if (texture.width(), texture.height()) != (width, height) {
+            return Err(VideoBufferError::InitFailed(format!(
+                "Texture is {}x{} but frames are {}x{}",
+                texture.width(),
+                texture.height(),
+                width,
+                height
+            )));
+        }
+        if !matches!(
+            texture.format(),
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb
+        ) {
+            return Err(VideoBufferError::InitFailed(format!(
+                "Texture format {:?} is not RGBA8",
+                texture.format()
+            )));
+        }
+        if !texture.usage().contains(TextureUsages::COPY_DST) {
+            return Err(VideoBufferError::InitFailed(
+                "Texture needs COPY_DST usage to receive frames".to_string(),
+            ));
+        }
+
+        self.initialized = true;
+        Ok(())
+    }
+
+    fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        let (width, height) = (self.texture.width(), self.texture.height());
+        self.write(
+            frame,
+            Self::FORMAT.stride(width),
+            Rect::new(0, 0, width, height),
+        )
+    }
+
+    /// Submit the upload right away and wait for the GPU to finish it
+    fn present_blocking(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        self.present(frame)?;
+        let submission = self.queue.submit([]);
+        self.device
+            .poll(Maintain::WaitForSubmissionIndex(submission));
+        Ok(())
+    }
+
+    /// Upload only the rows and columns of `rect`
+    fn present_region(&mut self, frame: &[u8], rect: Rect) -> Result<(), VideoBufferError> {
+        let stride = Self::FORMAT.stride(self.texture.width());
+        self.write(frame, stride, rect)
+    }
+}
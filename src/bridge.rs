@@ -1,8 +1,58 @@
 use crate::{
+    accumulate::Accumulator,
     buffer::TripleBuffer,
-    convert::{convert, needs_conversion},
-    DisplayBackend, PixelFormat, Renderer, VideoBufferError,
+    clock::{default_clock, Clock},
+    compositing::composite_over,
+    convert::{
+        convert, convert_dithered, convert_frame, convert_region, convert_strided,
+        needs_conversion, supports,
+    },
+    scale::{scale_frame, ScaleMode},
+    stereo::{pack_stereo, StereoLayout},
+    trace::TraceRecorder,
+    traits::init_backend,
+    transition::{apply_brightness, blend_frames, Transition},
+    viewport::copy_viewport,
+    DisplayBackend, FrameQueue, PixelFormat, Rect, Renderer, SwapBuffer, VideoBufferError,
 };
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Number of recent present timestamps `fps_estimate` averages over
+const PACING_WINDOW: usize = 60;
+
+/// What the presenter does with a frame it skips due to frame rate limiting
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SkipPolicy {
+    /// Drop the skipped frame.
+    #[default]
+    Discard,
+    /// Keep the skipped frame so `present_held` can show it once the rate window opens.
+    HoldAsCurrent,
+}
+
+/// Whether a present made its deadline, see `DisplayPresenter::present_with_deadline`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PresentOutcome {
+    /// The frame was presented at or before its deadline.
+    OnTime,
+    /// The frame was presented this many milliseconds after its deadline.
+    Late(f64),
+    /// The frame was skipped due to frame rate limiting.
+    Skipped,
+}
+
+/// Callback receiving each frame's final backend-format bytes, see `DisplayPresenter::with_tap`
+pub type PresentTap = Box<dyn FnMut(&[u8])>;
+
+/// Callback receiving `(frame_no, now_ms)` after each successful present, see
+/// `DisplayPresenter::with_on_present`
+pub type PresentCallback = Box<dyn FnMut(u64, f64)>;
+
+struct HeldFrame {
+    frame_no: Option<u64>,
+    data: Vec<u8>,
+}
 
 /// Handles presentation: reads from buffer, converts format, and displays
 ///
@@ -10,10 +60,52 @@ use crate::{
 /// between threads but the backend is only accessed from the main thread.
 pub struct DisplayPresenter<B: DisplayBackend> {
     backend: B,
+    width: u32,
+    height: u32,
     source_format: PixelFormat,
     convert_buffer: Option<Vec<u8>>,
+    shared_convert_buffer: Option<Arc<Mutex<Vec<u8>>>>,
+    /// Caller's scratch buffer, held only during `present_frame_into`
+    lent_convert_buffer: Option<Vec<u8>>,
     max_fps: Option<f64>,
     last_present_time_ms: f64,
+    /// Time source for the methods that don't take `now_ms`
+    clock: Option<Box<dyn Clock>>,
+    skip_policy: SkipPolicy,
+    held_frame: Option<HeldFrame>,
+    still_frame: Option<Vec<u8>>,
+    last_queued_frame: Option<HeldFrame>,
+    judder_reduction: bool,
+    interpolation: bool,
+    /// Last source-format frame presented, kept while interpolation is enabled
+    interpolation_frame: Vec<u8>,
+    source_frames: Vec<HeldFrame>,
+    /// Time of the first `present_at_source_rate` call and the frame number due then
+    source_start: Option<(f64, u64)>,
+    /// Number of the last source frame `present_at_source_rate` showed
+    last_source_frame: Option<u64>,
+    transition: Transition,
+    first_present_time_ms: Option<f64>,
+    presented_count: u64,
+    skipped_count: u64,
+    recent_presents_ms: VecDeque<f64>,
+    clear_color: Option<[u8; 4]>,
+    vsync: bool,
+    dithering: bool,
+    accumulator: Option<Accumulator>,
+    tap: Option<PresentTap>,
+    on_present: Option<PresentCallback>,
+    trace: Option<TraceRecorder>,
+    compose_buffer: Vec<u8>,
+    scratch_buffer: Vec<u8>,
+    /// Last frame presented by `present_region`, in backend format
+    region_frame: Vec<u8>,
+    /// Display size and filter when frames are scaled before presenting
+    output_scaling: Option<(u32, u32, ScaleMode)>,
+    scaled_frame: Vec<u8>,
+    /// Copy of the last frame handed to the backend, for `capture_png`
+    #[cfg(feature = "png-capture")]
+    captured_frame: Vec<u8>,
 }
 
 impl<B: DisplayBackend> DisplayPresenter<B> {
@@ -23,7 +115,14 @@ impl<B: DisplayBackend> DisplayPresenter<B> {
         height: u32,
         source_format: PixelFormat,
     ) -> Result<Self, VideoBufferError> {
-        backend.init(width, height)?;
+        if !supports(source_format, B::FORMAT) {
+            return Err(VideoBufferError::InitFailed(format!(
+                "{:?} frames can't be converted for a {:?} backend",
+                source_format,
+                B::FORMAT
+            )));
+        }
+        init_backend(&mut backend, width, height)?;
 
         let convert_buffer = if needs_conversion(source_format, B::FORMAT) {
             let size = B::FORMAT.buffer_size(width, height);
@@ -34,261 +133,3138 @@ impl<B: DisplayBackend> DisplayPresenter<B> {
 
         Ok(Self {
             backend,
+            width,
+            height,
             source_format,
             convert_buffer,
+            shared_convert_buffer: None,
+            lent_convert_buffer: None,
             max_fps: None,
             last_present_time_ms: 0.0,
+            clock: default_clock(),
+            skip_policy: SkipPolicy::Discard,
+            held_frame: None,
+            still_frame: None,
+            last_queued_frame: None,
+            judder_reduction: false,
+            interpolation: false,
+            interpolation_frame: Vec::new(),
+            source_frames: Vec::new(),
+            source_start: None,
+            last_source_frame: None,
+            transition: Transition::default(),
+            first_present_time_ms: None,
+            presented_count: 0,
+            skipped_count: 0,
+            recent_presents_ms: VecDeque::with_capacity(PACING_WINDOW),
+            clear_color: None,
+            vsync: false,
+            dithering: false,
+            accumulator: None,
+            tap: None,
+            on_present: None,
+            trace: None,
+            compose_buffer: Vec::new(),
+            scratch_buffer: Vec::new(),
+            region_frame: Vec::new(),
+            output_scaling: None,
+            scaled_frame: Vec::new(),
+            #[cfg(feature = "png-capture")]
+            captured_frame: Vec::new(),
         })
     }
 
+    /// Collect options with a `DisplayPresenterBuilder`, then initialize the backend with
+    /// its `build`
+    pub fn builder(
+        backend: B,
+        width: u32,
+        height: u32,
+        source_format: PixelFormat,
+    ) -> DisplayPresenterBuilder<B> {
+        DisplayPresenterBuilder {
+            backend,
+            size: (width, height),
+            source_format,
+            scaling: None,
+            max_fps: None,
+            clock: None,
+            skip_policy: SkipPolicy::default(),
+            judder_reduction: false,
+            interpolation: false,
+            vsync: false,
+            dithering: false,
+            clear_color: None,
+            fade_in_ms: None,
+            fade_out: None,
+            shared_convert_buffer: None,
+            tap: None,
+            on_present: None,
+            trace: None,
+        }
+    }
+
+    /// Create a presenter whose frames are rendered at `render_size` and scaled to
+    /// `display_size` (both width, height) before reaching the backend
+    ///
+    /// The backend is initialized at the display size; every other method works with
+    /// frames at the render size.
+    pub fn new_scaled(
+        backend: B,
+        render_size: (u32, u32),
+        display_size: (u32, u32),
+        source_format: PixelFormat,
+        mode: ScaleMode,
+    ) -> Result<Self, VideoBufferError> {
+        let (display_width, display_height) = display_size;
+        let mut presenter = Self::new(backend, display_width, display_height, source_format)?;
+        (presenter.width, presenter.height) = render_size;
+        if let Some(convert_buffer) = presenter.convert_buffer.as_mut() {
+            convert_buffer.resize(B::FORMAT.buffer_size(render_size.0, render_size.1), 0);
+        }
+        if render_size != display_size {
+            presenter.output_scaling = Some((display_width, display_height, mode));
+        }
+        Ok(presenter)
+    }
+
     /// Configure maximum FPS for frame rate limiting
     pub fn with_max_fps(mut self, fps: f64) -> Self {
         self.max_fps = Some(fps);
         self
     }
 
-    /// Present a frame from the given buffer with optional timing control
+    /// Time `present` and `present_frame` with `clock`
     ///
-    /// Returns `true` if the frame was presented, `false` if it was skipped due to timing.
-    pub fn present(
-        &mut self,
-        buffer: &TripleBuffer,
-        now_ms: f64,
-    ) -> Result<bool, VideoBufferError> {
-        // Check if enough time has elapsed
-        if let Some(max_fps) = self.max_fps {
-            let min_interval = 1000.0 / max_fps;
-            if now_ms - self.last_present_time_ms < min_interval {
-                return Ok(false); // Too soon, skip frame
-            }
-        }
-
-        buffer.commit_present();
-        let present_buf = buffer.present_buffer();
+    /// Defaults to an `InstantClock`, or a `DateClock` on wasm32 with the
+    /// `wasm-canvas-backend` feature.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
 
-        let present_buffer = if let Some(ref mut convert_buf) = self.convert_buffer {
-            convert(&present_buf, convert_buf, self.source_format, B::FORMAT);
-            convert_buf.as_slice()
-        } else {
-            &present_buf[..]
-        };
+    /// Current time on the presenter's clock, for the methods that take `now_ms`
+    ///
+    /// Returns `NotInitialized` on targets without a default clock unless one was set
+    /// with `with_clock`.
+    pub fn now_ms(&self) -> Result<f64, VideoBufferError> {
+        self.clock
+            .as_ref()
+            .map(|clock| clock.now_ms())
+            .ok_or(VideoBufferError::NotInitialized)
+    }
 
-        self.backend.present(present_buffer)?;
-        self.last_present_time_ms = now_ms;
-        Ok(true)
+    /// Milliseconds until the frame rate limit allows the next present
+    ///
+    /// Returns `None` if a present at `now_ms` would go through, so async loops can sleep
+    /// for exactly the remaining time instead of polling.
+    pub fn time_until_next_frame(&self, now_ms: f64) -> Option<f64> {
+        let interval_ms = 1000.0 / self.max_fps?;
+        let remaining_ms = self.last_present_time_ms + interval_ms - now_ms;
+        (remaining_ms > 0.0).then_some(remaining_ms)
     }
 
-    /// Present a raw frame directly (for use with FrameQueue)
+    /// Encode the last frame handed to the backend as an RGBA PNG
     ///
-    /// Returns `true` if the frame was presented, `false` if it was skipped due to timing.
-    pub fn present_frame(&mut self, frame: &[u8], now_ms: f64) -> Result<bool, VideoBufferError> {
-        // Check if enough time has elapsed
-        if let Some(max_fps) = self.max_fps {
-            let min_interval = 1000.0 / max_fps;
-            if now_ms - self.last_present_time_ms < min_interval {
-                return Ok(false); // Too soon, skip frame
-            }
+    /// With the `png-capture` feature every present keeps a copy of its final frame, so
+    /// the capture matches what the backend was given, effects included.
+    #[cfg(feature = "png-capture")]
+    pub fn capture_png<W: std::io::Write>(&self, writer: W) -> Result<(), VideoBufferError> {
+        if self.captured_frame.is_empty() {
+            return Err(VideoBufferError::CaptureFailed(
+                "no frame has been presented yet".to_string(),
+            ));
         }
 
-        // Convert if needed
-        let present_buffer = if let Some(ref mut convert_buf) = self.convert_buffer {
-            convert(frame, convert_buf, self.source_format, B::FORMAT);
-            convert_buf.as_slice()
+        let (width, height) = self.effective_output_size();
+        let mut rgba = Vec::new();
+        let pixels = if B::FORMAT == PixelFormat::Rgba8 {
+            &self.captured_frame
         } else {
-            frame
+            rgba.resize(PixelFormat::Rgba8.buffer_size(width, height), 0);
+            convert(
+                &self.captured_frame,
+                &mut rgba,
+                B::FORMAT,
+                PixelFormat::Rgba8,
+            );
+            &rgba
         };
 
-        self.backend.present(present_buffer)?;
-        self.last_present_time_ms = now_ms;
-        Ok(true)
+        let mut encoder = png::Encoder::new(writer, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut png| png.write_image_data(pixels))
+            .map_err(|e| VideoBufferError::CaptureFailed(e.to_string()))
     }
-}
 
-pub struct DisplayBridge<B: DisplayBackend> {
-    buffer: TripleBuffer,
-    backend: B,
-    convert_buffer: Option<Vec<u8>>,
-}
-
-impl<B: DisplayBackend> DisplayBridge<B> {
-    pub fn new(
-        mut backend: B,
-        width: u32,
-        height: u32,
-        renderer_format: PixelFormat,
-    ) -> Result<Self, VideoBufferError> {
-        backend.init(width, height)?;
+    /// Change or remove (`None`) the frame rate limit of a running presenter
+    ///
+    /// The next present is timed against the new rate from the last present.
+    pub fn set_max_fps(&mut self, fps: Option<f64>) {
+        self.max_fps = fps;
+    }
 
-        let buffer = TripleBuffer::new(width, height, renderer_format);
+    /// Convert into a scratch buffer shared with other presenters instead of an owned one
+    ///
+    /// Presenters sharing a buffer must present sequentially (e.g. from one thread); the
+    /// buffer is resized as needed. Has no effect if this presenter doesn't convert.
+    pub fn with_shared_convert_buffer(mut self, buffer: Arc<Mutex<Vec<u8>>>) -> Self {
+        if self.convert_buffer.take().is_some() {
+            self.shared_convert_buffer = Some(buffer);
+        }
+        self
+    }
 
-        let convert_buffer = if needs_conversion(renderer_format, B::FORMAT) {
-            let size = B::FORMAT.buffer_size(width, height);
-            Some(vec![0u8; size])
-        } else {
-            None
-        };
+    /// Fade in from black over `duration_ms`, starting at the first presented frame
+    pub fn with_fade_in(mut self, duration_ms: f64) -> Self {
+        self.transition.set_fade_in(duration_ms);
+        self
+    }
 
-        Ok(Self {
-            buffer,
-            backend,
-            convert_buffer,
-        })
+    /// Fade out to black over `duration_ms`, starting at `start_ms`
+    ///
+    /// Frames presented after the fade completes are black.
+    pub fn with_fade_out(mut self, start_ms: f64, duration_ms: f64) -> Self {
+        self.transition.set_fade_out(start_ms, duration_ms);
+        self
     }
 
-    /// Single-threaded rendering: render → swap → swap → present (all inline)
+    /// Clear the backend surface to an RGBA color before every present
     ///
-    /// This is the simplest API for single-threaded rendering. For parallel
-    /// rendering, use `TripleBuffer` + `DisplayPresenter` instead.
-    pub fn render_frame<R: Renderer>(&mut self, renderer: &mut R) -> Result<(), VideoBufferError> {
-        let width = self.buffer.width();
-        let height = self.buffer.height();
+    /// Guarantees no stale pixels remain around frames that don't cover the whole surface.
+    pub fn with_clear_color(mut self, color: [u8; 4]) -> Self {
+        self.clear_color = Some(color);
+        self
+    }
 
-        // Render to current render buffer
-        {
-            let mut render_buf = self.buffer.render_buffer();
-            renderer.render(&mut render_buf, width, height);
-        }
+    /// Present through `DisplayBackend::present_blocking` so each present waits for vsync
+    ///
+    /// Blocking presents pace the render loop to the display without `with_max_fps`.
+    /// Frame numbers are not forwarded to the backend in this mode.
+    pub fn with_vsync(mut self, vsync: bool) -> Self {
+        self.vsync = vsync;
+        self
+    }
 
-        // Swap render ↔ ready
-        self.buffer.commit_render();
+    /// Apply ordered dithering when conversion reduces bit depth, e.g. RGBA8 to RGB565
+    pub fn with_dithering(mut self, dithering: bool) -> Self {
+        self.dithering = dithering;
+        self
+    }
 
-        // Swap ready ↔ present
-        self.buffer.commit_present();
+    /// Inspect every frame exactly as it is handed to the backend
+    ///
+    /// The tap sees the final backend-format bytes after conversion and effects.
+    pub fn with_tap(mut self, tap: PresentTap) -> Self {
+        self.tap = Some(tap);
+        self
+    }
 
-        // Present
-        let present_buf = self.buffer.present_buffer();
+    /// Run `on_present` each time a frame reaches the backend
+    ///
+    /// Frames skipped by the rate limit and failed presents don't trigger it. The frame
+    /// number is the one given with the frame, or else the count of earlier presents.
+    pub fn with_on_present(mut self, on_present: PresentCallback) -> Self {
+        self.on_present = Some(on_present);
+        self
+    }
 
-        let present_buffer = if let Some(ref mut convert_buf) = self.convert_buffer {
-            convert(&present_buf, convert_buf, self.buffer.format(), B::FORMAT);
-            convert_buf.as_slice()
-        } else {
-            &present_buf[..]
-        };
+    /// Blend adjacent source frames in `present_at_source_rate` instead of repeating them
+    ///
+    /// Each present mixes the two source frames around the display time, weighted by
+    /// how close it is to each, which hides the judder of e.g. 24 fps content on 60 Hz.
+    pub fn with_judder_reduction(mut self, enabled: bool) -> Self {
+        self.judder_reduction = enabled;
+        self
+    }
 
-        self.backend.present(present_buffer)?;
+    /// Hold rate-limited raw frames as a blend with the previously presented frame
+    ///
+    /// A frame that arrives before the display interval is up is mixed into the last
+    /// presented one, weighted by how much of the interval had passed, and held for
+    /// `present_held` in place of the frame itself. This smooths motion when frames
+    /// arrive unevenly. The blend is a plain per-channel mix, which is only right for
+    /// straight-alpha `Rgba8` sources; other formats panic.
+    pub fn with_interpolation(mut self, enabled: bool) -> Self {
+        assert!(
+            !enabled || self.source_format == PixelFormat::Rgba8,
+            "interpolation needs Rgba8 frames, not {:?}",
+            self.source_format
+        );
+        self.interpolation = enabled;
+        if !enabled {
+            self.interpolation_frame = Vec::new();
+        }
+        self
+    }
 
-        Ok(())
+    /// Log every present to a trace file
+    ///
+    /// A present counts as on time unless a frame rate limit is set and more than one
+    /// and a half frame intervals passed since the previous present.
+    pub fn with_trace(mut self, trace: TraceRecorder) -> Self {
+        self.trace = Some(trace);
+        self
     }
 
-    pub fn width(&self) -> u32 {
-        self.buffer.width()
+    /// Detach the trace recorder, e.g. to `finish` it
+    pub fn take_trace(&mut self) -> Option<TraceRecorder> {
+        self.trace.take()
     }
 
-    pub fn height(&self) -> u32 {
-        self.buffer.height()
+    /// Configure what happens to raw frames skipped by the frame rate limit
+    pub fn with_skip_policy(mut self, policy: SkipPolicy) -> Self {
+        self.skip_policy = policy;
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Number of frames handed to the backend so far
+    pub fn presented_count(&self) -> u64 {
+        self.presented_count
+    }
 
-    struct MockRenderer {
-        render_count: usize,
+    /// Number of frames dropped without ever being shown so far
+    ///
+    /// A frame counts once it can no longer be presented: a frame passed in that the rate
+    /// limit or `present_frame_timed`'s age limit turns away, a held frame replaced before
+    /// `present_held`, or a source frame `present_at_source_rate` skips past. Frames left in
+    /// a swap buffer, queue or accumulator for a later present don't count, and neither do
+    /// calls with no new frame, such as `present_pull` or `present_still`. Frames a
+    /// `TripleBuffer` overwrites before they are presented show up in its `dropped_frames`.
+    pub fn skipped_count(&self) -> u64 {
+        self.skipped_count
     }
 
-    impl MockRenderer {
-        fn new() -> Self {
-            Self { render_count: 0 }
+    /// Present rate over the last 60 presents, in frames per second
+    ///
+    /// Returns `0.0` until at least two frames have been presented.
+    pub fn fps_estimate(&self) -> f64 {
+        match (
+            self.recent_presents_ms.front(),
+            self.recent_presents_ms.back(),
+        ) {
+            (Some(&first), Some(&last)) if last > first => {
+                (self.recent_presents_ms.len() - 1) as f64 * 1000.0 / (last - first)
+            }
+            _ => 0.0,
         }
     }
 
-    impl Renderer for MockRenderer {
-        const FORMAT: PixelFormat = PixelFormat::Rgba8;
+    /// Time of the most recent present, or `None` before the first one
+    pub fn last_present_time_ms(&self) -> Option<f64> {
+        self.first_present_time_ms
+            .map(|_| self.last_present_time_ms)
+    }
 
-        fn render(&mut self, frame: &mut [u8], width: u32, height: u32) {
-            self.render_count += 1;
-            let expected_size = (width * height * 4) as usize;
-            assert_eq!(frame.len(), expected_size);
+    /// Whether a frame skipped under `SkipPolicy::HoldAsCurrent` is waiting for `present_held`
+    pub fn has_held_frame(&self) -> bool {
+        self.held_frame.is_some()
+    }
 
-            for i in 0..frame.len() {
-                frame[i] = ((self.render_count + i) % 256) as u8;
-            }
+    /// Whether source frames are converted to the backend format on every present
+    pub fn is_converting(&self) -> bool {
+        needs_conversion(self.source_format, B::FORMAT)
+    }
+
+    /// Size of the frames handed to the backend, after all presenter transforms
+    ///
+    /// This is the display size of a presenter made with `new_scaled`, otherwise the
+    /// source frame size; stereo packing and effects keep the frame dimensions unchanged.
+    pub fn effective_output_size(&self) -> (u32, u32) {
+        match self.output_scaling {
+            Some((width, height, _)) => (width, height),
+            None => (self.width, self.height),
         }
     }
 
-    struct MockBackend {
-        init_called: bool,
-        present_count: usize,
-        last_frame: Vec<u8>,
+    pub fn backend(&self) -> &B {
+        &self.backend
     }
 
-    impl MockBackend {
-        fn new() -> Self {
-            Self {
-                init_called: false,
-                present_count: 0,
-                last_frame: Vec::new(),
-            }
-        }
+    pub fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
     }
 
-    impl DisplayBackend for MockBackend {
-        const FORMAT: PixelFormat = PixelFormat::Rgba8;
+    /// Present a frame from the given buffer, timed by the presenter's clock
+    ///
+    /// Shares its conversion, rate limiting and statistics with `present_frame`.
+    /// Returns `true` if the frame was presented, `false` if it was skipped due to timing.
+    pub fn present<S: SwapBuffer + ?Sized>(
+        &mut self,
+        buffer: &S,
+    ) -> Result<bool, VideoBufferError> {
+        let now_ms = self.now_ms()?;
+        self.present_at(buffer, now_ms)
+    }
 
-        fn init(&mut self, _width: u32, _height: u32) -> Result<(), VideoBufferError> {
-            self.init_called = true;
-            Ok(())
+    /// Like `present`, with the current time given as `now_ms`
+    pub fn present_at<S: SwapBuffer + ?Sized>(
+        &mut self,
+        buffer: &S,
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        if !self.ready_to_present(now_ms) {
+            return Ok(false); // Too soon, skip frame
         }
 
-        fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
-            self.present_count += 1;
-            self.last_frame = frame.to_vec();
-            Ok(())
+        buffer.commit_present();
+        let present_buf = buffer.present_buffer();
+
+        let row = self.source_format.stride(self.width);
+        if buffer.stride() == row {
+            self.present_bytes(&present_buf, None, now_ms)?;
+            return Ok(true);
         }
-    }
 
-    #[test]
-    fn test_bridge_creation() {
-        let backend = MockBackend::new();
-        let bridge = DisplayBridge::new(backend, 320, 200, PixelFormat::Rgba8).unwrap();
+        // Padded rows are packed before presenting
+        let mut packed = std::mem::take(&mut self.compose_buffer);
+        packed.resize(self.source_format.buffer_size(self.width, self.height), 0);
+        convert_strided(
+            &present_buf,
+            buffer.stride(),
+            &mut packed,
+            row,
+            self.width,
+            self.source_format,
+            self.source_format,
+        );
+        let result = self.present_bytes(&packed, None, now_ms);
+        self.compose_buffer = packed;
 
-        assert_eq!(bridge.width(), 320);
-        assert_eq!(bridge.height(), 200);
-        assert!(bridge.backend.init_called);
+        result.map(|()| true)
     }
 
-    #[test]
-    fn test_render_frame_no_conversion() {
-        let backend = MockBackend::new();
-        let mut bridge = DisplayBridge::new(backend, 100, 100, PixelFormat::Rgba8).unwrap();
-        let mut renderer = MockRenderer::new();
+    /// Present a frame from the given buffer and report whether it missed `deadline_ms`
+    ///
+    /// Late frames are still presented; the outcome only tells the caller how far behind
+    /// it is, e.g. to lower render quality.
+    pub fn present_with_deadline<S: SwapBuffer + ?Sized>(
+        &mut self,
+        buffer: &S,
+        deadline_ms: f64,
+        now_ms: f64,
+    ) -> Result<PresentOutcome, VideoBufferError> {
+        if !self.present_at(buffer, now_ms)? {
+            return Ok(PresentOutcome::Skipped);
+        }
 
-        assert!(bridge.convert_buffer.is_none());
+        if now_ms > deadline_ms {
+            Ok(PresentOutcome::Late(now_ms - deadline_ms))
+        } else {
+            Ok(PresentOutcome::OnTime)
+        }
+    }
 
-        bridge.render_frame(&mut renderer).unwrap();
+    /// Present a raw frame directly (for use with FrameQueue)
+    ///
+    /// This is the path for frames that don't live in a `SwapBuffer`, e.g. decoded from
+    /// a network stream. It behaves exactly like `present` apart from the skip policy,
+    /// which only applies to raw frames.
+    /// Returns `true` if the frame was presented, `false` if it was skipped due to timing.
+    pub fn present_frame(&mut self, frame: &[u8]) -> Result<bool, VideoBufferError> {
+        let now_ms = self.now_ms()?;
+        self.present_frame_at(frame, now_ms)
+    }
 
-        assert_eq!(renderer.render_count, 1);
-        assert_eq!(bridge.backend.present_count, 1);
-        assert_eq!(bridge.backend.last_frame.len(), 100 * 100 * 4);
+    /// Like `present_frame`, with the current time given as `now_ms`
+    pub fn present_frame_at(
+        &mut self,
+        frame: &[u8],
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        if !self.ready_to_present(now_ms) {
+            self.skip_frame(None, frame, now_ms);
+            return Ok(false); // Too soon, skip frame
+        }
+
+        self.present_bytes(frame, None, now_ms)?;
+        Ok(true)
     }
 
-    #[test]
-    fn test_multiple_frames() {
-        let backend = MockBackend::new();
-        let mut bridge = DisplayBridge::new(backend, 50, 50, PixelFormat::Rgba8).unwrap();
-        let mut renderer = MockRenderer::new();
+    /// Present a raw frame unless it is older than `max_age_ms`
+    ///
+    /// Frames whose presentation timestamp `pts_ms` lags `now_ms` by more than `max_age_ms`
+    /// are dropped without reaching the backend or the skip policy.
+    /// Returns `true` if the frame was presented, `false` if it was stale or skipped due to timing.
+    pub fn present_frame_timed(
+        &mut self,
+        frame: &[u8],
+        pts_ms: f64,
+        now_ms: f64,
+        max_age_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        if now_ms - pts_ms > max_age_ms {
+            self.drop_frame();
+            return Ok(false); // Too old, drop frame
+        }
+        self.present_frame_at(frame, now_ms)
+    }
 
-        for i in 0..10 {
-            bridge.render_frame(&mut renderer).unwrap();
-            assert_eq!(renderer.render_count, i + 1);
+    /// Present a raw frame and pass its sequence number on to the backend
+    ///
+    /// Returns `true` if the frame was presented, `false` if it was skipped due to timing.
+    pub fn present_numbered_frame(
+        &mut self,
+        frame_no: u64,
+        frame: &[u8],
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        if !self.ready_to_present(now_ms) {
+            self.skip_frame(Some(frame_no), frame, now_ms);
+            return Ok(false); // Too soon, skip frame
         }
 
-        assert_eq!(bridge.backend.present_count, 10);
+        self.present_bytes(frame, Some(frame_no), now_ms)?;
+        Ok(true)
     }
 
-    #[test]
-    fn test_triple_buffer_cycling() {
-        let backend = MockBackend::new();
-        let mut bridge = DisplayBridge::new(backend, 10, 10, PixelFormat::Rgba8).unwrap();
-        let mut renderer = MockRenderer::new();
-
-        // Render 3 frames to ensure all buffers are cycled
-        for _ in 0..3 {
-            bridge.render_frame(&mut renderer).unwrap();
+    /// Render a frame on demand with `fill` and present it
+    ///
+    /// `fill` receives a source-format frame buffer and the number of frames presented so
+    /// far. It is only called when the rate limiter allows a present, so no frame is
+    /// rendered just to be dropped.
+    /// Returns `true` if a frame was rendered and presented, `false` if it was too soon.
+    pub fn present_pull(
+        &mut self,
+        mut fill: impl FnMut(&mut [u8], u64),
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        if !self.ready_to_present(now_ms) {
+            return Ok(false); // Too soon, don't render
         }
 
-        assert_eq!(renderer.render_count, 3);
+        let frame_no = self.presented_count;
+        let mut frame = std::mem::take(&mut self.compose_buffer);
+        frame.resize(self.source_format.buffer_size(self.width, self.height), 0);
+        fill(&mut frame, frame_no);
+        let result = self.present_bytes(&frame, Some(frame_no), now_ms);
+        self.compose_buffer = frame;
+
+        result.map(|()| true)
+    }
+
+    /// Present a pair of eye frames combined in the given stereo layout
+    ///
+    /// Both frames must be full presenter size in the source format.
+    /// Returns `true` if the frame was presented, `false` if it was skipped due to timing.
+    pub fn present_stereo(
+        &mut self,
+        left: &[u8],
+        right: &[u8],
+        layout: StereoLayout,
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        if !self.ready_to_present(now_ms) {
+            self.drop_frame();
+            return Ok(false); // Too soon, drop frame
+        }
+
+        if layout == StereoLayout::FrameSequential {
+            let eye = if self.presented_count.is_multiple_of(2) {
+                left
+            } else {
+                right
+            };
+            self.present_bytes(eye, None, now_ms)?;
+            return Ok(true);
+        }
+
+        let mut packed = std::mem::take(&mut self.compose_buffer);
+        packed.resize(self.source_format.buffer_size(self.width, self.height), 0);
+        pack_stereo(
+            left,
+            right,
+            &mut packed,
+            self.width,
+            self.height,
+            self.source_format,
+            layout,
+        );
+        let result = self.present_bytes(&packed, None, now_ms);
+        self.compose_buffer = packed;
+
+        result.map(|()| true)
+    }
+
+    /// Like `present_frame_at`, converting into the caller's `scratch` buffer
+    ///
+    /// `scratch` is used instead of the presenter's own conversion buffer, so callers
+    /// can pool scratch buffers across presenters. It is resized as needed and left
+    /// untouched if this presenter doesn't convert.
+    /// Returns `true` if the frame was presented, `false` if it was skipped due to timing.
+    pub fn present_frame_into(
+        &mut self,
+        frame: &[u8],
+        scratch: &mut Vec<u8>,
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        if !needs_conversion(self.source_format, B::FORMAT) {
+            return self.present_frame_at(frame, now_ms);
+        }
+
+        self.lent_convert_buffer = Some(std::mem::take(scratch));
+        let result = self.present_frame_at(frame, now_ms);
+        *scratch = self.lent_convert_buffer.take().unwrap_or_default();
+        result
+    }
+
+    /// Present `base` with `overlay` alpha-blended over its `overlay_rect` region
+    ///
+    /// Both are straight-alpha RGBA8 frames, `base` presenter size and `overlay` the size
+    /// of `overlay_rect`, e.g. a HUD or subtitle layer. Neither input is modified. Fails
+    /// for presenters whose source format isn't `Rgba8`.
+    /// Returns `true` if the frame was presented, `false` if it was skipped due to timing.
+    pub fn present_with_overlay(
+        &mut self,
+        base: &[u8],
+        overlay: &[u8],
+        overlay_rect: Rect,
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        if self.source_format != PixelFormat::Rgba8 {
+            return Err(VideoBufferError::UnsupportedFormat {
+                format: self.source_format,
+                operation: "present_with_overlay",
+            });
+        }
+        self.check_frame_len(base)?;
+        self.check_region(overlay_rect)?;
+        let overlay_len = PixelFormat::Rgba8.buffer_size(overlay_rect.width, overlay_rect.height);
+        if overlay.len() != overlay_len {
+            return Err(VideoBufferError::SizeMismatch {
+                expected: overlay_len,
+                actual: overlay.len(),
+            });
+        }
+        if !self.ready_to_present(now_ms) {
+            self.drop_frame();
+            return Ok(false); // Too soon, drop frame
+        }
+
+        let mut frame = std::mem::take(&mut self.compose_buffer);
+        frame.clear();
+        frame.extend_from_slice(base);
+        composite_over(&mut frame, self.width, overlay, overlay_rect);
+        let result = self.present_bytes(&frame, None, now_ms);
+        self.compose_buffer = frame;
+
+        result.map(|()| true)
+    }
+
+    /// Present the `viewport` region of a larger source-format canvas
+    ///
+    /// The viewport must be presenter size but may extend past the canvas edges; those
+    /// parts are filled with transparent black. Planar source formats aren't supported.
+    /// Returns `true` if the frame was presented, `false` if it was skipped due to timing.
+    pub fn present_viewport(
+        &mut self,
+        canvas: &[u8],
+        canvas_width: u32,
+        canvas_height: u32,
+        viewport: Rect,
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        let format = self.source_format;
+        if format.is_planar() {
+            return Err(VideoBufferError::UnsupportedFormat {
+                format,
+                operation: "present_viewport",
+            });
+        }
+        if (viewport.width, viewport.height) != (self.width, self.height) {
+            return Err(VideoBufferError::SizeMismatch {
+                expected: format.buffer_size(self.width, self.height),
+                actual: format.buffer_size(viewport.width, viewport.height),
+            });
+        }
+        let canvas_len = format.buffer_size(canvas_width, canvas_height);
+        if canvas.len() != canvas_len {
+            return Err(VideoBufferError::SizeMismatch {
+                expected: canvas_len,
+                actual: canvas.len(),
+            });
+        }
+        if !self.ready_to_present(now_ms) {
+            self.drop_frame();
+            return Ok(false); // Too soon, drop frame
+        }
+
+        let mut frame = std::mem::take(&mut self.compose_buffer);
+        frame.resize(self.source_format.buffer_size(self.width, self.height), 0);
+        copy_viewport(
+            canvas,
+            (canvas_width, canvas_height),
+            &mut frame,
+            viewport,
+            self.source_format,
+        );
+        let result = self.present_bytes(&frame, None, now_ms);
+        self.compose_buffer = frame;
+
+        result.map(|()| true)
+    }
+
+    /// Present the next ready frame from `queue`, or repeat the last one once `deadline_ms` passes
+    ///
+    /// When the queue has nothing ready, the last frame presented through this method is
+    /// re-presented if `now_ms >= deadline_ms`, so the display keeps refreshing while the
+    /// renderer is starved.
+    /// Returns `true` if a frame was presented, `false` if nothing was due or it's too soon.
+    pub fn present_next_or_hold(
+        &mut self,
+        queue: &mut FrameQueue,
+        deadline_ms: f64,
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        if !self.ready_to_present(now_ms) {
+            return Ok(false); // Too soon, leave the frame queued
+        }
+
+        let next = match queue.pop_ready_numbered() {
+            Some((frame_no, data)) => HeldFrame {
+                frame_no: Some(frame_no),
+                data,
+            },
+            None if now_ms >= deadline_ms => match self.last_queued_frame.take() {
+                Some(last) => last,
+                None => return Ok(false),
+            },
+            None => return Ok(false),
+        };
+
+        let result = self.present_bytes(&next.data, next.frame_no, now_ms);
+        if let Some(previous) = self.last_queued_frame.replace(next) {
+            queue.release(previous.data);
+        }
+        result.map(|()| true)
+    }
+
+    /// Present `queue` as a stream of `source_fps` frames, timed against `now_ms`
+    ///
+    /// The queue's next frame is due at the first call and each one after it
+    /// `1 / source_fps` seconds later. Each present shows the latest due frame, or with
+    /// `with_judder_reduction` a blend of it and the following frame once that has been
+    /// queued. After seeking the queue, call `reset_source_clock` to time frames from the
+    /// new position.
+    /// Returns `true` if a frame was presented, `false` if none is due yet or it's too soon.
+    pub fn present_at_source_rate(
+        &mut self,
+        queue: &mut FrameQueue,
+        source_fps: f64,
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        if !self.ready_to_present(now_ms) {
+            return Ok(false); // Too soon, leave the frames queued
+        }
+
+        let (start_ms, first_frame) = *self
+            .source_start
+            .get_or_insert((now_ms, queue.next_frame_number()));
+        let position = ((now_ms - start_ms) * source_fps / 1000.0).max(0.0);
+        let due = first_frame.saturating_add(position.floor() as u64);
+
+        // Keep the latest due frame and the one after it
+        while queue.next_frame_number() <= due.saturating_add(1) {
+            let Some((frame_no, data)) = queue.pop_ready_numbered() else {
+                break;
+            };
+            self.source_frames.push(HeldFrame {
+                frame_no: Some(frame_no),
+                data,
+            });
+            if self.source_frames.len() > 2 {
+                let evicted = self.source_frames.remove(0);
+                if evicted.frame_no > self.last_source_frame {
+                    self.drop_frame(); // Overtaken before it was ever shown
+                }
+                queue.release(evicted.data);
+            }
+        }
+        let is_due = |frame: &HeldFrame| frame.frame_no.is_some_and(|frame_no| frame_no <= due);
+        let Some(current) = self.source_frames.iter().rposition(is_due) else {
+            return Ok(false); // Nothing due yet
+        };
+
+        let frames = std::mem::take(&mut self.source_frames);
+        let result = match frames.get(current + 1) {
+            Some(next) if self.judder_reduction => {
+                let mut blended = std::mem::take(&mut self.compose_buffer);
+                blended.resize(frames[current].data.len(), 0);
+                blend_frames(
+                    &frames[current].data,
+                    &next.data,
+                    &mut blended,
+                    self.source_format,
+                    position.fract(),
+                );
+                let result = self.present_bytes(&blended, frames[current].frame_no, now_ms);
+                self.compose_buffer = blended;
+                result
+            }
+            _ => self.present_bytes(&frames[current].data, frames[current].frame_no, now_ms),
+        };
+        if result.is_ok() {
+            self.last_source_frame = frames[current].frame_no;
+        }
+        self.source_frames = frames;
+
+        result.map(|()| true)
+    }
+
+    /// Restart `present_at_source_rate` timing at the queue's next frame
+    ///
+    /// Call after seeking, e.g. with `FrameQueue::reset` or `FrameQueue::rebase`. Source
+    /// frames kept from before are dropped.
+    pub fn reset_source_clock(&mut self) {
+        self.source_start = None;
+        self.last_source_frame = None;
+        self.source_frames.clear();
+    }
+
+    /// Add a frame to the running average and present the averaged result
+    ///
+    /// Pass `reset = true` when the scene changes to start a new average with `frame`.
+    /// Frames skipped due to timing still contribute to the average.
+    /// Returns `true` if the frame was presented, `false` if it was skipped due to timing.
+    pub fn present_accumulated(
+        &mut self,
+        frame: &[u8],
+        reset: bool,
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        let (width, height, format) = (self.width, self.height, self.source_format);
+        let accumulator = self
+            .accumulator
+            .get_or_insert_with(|| Accumulator::new(width, height, format));
+        if reset {
+            accumulator.reset();
+        }
+        accumulator.add(frame);
+
+        if !self.ready_to_present(now_ms) {
+            return Ok(false); // Too soon, skip frame
+        }
+
+        let mut averaged = std::mem::take(&mut self.compose_buffer);
+        averaged.resize(format.buffer_size(width, height), 0);
+        if let Some(accumulator) = &self.accumulator {
+            accumulator.average_into(&mut averaged);
+        }
+        let result = self.present_bytes(&averaged, None, now_ms);
+        self.compose_buffer = averaged;
+
+        result.map(|()| true)
+    }
+
+    /// Present the first `complete_rows` rows of a frame that is still arriving
+    ///
+    /// `frame` is a full source-format frame of which only the top rows are valid. This
+    /// is `present_region` with the complete rows as the dirty rectangle, so rows below
+    /// keep the contents of the previous partial or region present.
+    /// Returns `true` if the frame was presented, `false` if it was skipped due to timing.
+    pub fn present_partial(
+        &mut self,
+        frame: &[u8],
+        complete_rows: u32,
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        let rows = Rect::new(0, 0, self.width, complete_rows.min(self.height));
+        self.present_region(frame, rows, now_ms)
+    }
+
+    /// Present a frame of which only the `dirty` rectangle changed
+    ///
+    /// Only `dirty` is converted, over the frame shown by the previous `present_region`
+    /// call, which the presenter retains in backend format; present the first frame with
+    /// `Rect::full`. The backend gets the rectangle through `DisplayBackend::present_region`,
+    /// unless a fade or output scaling touches the whole frame anyway. Planar source formats
+    /// aren't supported.
+    /// Returns `true` if the frame was presented, `false` if it was skipped due to timing.
+    pub fn present_region(
+        &mut self,
+        frame: &[u8],
+        dirty: Rect,
+        now_ms: f64,
+    ) -> Result<bool, VideoBufferError> {
+        if self.source_format.is_planar() {
+            return Err(VideoBufferError::UnsupportedFormat {
+                format: self.source_format,
+                operation: "present_region",
+            });
+        }
+        self.check_frame_len(frame)?;
+        self.check_region(dirty)?;
+        if !self.ready_to_present(now_ms) {
+            self.drop_frame();
+            return Ok(false); // Too soon, drop frame
+        }
+
+        let mut retained = std::mem::take(&mut self.region_frame);
+        retained.resize(B::FORMAT.buffer_size(self.width, self.height), 0);
+        convert_region(
+            frame,
+            &mut retained,
+            dirty,
+            self.width,
+            self.source_format,
+            B::FORMAT,
+        );
+        let result = self.present_with(&retained, None, now_ms, false, Some(dirty));
+        self.region_frame = retained;
+
+        result.map(|()| true)
+    }
+
+    /// Cache a still image for `present_still`, converting it to the backend format once
+    pub fn set_still(&mut self, frame: &[u8]) {
+        let mut still = self.still_frame.take().unwrap_or_default();
+        still.resize(B::FORMAT.buffer_size(self.width, self.height), 0);
+        convert_frame(
+            frame,
+            &mut still,
+            self.source_format,
+            B::FORMAT,
+            self.width,
+            self.height,
+        );
+        self.still_frame = Some(still);
+    }
+
+    /// Drop the cached still image
+    pub fn clear_still(&mut self) {
+        self.still_frame = None;
+    }
+
+    /// Re-present the still image cached by `set_still` without converting it again
+    ///
+    /// Returns `true` if the still was presented, `false` if there is none or it's too soon.
+    pub fn present_still(&mut self, now_ms: f64) -> Result<bool, VideoBufferError> {
+        if self.still_frame.is_none() || !self.ready_to_present(now_ms) {
+            return Ok(false);
+        }
+
+        let still = self.still_frame.take().unwrap();
+        let result = self.present_with(&still, None, now_ms, false, None);
+        self.still_frame = Some(still);
+        result.map(|()| true)
+    }
+
+    /// Present the frame held by `SkipPolicy::HoldAsCurrent`, if any
+    ///
+    /// Returns `true` if the held frame was presented, `false` if there is none or it's too soon.
+    /// The frame stays held if the backend fails, so the call can be retried.
+    pub fn present_held(&mut self, now_ms: f64) -> Result<bool, VideoBufferError> {
+        if !self.ready_to_present(now_ms) {
+            return Ok(false);
+        }
+        self.flush_held(now_ms)
+    }
+
+    /// Present the held frame, if any, regardless of the frame rate limit
+    pub(crate) fn flush_held(&mut self, now_ms: f64) -> Result<bool, VideoBufferError> {
+        let Some(held) = self.held_frame.take() else {
+            return Ok(false);
+        };
+        match self.present_bytes(&held.data, held.frame_no, now_ms) {
+            Ok(()) => Ok(true),
+            Err(err) => {
+                self.held_frame = Some(held);
+                Err(err)
+            }
+        }
+    }
+
+    /// Apply the skip policy or interpolation to a frame that was rate limited
+    fn skip_frame(&mut self, frame_no: Option<u64>, frame: &[u8], now_ms: f64) {
+        let interpolate = self.interpolation && self.interpolation_frame.len() == frame.len();
+        let hold = interpolate || self.skip_policy == SkipPolicy::HoldAsCurrent;
+        // Holding a frame only drops one if it replaces a held frame that was never shown
+        if !hold || self.held_frame.is_some() {
+            self.drop_frame();
+        }
+
+        if interpolate {
+            let interval_ms = self.max_fps.map_or(0.0, |max_fps| 1000.0 / max_fps);
+            let weight = (now_ms - self.last_present_time_ms) / interval_ms;
+            let mut data = self
+                .held_frame
+                .take()
+                .map(|held| held.data)
+                .unwrap_or_default();
+            data.resize(frame.len(), 0);
+            blend_frames(
+                &self.interpolation_frame,
+                frame,
+                &mut data,
+                self.source_format,
+                weight,
+            );
+            self.held_frame = Some(HeldFrame { frame_no, data });
+        } else if self.skip_policy == SkipPolicy::HoldAsCurrent {
+            // Reuse the previous held frame's allocation
+            let mut data = self
+                .held_frame
+                .take()
+                .map(|held| held.data)
+                .unwrap_or_default();
+            data.clear();
+            data.extend_from_slice(frame);
+            self.held_frame = Some(HeldFrame { frame_no, data });
+        }
+    }
+
+    /// Fail unless `frame` is a full presenter-size frame in the source format
+    fn check_frame_len(&self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        let expected = self.source_format.buffer_size(self.width, self.height);
+        if frame.len() != expected {
+            return Err(VideoBufferError::SizeMismatch {
+                expected,
+                actual: frame.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Fail unless `rect` lies within the presenter-size frame
+    fn check_region(&self, rect: Rect) -> Result<(), VideoBufferError> {
+        if !rect.fits_within(self.width, self.height) {
+            return Err(VideoBufferError::RegionOutOfBounds {
+                rect,
+                width: self.width,
+                height: self.height,
+            });
+        }
+        Ok(())
+    }
+
+    /// Count a frame handed to the presenter that will never be shown
+    fn drop_frame(&mut self) {
+        self.skipped_count += 1;
+    }
+
+    /// Check if enough time has elapsed since the last present
+    fn ready_to_present(&self, now_ms: f64) -> bool {
+        match self.max_fps {
+            Some(max_fps) => now_ms - self.last_present_time_ms >= 1000.0 / max_fps,
+            None => true,
+        }
+    }
+
+    /// Convert a source-format frame if needed and present it, bypassing the rate limit
+    pub(crate) fn present_bytes(
+        &mut self,
+        frame: &[u8],
+        frame_no: Option<u64>,
+        now_ms: f64,
+    ) -> Result<(), VideoBufferError> {
+        if self.interpolation {
+            self.interpolation_frame.clear();
+            self.interpolation_frame.extend_from_slice(frame);
+        }
+        self.present_with(frame, frame_no, now_ms, true, None)
+    }
+
+    /// Apply transitions and hand the frame to the backend
+    ///
+    /// `frame` is in the source format if `in_source_format` is set, otherwise already in
+    /// the backend format. `dirty` limits the backend update to a changed region.
+    fn present_with(
+        &mut self,
+        frame: &[u8],
+        frame_no: Option<u64>,
+        now_ms: f64,
+        in_source_format: bool,
+        dirty: Option<Rect>,
+    ) -> Result<(), VideoBufferError> {
+        let shared = self
+            .shared_convert_buffer
+            .as_ref()
+            .filter(|_| in_source_format && self.lent_convert_buffer.is_none())
+            .map(Arc::clone);
+        let Some(shared) = shared else {
+            return self.present_to_backend(frame, frame_no, now_ms, in_source_format, dirty);
+        };
+
+        // Borrow the shared buffer rather than holding its lock, so callbacks can present
+        // through other presenters sharing it
+        self.lent_convert_buffer = Some(std::mem::take(&mut *shared.lock().unwrap()));
+        let result = self.present_to_backend(frame, frame_no, now_ms, in_source_format, dirty);
+        *shared.lock().unwrap() = self.lent_convert_buffer.take().unwrap_or_default();
+        result
+    }
+
+    /// `present_with` once a shared conversion buffer, if any, has been lent
+    fn present_to_backend(
+        &mut self,
+        frame: &[u8],
+        frame_no: Option<u64>,
+        now_ms: f64,
+        in_source_format: bool,
+        dirty: Option<Rect>,
+    ) -> Result<(), VideoBufferError> {
+        let start_ms = *self.first_present_time_ms.get_or_insert(now_ms);
+        let brightness = self.transition.brightness(start_ms, now_ms);
+
+        let convert_buf = self
+            .lent_convert_buffer
+            .as_mut()
+            .or(self.convert_buffer.as_mut())
+            .filter(|_| in_source_format);
+
+        let present_buffer = if let Some(convert_buf) = convert_buf {
+            convert_buf.resize(B::FORMAT.buffer_size(self.width, self.height), 0);
+            if self.dithering && !self.source_format.is_planar() {
+                convert_dithered(
+                    frame,
+                    convert_buf,
+                    self.source_format,
+                    B::FORMAT,
+                    self.width,
+                );
+            } else {
+                convert_frame(
+                    frame,
+                    convert_buf,
+                    self.source_format,
+                    B::FORMAT,
+                    self.width,
+                    self.height,
+                );
+            }
+            if brightness < 1.0 {
+                apply_brightness(convert_buf, B::FORMAT, brightness);
+            }
+            convert_buf.as_slice()
+        } else if brightness < 1.0 {
+            // Effects must not modify the caller's frame, so work on a copy
+            self.scratch_buffer.clear();
+            self.scratch_buffer.extend_from_slice(frame);
+            apply_brightness(&mut self.scratch_buffer, B::FORMAT, brightness);
+            self.scratch_buffer.as_slice()
+        } else {
+            frame
+        };
+        let present_buffer = match self.output_scaling {
+            Some((output_width, output_height, mode)) => {
+                self.scaled_frame
+                    .resize(B::FORMAT.buffer_size(output_width, output_height), 0);
+                scale_frame(
+                    present_buffer,
+                    (self.width, self.height),
+                    &mut self.scaled_frame,
+                    (output_width, output_height),
+                    B::FORMAT,
+                    mode,
+                );
+                self.scaled_frame.as_slice()
+            }
+            None => present_buffer,
+        };
+
+        if let Some(tap) = self.tap.as_mut() {
+            tap(present_buffer);
+        }
+        #[cfg(feature = "png-capture")]
+        {
+            self.captured_frame.clear();
+            self.captured_frame.extend_from_slice(present_buffer);
+        }
+        if let Some(color) = self.clear_color {
+            self.backend.clear(color)?;
+        }
+        // Fades and scaling change every output pixel, not just the dirty ones
+        let dirty = dirty.filter(|_| brightness >= 1.0 && self.output_scaling.is_none());
+        match (frame_no, dirty) {
+            _ if self.vsync => self.backend.present_blocking(present_buffer)?,
+            (_, Some(rect)) => self.backend.present_region(present_buffer, rect)?,
+            (Some(frame_no), None) => self.backend.present_numbered(frame_no, present_buffer)?,
+            (None, None) => self.backend.present(present_buffer)?,
+        }
+        if let Some(trace) = self.trace.as_mut() {
+            let on_time = match self.max_fps {
+                Some(max_fps) if self.presented_count > 0 => {
+                    now_ms - self.last_present_time_ms <= 1500.0 / max_fps
+                }
+                _ => true,
+            };
+            trace.record(now_ms, frame_no, on_time)?;
+        }
+        if let Some(on_present) = self.on_present.as_mut() {
+            on_present(frame_no.unwrap_or(self.presented_count), now_ms);
+        }
+        self.last_present_time_ms = now_ms;
+        self.presented_count += 1;
+        if self.recent_presents_ms.len() == PACING_WINDOW {
+            self.recent_presents_ms.pop_front();
+        }
+        self.recent_presents_ms.push_back(now_ms);
+        // Anything presented supersedes a held frame
+        if self.held_frame.take().is_some() {
+            self.drop_frame();
+        }
+        Ok(())
+    }
+}
+
+/// Every `DisplayPresenter` option in one place, see `DisplayPresenter::builder`
+///
+/// Nothing touches the backend until `build`. Each method matches the presenter's
+/// `with_*` method of the same name; `new` and the `with_*` methods remain the shorthand
+/// for simple setups. There is deliberately no overlay option: overlays change from
+/// frame to frame, so they are passed to `DisplayPresenter::present_with_overlay` instead.
+pub struct DisplayPresenterBuilder<B: DisplayBackend> {
+    backend: B,
+    size: (u32, u32),
+    source_format: PixelFormat,
+    scaling: Option<((u32, u32), ScaleMode)>,
+    max_fps: Option<f64>,
+    clock: Option<Box<dyn Clock>>,
+    skip_policy: SkipPolicy,
+    judder_reduction: bool,
+    interpolation: bool,
+    vsync: bool,
+    dithering: bool,
+    clear_color: Option<[u8; 4]>,
+    fade_in_ms: Option<f64>,
+    /// Start and duration of the fade-out, in ms
+    fade_out: Option<(f64, f64)>,
+    shared_convert_buffer: Option<Arc<Mutex<Vec<u8>>>>,
+    tap: Option<PresentTap>,
+    on_present: Option<PresentCallback>,
+    trace: Option<TraceRecorder>,
+}
+
+impl<B: DisplayBackend> DisplayPresenterBuilder<B> {
+    /// Scale frames to `display_size` with `mode`, like `DisplayPresenter::new_scaled`
+    ///
+    /// The builder's size stays the render size.
+    pub fn scale_mode(mut self, display_size: (u32, u32), mode: ScaleMode) -> Self {
+        self.scaling = Some((display_size, mode));
+        self
+    }
+
+    pub fn max_fps(mut self, fps: f64) -> Self {
+        self.max_fps = Some(fps);
+        self
+    }
+
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
+    pub fn skip_policy(mut self, policy: SkipPolicy) -> Self {
+        self.skip_policy = policy;
+        self
+    }
+
+    pub fn judder_reduction(mut self, enabled: bool) -> Self {
+        self.judder_reduction = enabled;
+        self
+    }
+
+    /// `build` fails unless the source format is `Rgba8`
+    pub fn interpolation(mut self, enabled: bool) -> Self {
+        self.interpolation = enabled;
+        self
+    }
+
+    pub fn vsync(mut self, vsync: bool) -> Self {
+        self.vsync = vsync;
+        self
+    }
+
+    pub fn dithering(mut self, dithering: bool) -> Self {
+        self.dithering = dithering;
+        self
+    }
+
+    pub fn clear_color(mut self, color: [u8; 4]) -> Self {
+        self.clear_color = Some(color);
+        self
+    }
+
+    pub fn fade_in(mut self, duration_ms: f64) -> Self {
+        self.fade_in_ms = Some(duration_ms);
+        self
+    }
+
+    pub fn fade_out(mut self, start_ms: f64, duration_ms: f64) -> Self {
+        self.fade_out = Some((start_ms, duration_ms));
+        self
+    }
+
+    pub fn shared_convert_buffer(mut self, buffer: Arc<Mutex<Vec<u8>>>) -> Self {
+        self.shared_convert_buffer = Some(buffer);
+        self
+    }
+
+    pub fn tap(mut self, tap: PresentTap) -> Self {
+        self.tap = Some(tap);
+        self
+    }
+
+    pub fn on_present(mut self, on_present: PresentCallback) -> Self {
+        self.on_present = Some(on_present);
+        self
+    }
+
+    pub fn trace(mut self, trace: TraceRecorder) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
+    /// Initialize the backend and create the presenter
+    ///
+    /// Invalid options are reported as `InitFailed` rather than panicking.
+    pub fn build(self) -> Result<DisplayPresenter<B>, VideoBufferError> {
+        if self.interpolation && self.source_format != PixelFormat::Rgba8 {
+            return Err(VideoBufferError::InitFailed(format!(
+                "interpolation needs Rgba8 frames, not {:?}",
+                self.source_format
+            )));
+        }
+
+        let (width, height) = self.size;
+        let presenter = match self.scaling {
+            Some((display_size, mode)) => DisplayPresenter::new_scaled(
+                self.backend,
+                self.size,
+                display_size,
+                self.source_format,
+                mode,
+            )?,
+            None => DisplayPresenter::new(self.backend, width, height, self.source_format)?,
+        };
+
+        let mut presenter = presenter
+            .with_skip_policy(self.skip_policy)
+            .with_judder_reduction(self.judder_reduction)
+            .with_vsync(self.vsync)
+            .with_dithering(self.dithering);
+        // Checked above, so skip `with_interpolation`'s assertion
+        presenter.interpolation = self.interpolation;
+        presenter.max_fps = self.max_fps;
+        presenter.clear_color = self.clear_color;
+        presenter.tap = self.tap;
+        presenter.on_present = self.on_present;
+        presenter.trace = self.trace;
+        if let Some(clock) = self.clock {
+            presenter.clock = Some(clock);
+        }
+        if let Some(duration_ms) = self.fade_in_ms {
+            presenter = presenter.with_fade_in(duration_ms);
+        }
+        if let Some((start_ms, duration_ms)) = self.fade_out {
+            presenter = presenter.with_fade_out(start_ms, duration_ms);
+        }
+        if let Some(buffer) = self.shared_convert_buffer {
+            presenter = presenter.with_shared_convert_buffer(buffer);
+        }
+        Ok(presenter)
+    }
+}
+
+pub struct DisplayBridge<B: DisplayBackend, S: SwapBuffer = TripleBuffer> {
+    buffer: S,
+    backend: B,
+    convert_buffer: Option<Vec<u8>>,
+    /// Format of the rendered frames, as reported by the renderer
+    frame_format: PixelFormat,
+    next_frame_no: u64,
+}
+
+impl<B: DisplayBackend> DisplayBridge<B> {
+    pub fn new(
+        backend: B,
+        width: u32,
+        height: u32,
+        renderer_format: PixelFormat,
+    ) -> Result<Self, VideoBufferError> {
+        Self::with_swap_buffer(backend, TripleBuffer::new(width, height, renderer_format))
+    }
+
+    /// Like `new`, taking the format from the renderer that will draw the frames
+    pub fn for_renderer<R: Renderer>(
+        backend: B,
+        width: u32,
+        height: u32,
+        renderer: &R,
+    ) -> Result<Self, VideoBufferError> {
+        Self::new(backend, width, height, renderer.format())
+    }
+}
+
+impl<B: DisplayBackend, S: SwapBuffer> DisplayBridge<B, S> {
+    /// Create a bridge around an existing buffer, e.g. a `DoubleBuffer` for lower latency
+    ///
+    /// The backend is initialized at the buffer's size and frames are rendered in its format.
+    pub fn with_swap_buffer(mut backend: B, buffer: S) -> Result<Self, VideoBufferError> {
+        let (width, height) = buffer.size();
+        if !supports(buffer.format(), B::FORMAT) {
+            return Err(VideoBufferError::InitFailed(format!(
+                "{:?} frames can't be converted for a {:?} backend",
+                buffer.format(),
+                B::FORMAT
+            )));
+        }
+        init_backend(&mut backend, width, height)?;
+
+        let convert_buffer = if needs_conversion(buffer.format(), B::FORMAT) {
+            let size = B::FORMAT.buffer_size(width, height);
+            Some(vec![0u8; size])
+        } else {
+            None
+        };
+
+        let frame_format = buffer.format();
+        Ok(Self {
+            buffer,
+            backend,
+            convert_buffer,
+            frame_format,
+            next_frame_no: 0,
+        })
+    }
+
+    /// Single-threaded rendering: render → swap → swap → present (all inline)
+    ///
+    /// This is the simplest API for single-threaded rendering. For parallel
+    /// rendering, use a `SwapBuffer` + `DisplayPresenter` instead.
+    pub fn render_frame<R: Renderer>(&mut self, renderer: &mut R) -> Result<(), VideoBufferError> {
+        self.render(renderer)?;
+        self.present()
+    }
+
+    /// Render the next frame and commit it, without presenting it yet
+    ///
+    /// Lets rendering run ahead, e.g. during idle time. Only the latest rendered frame
+    /// is kept for `present`; rendering again first replaces it. Fails without rendering
+    /// if the renderer's format has a different frame size than the buffer's, or can't be
+    /// converted for the backend.
+    pub fn render<R: Renderer>(&mut self, renderer: &mut R) -> Result<(), VideoBufferError> {
+        let (width, height) = self.buffer.size();
+        let format = renderer.format();
+        if format.buffer_size(width, height) != self.buffer.format().buffer_size(width, height)
+            || !supports(format, B::FORMAT)
+        {
+            return Err(VideoBufferError::FormatMismatch {
+                frame: format,
+                buffer: self.buffer.format(),
+            });
+        }
+        self.frame_format = format;
+
+        // Render to current render buffer
+        {
+            let mut render_buf = self.buffer.render_buffer();
+            renderer.render(&mut render_buf, width, height, self.next_frame_no);
+        }
+        self.next_frame_no = self.next_frame_no.wrapping_add(1);
+
+        // Swap render ↔ ready
+        self.buffer.commit_render();
+        Ok(())
+    }
+
+    /// Present the most recently rendered frame, e.g. in response to vsync
+    ///
+    /// Re-presents the previous frame if nothing was rendered since the last call.
+    pub fn present(&mut self) -> Result<(), VideoBufferError> {
+        // Swap ready ↔ present
+        self.buffer.commit_present();
+
+        // Present
+        let present_buf = self.buffer.present_buffer();
+
+        let (width, height) = self.buffer.size();
+        let stride = self.buffer.stride();
+        let padded = stride != self.buffer.format().stride(width);
+        let present_buffer = if padded || needs_conversion(self.frame_format, B::FORMAT) {
+            let convert_buf = self.convert_buffer.get_or_insert_with(Vec::new);
+            convert_buf.resize(B::FORMAT.buffer_size(width, height), 0);
+            if padded {
+                // Padded rows are packed while converting
+                convert_strided(
+                    &present_buf,
+                    stride,
+                    convert_buf,
+                    B::FORMAT.stride(width),
+                    width,
+                    self.frame_format,
+                    B::FORMAT,
+                );
+            } else {
+                convert_frame(
+                    &present_buf,
+                    convert_buf,
+                    self.frame_format,
+                    B::FORMAT,
+                    width,
+                    height,
+                );
+            }
+            convert_buf.as_slice()
+        } else {
+            &present_buf[..]
+        };
+
+        self.backend.present(present_buffer)?;
+
+        Ok(())
+    }
+
+    /// Frame number the next `render` or `render_frame` call passes to the renderer
+    pub fn next_frame_no(&self) -> u64 {
+        self.next_frame_no
+    }
+
+    /// Whether rendered frames are converted to the backend format on every present
+    pub fn is_converting(&self) -> bool {
+        needs_conversion(self.frame_format, B::FORMAT)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.buffer.size().0
+    }
+
+    pub fn height(&self) -> u32 {
+        self.buffer.size().1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::{memory, MemoryBackend};
+    use crate::{DoubleBuffer, FnRenderer};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct MockRenderer {
+        render_count: usize,
+        frame_numbers: Vec<u64>,
+    }
+
+    impl MockRenderer {
+        fn new() -> Self {
+            Self {
+                render_count: 0,
+                frame_numbers: Vec::new(),
+            }
+        }
+    }
+
+    impl Renderer for MockRenderer {
+        fn format(&self) -> PixelFormat {
+            PixelFormat::Rgba8
+        }
+
+        fn render(&mut self, frame: &mut [u8], width: u32, height: u32, frame_no: u64) {
+            self.render_count += 1;
+            self.frame_numbers.push(frame_no);
+            let expected_size = (width * height * 4) as usize;
+            assert_eq!(frame.len(), expected_size);
+
+            for (i, byte) in frame.iter_mut().enumerate() {
+                *byte = ((self.render_count + i) % 256) as u8;
+            }
+        }
+    }
+
+    struct MockBackend {
+        init_called: bool,
+        present_count: usize,
+        last_frame: Vec<u8>,
+        frame_numbers: Vec<u64>,
+        blocking_presents: usize,
+        fail_next_present: bool,
+    }
+
+    impl MockBackend {
+        fn new() -> Self {
+            Self {
+                init_called: false,
+                present_count: 0,
+                last_frame: Vec::new(),
+                frame_numbers: Vec::new(),
+                blocking_presents: 0,
+                fail_next_present: false,
+            }
+        }
+    }
+
+    impl DisplayBackend for MockBackend {
+        const FORMAT: PixelFormat = PixelFormat::Rgba8;
+
+        fn init(&mut self, _width: u32, _height: u32) -> Result<(), VideoBufferError> {
+            self.init_called = true;
+            Ok(())
+        }
+
+        fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+            if std::mem::take(&mut self.fail_next_present) {
+                return Err(VideoBufferError::PresentFailed("surface lost".to_string()));
+            }
+            self.present_count += 1;
+            self.last_frame = frame.to_vec();
+            Ok(())
+        }
+
+        fn present_numbered(
+            &mut self,
+            frame_no: u64,
+            frame: &[u8],
+        ) -> Result<(), VideoBufferError> {
+            self.frame_numbers.push(frame_no);
+            self.present(frame)
+        }
+
+        fn present_blocking(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+            self.blocking_presents += 1;
+            self.present(frame)
+        }
+    }
+
+    #[test]
+    fn test_bridge_creation() {
+        let backend = MockBackend::new();
+        let bridge = DisplayBridge::new(backend, 320, 200, PixelFormat::Rgba8).unwrap();
+
+        assert_eq!(bridge.width(), 320);
+        assert_eq!(bridge.height(), 200);
+        assert!(bridge.backend.init_called);
+    }
+
+    #[test]
+    fn test_render_frame_no_conversion() {
+        let backend = MockBackend::new();
+        let mut bridge = DisplayBridge::new(backend, 100, 100, PixelFormat::Rgba8).unwrap();
+        let mut renderer = MockRenderer::new();
+
+        assert!(bridge.convert_buffer.is_none());
+        assert!(!bridge.is_converting());
+
+        bridge.render_frame(&mut renderer).unwrap();
+
+        assert_eq!(renderer.render_count, 1);
+        assert_eq!(bridge.backend.present_count, 1);
+        assert_eq!(bridge.backend.last_frame.len(), 100 * 100 * 4);
+    }
+
+    struct ManualClock(Rc<RefCell<f64>>);
+
+    impl Clock for ManualClock {
+        fn now_ms(&self) -> f64 {
+            *self.0.borrow()
+        }
+    }
+
+    #[test]
+    fn test_presenter_reads_its_clock() {
+        let time = Rc::new(RefCell::new(100.0));
+        let mut presenter = DisplayPresenter::new(MockBackend::new(), 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(10.0)
+            .with_clock(ManualClock(Rc::clone(&time)));
+
+        assert!(presenter.present_frame(&[0; 4]).unwrap());
+        *time.borrow_mut() = 150.0;
+        assert!(!presenter.present_frame(&[0; 4]).unwrap());
+        *time.borrow_mut() = 200.0;
+        assert!(presenter.present_frame(&[0; 4]).unwrap());
+        assert_eq!(presenter.now_ms().unwrap(), 200.0);
+    }
+
+    #[test]
+    fn test_present_without_clock_fails() {
+        let mut presenter =
+            DisplayPresenter::new(MockBackend::new(), 1, 1, PixelFormat::Rgba8).unwrap();
+        presenter.clock = None;
+
+        let result = presenter.present_frame(&[0; 4]);
+
+        assert!(matches!(result, Err(VideoBufferError::NotInitialized)));
+        assert_eq!(presenter.backend.present_count, 0);
+    }
+
+    #[test]
+    fn test_render_ahead_and_present_later() {
+        let backend = MockBackend::new();
+        let mut bridge = DisplayBridge::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+        let mut renderer = MockRenderer::new();
+
+        bridge.render(&mut renderer).unwrap();
+        bridge.render(&mut renderer).unwrap();
+        assert_eq!(bridge.backend.present_count, 0);
+
+        // The newer frame wins
+        bridge.present().unwrap();
+        let rendered: Vec<u8> = (0..16).map(|i| (2 + i) as u8).collect();
+        assert_eq!(bridge.backend.last_frame, rendered);
+
+        // Nothing new: the same frame is shown again
+        bridge.present().unwrap();
+        assert_eq!(bridge.backend.present_count, 2);
+        assert_eq!(bridge.backend.last_frame, rendered);
+    }
+
+    #[test]
+    fn test_multiple_frames() {
+        let backend = MockBackend::new();
+        let mut bridge = DisplayBridge::new(backend, 50, 50, PixelFormat::Rgba8).unwrap();
+        let mut renderer = MockRenderer::new();
+
+        for i in 0..10 {
+            bridge.render_frame(&mut renderer).unwrap();
+            assert_eq!(renderer.render_count, i + 1);
+        }
+
+        assert_eq!(bridge.backend.present_count, 10);
+        assert_eq!(renderer.frame_numbers, (0..10).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn test_frame_number_wraps() {
+        let backend = MockBackend::new();
+        let mut bridge = DisplayBridge::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+        let mut renderer = MockRenderer::new();
+        bridge.next_frame_no = u64::MAX;
+
+        bridge.render_frame(&mut renderer).unwrap();
+        bridge.render_frame(&mut renderer).unwrap();
+
+        assert_eq!(renderer.frame_numbers, vec![u64::MAX, 0]);
+        assert_eq!(bridge.next_frame_no(), 1);
+    }
+
+    #[test]
+    fn test_triple_buffer_cycling() {
+        let backend = MockBackend::new();
+        let mut bridge = DisplayBridge::new(backend, 10, 10, PixelFormat::Rgba8).unwrap();
+        let mut renderer = MockRenderer::new();
+
+        // Render 3 frames to ensure all buffers are cycled
+        for _ in 0..3 {
+            bridge.render_frame(&mut renderer).unwrap();
+        }
+
+        assert_eq!(renderer.render_count, 3);
         assert_eq!(bridge.backend.present_count, 3);
     }
+
+    #[test]
+    fn test_present_frame_without_number_uses_plain_present() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+
+        presenter.present_frame_at(&[0u8; 16], 0.0).unwrap();
+
+        assert_eq!(presenter.backend.present_count, 1);
+        assert!(presenter.backend.frame_numbers.is_empty());
+    }
+
+    #[test]
+    fn test_hold_as_current_presents_skipped_frame() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(10.0)
+            .with_skip_policy(SkipPolicy::HoldAsCurrent);
+
+        assert!(presenter.present_frame_at(&[1u8; 16], 100.0).unwrap());
+        // Too soon: skipped but held
+        assert!(!presenter.present_frame_at(&[2u8; 16], 150.0).unwrap());
+        assert!(!presenter.present_held(180.0).unwrap());
+
+        // Rate window opens: the held frame is shown
+        assert!(presenter.present_held(200.0).unwrap());
+        assert_eq!(presenter.backend.last_frame, vec![2u8; 16]);
+        assert_eq!(presenter.backend.present_count, 2);
+
+        // Nothing left to hold
+        assert!(!presenter.present_held(300.0).unwrap());
+    }
+
+    #[test]
+    fn test_held_frame_survives_failed_present() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(10.0)
+            .with_skip_policy(SkipPolicy::HoldAsCurrent);
+
+        assert!(presenter.present_frame_at(&[1u8; 16], 100.0).unwrap());
+        assert!(!presenter.present_frame_at(&[2u8; 16], 150.0).unwrap());
+
+        presenter.backend.fail_next_present = true;
+        assert!(presenter.present_held(200.0).is_err());
+        assert!(presenter.has_held_frame());
+
+        assert!(presenter.present_held(200.0).unwrap());
+        assert_eq!(presenter.backend.last_frame, vec![2u8; 16]);
+        assert!(!presenter.has_held_frame());
+    }
+
+    #[test]
+    fn test_interpolation_holds_blend_of_early_frame() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(10.0)
+            .with_interpolation(true);
+
+        assert!(presenter.present_frame_at(&[0, 0, 0, 255], 100.0).unwrap());
+        // Halfway through the display interval: held as the midpoint
+        assert!(!presenter
+            .present_frame_at(&[200, 100, 50, 255], 150.0)
+            .unwrap());
+        assert!(presenter.present_held(200.0).unwrap());
+        assert_eq!(presenter.backend.last_frame, [100, 50, 25, 255]);
+    }
+
+    #[test]
+    #[should_panic(expected = "interpolation needs Rgba8 frames")]
+    fn test_interpolation_rejects_other_formats() {
+        let backend = MockBackend::new();
+        let _ = DisplayPresenter::new(backend, 1, 1, PixelFormat::Prgb8)
+            .unwrap()
+            .with_interpolation(true);
+    }
+
+    #[test]
+    fn test_discard_policy_drops_skipped_frame() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(10.0);
+
+        assert!(presenter.present_frame_at(&[1u8; 16], 100.0).unwrap());
+        assert!(!presenter.present_frame_at(&[2u8; 16], 150.0).unwrap());
+
+        assert!(!presenter.present_held(200.0).unwrap());
+        assert_eq!(presenter.backend.last_frame, vec![1u8; 16]);
+    }
+
+    #[test]
+    fn test_newer_frame_supersedes_held_frame() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(10.0)
+            .with_skip_policy(SkipPolicy::HoldAsCurrent);
+
+        assert!(presenter.present_frame_at(&[1u8; 16], 100.0).unwrap());
+        assert!(!presenter.present_frame_at(&[2u8; 16], 150.0).unwrap());
+        assert!(presenter.present_frame_at(&[3u8; 16], 200.0).unwrap());
+
+        assert!(!presenter.present_held(300.0).unwrap());
+        assert_eq!(presenter.backend.last_frame, vec![3u8; 16]);
+    }
+
+    #[test]
+    fn test_fade_in_midpoint_is_half_brightness() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8)
+            .unwrap()
+            .with_fade_in(1000.0);
+        let white = [255u8; 16];
+
+        presenter.present_frame_at(&white, 0.0).unwrap();
+        assert_eq!(&presenter.backend.last_frame[..4], &[0, 0, 0, 255]);
+
+        presenter.present_frame_at(&white, 500.0).unwrap();
+        assert_eq!(&presenter.backend.last_frame[..4], &[128, 128, 128, 255]);
+
+        presenter.present_frame_at(&white, 1000.0).unwrap();
+        assert_eq!(presenter.backend.last_frame, white.to_vec());
+    }
+
+    #[test]
+    fn test_fade_out_applies_to_converted_frames() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Prgb8)
+            .unwrap()
+            .with_fade_out(1000.0, 200.0);
+        let white = [255u8; 16];
+
+        presenter.present_frame_at(&white, 0.0).unwrap();
+        assert_eq!(presenter.backend.last_frame, white.to_vec());
+
+        presenter.present_frame_at(&white, 1100.0).unwrap();
+        assert_eq!(&presenter.backend.last_frame[..4], &[128, 128, 128, 255]);
+
+        presenter.present_frame_at(&white, 1200.0).unwrap();
+        assert_eq!(&presenter.backend.last_frame[..4], &[0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_presenters_share_convert_buffer() {
+        let scratch = Arc::new(Mutex::new(Vec::new()));
+        let mut first = DisplayPresenter::new(MockBackend::new(), 1, 1, PixelFormat::Prgb8)
+            .unwrap()
+            .with_shared_convert_buffer(Arc::clone(&scratch));
+        let mut second = DisplayPresenter::new(MockBackend::new(), 1, 1, PixelFormat::Prgb8)
+            .unwrap()
+            .with_shared_convert_buffer(Arc::clone(&scratch));
+
+        assert!(first.convert_buffer.is_none());
+        assert!(second.convert_buffer.is_none());
+
+        first.present_frame_at(&[255, 10, 20, 30], 0.0).unwrap();
+        second.present_frame_at(&[128, 40, 50, 60], 0.0).unwrap();
+
+        assert_eq!(first.backend.last_frame, vec![10, 20, 30, 255]);
+        assert_eq!(second.backend.last_frame, vec![40, 50, 60, 128]);
+        assert_eq!(scratch.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_callbacks_can_present_through_presenters_sharing_convert_buffer() {
+        let scratch = Arc::new(Mutex::new(Vec::new()));
+        let second = Rc::new(RefCell::new(
+            DisplayPresenter::new(MockBackend::new(), 1, 1, PixelFormat::Prgb8)
+                .unwrap()
+                .with_shared_convert_buffer(Arc::clone(&scratch)),
+        ));
+        let tapped = Rc::clone(&second);
+        let mirrored = Rc::clone(&second);
+        let mut first = DisplayPresenter::new(MockBackend::new(), 1, 1, PixelFormat::Prgb8)
+            .unwrap()
+            .with_shared_convert_buffer(Arc::clone(&scratch))
+            .with_tap(Box::new(move |_| {
+                tapped
+                    .borrow_mut()
+                    .present_frame_at(&[128, 40, 50, 60], 100.0)
+                    .unwrap();
+            }))
+            .with_on_present(Box::new(move |_, now_ms| {
+                mirrored
+                    .borrow_mut()
+                    .present_frame_at(&[255, 70, 80, 90], now_ms + 100.0)
+                    .unwrap();
+            }));
+
+        first.present_frame_at(&[255, 10, 20, 30], 100.0).unwrap();
+
+        assert_eq!(first.backend.last_frame, vec![10, 20, 30, 255]);
+        assert_eq!(second.borrow().backend.last_frame, vec![70, 80, 90, 255]);
+        assert_eq!(second.borrow().presented_count(), 2);
+        assert_eq!(scratch.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_builder_applies_options() {
+        let now = Rc::new(RefCell::new(100.0));
+        let presents = Rc::new(RefCell::new(0));
+        let counted = Rc::clone(&presents);
+        let mut presenter = DisplayPresenter::builder(MockBackend::new(), 1, 1, PixelFormat::Prgb8)
+            .scale_mode((2, 1), ScaleMode::Nearest)
+            .max_fps(10.0)
+            .clock(ManualClock(Rc::clone(&now)))
+            .on_present(Box::new(move |_, _| *counted.borrow_mut() += 1))
+            .build()
+            .unwrap();
+
+        assert!(presenter.backend.init_called);
+        assert!(presenter.present_frame(&[255, 1, 2, 3]).unwrap());
+        *now.borrow_mut() = 150.0;
+        assert!(!presenter.present_frame(&[255, 1, 2, 3]).unwrap());
+
+        assert_eq!(*presents.borrow(), 1);
+        assert_eq!(presenter.backend.last_frame, [1, 2, 3, 255, 1, 2, 3, 255]);
+    }
+
+    #[test]
+    fn test_builder_rejects_interpolation_of_other_formats() {
+        let result = DisplayPresenter::builder(MockBackend::new(), 1, 1, PixelFormat::Prgb8)
+            .interpolation(true)
+            .build();
+        assert!(matches!(result, Err(VideoBufferError::InitFailed(_))));
+
+        let presenter = DisplayPresenter::builder(MockBackend::new(), 1, 1, PixelFormat::Rgba8)
+            .interpolation(true)
+            .build()
+            .unwrap();
+        assert!(presenter.interpolation);
+    }
+
+    #[test]
+    fn test_builder_applies_fade_out() {
+        let mut presenter = DisplayPresenter::builder(MockBackend::new(), 1, 1, PixelFormat::Rgba8)
+            .fade_out(100.0, 100.0)
+            .build()
+            .unwrap();
+
+        presenter
+            .present_frame_at(&[200, 200, 200, 255], 300.0)
+            .unwrap();
+
+        assert_eq!(presenter.backend.last_frame, [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_present_frame_into_uses_caller_scratch() {
+        let mut scratch = Vec::new();
+        let mut presenter =
+            DisplayPresenter::new(MockBackend::new(), 1, 1, PixelFormat::Prgb8).unwrap();
+
+        presenter
+            .present_frame_into(&[255, 1, 2, 3], &mut scratch, 0.0)
+            .unwrap();
+
+        assert_eq!(scratch, [1, 2, 3, 255]);
+        assert_eq!(presenter.backend.last_frame, [1, 2, 3, 255]);
+        assert!(presenter.lent_convert_buffer.is_none());
+        // The presenter's own buffer was left alone
+        assert_eq!(presenter.convert_buffer, Some(vec![0; 4]));
+    }
+
+    #[test]
+    fn test_shared_convert_buffer_ignored_without_conversion() {
+        let scratch = Arc::new(Mutex::new(Vec::new()));
+        let mut presenter = DisplayPresenter::new(MockBackend::new(), 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_shared_convert_buffer(Arc::clone(&scratch));
+
+        presenter.present_frame_at(&[1, 2, 3, 4], 0.0).unwrap();
+
+        assert!(presenter.shared_convert_buffer.is_none());
+        assert!(scratch.lock().unwrap().is_empty());
+        assert!(!presenter.is_converting());
+        assert_eq!(presenter.backend.last_frame, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_present_stereo_side_by_side() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 1, PixelFormat::Rgba8).unwrap();
+        let left = [1, 1, 1, 255, 2, 2, 2, 255];
+        let right = [3, 3, 3, 255, 4, 4, 4, 255];
+
+        assert!(presenter
+            .present_stereo(&left, &right, StereoLayout::SideBySide, 0.0)
+            .unwrap());
+
+        // Each half shows its eye's first column
+        assert_eq!(
+            presenter.backend.last_frame,
+            vec![1, 1, 1, 255, 3, 3, 3, 255]
+        );
+    }
+
+    #[test]
+    fn test_present_stereo_frame_sequential_alternates() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8).unwrap();
+        let left = [1, 1, 1, 255];
+        let right = [2, 2, 2, 255];
+
+        let mut shown = Vec::new();
+        for i in 0..4 {
+            presenter
+                .present_stereo(&left, &right, StereoLayout::FrameSequential, i as f64)
+                .unwrap();
+            shown.push(presenter.backend.last_frame[0]);
+        }
+
+        assert_eq!(shown, vec![1, 2, 1, 2]);
+    }
+
+    /// Backend whose 4x4 surface keeps its contents between presents and receives
+    /// 2x2 frames blitted at offset (1, 1)
+    struct OffsetBackend {
+        surface: Vec<u8>,
+    }
+
+    impl DisplayBackend for OffsetBackend {
+        const FORMAT: PixelFormat = PixelFormat::Rgba8;
+
+        fn init(&mut self, _width: u32, _height: u32) -> Result<(), VideoBufferError> {
+            Ok(())
+        }
+
+        fn clear(&mut self, color: [u8; 4]) -> Result<(), VideoBufferError> {
+            for pixel in self.surface.chunks_exact_mut(4) {
+                pixel.copy_from_slice(&color);
+            }
+            Ok(())
+        }
+
+        fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+            for (y, row) in frame.chunks_exact(8).enumerate() {
+                let offset = ((y + 1) * 4 + 1) * 4;
+                self.surface[offset..offset + 8].copy_from_slice(row);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_clear_color_fills_area_outside_offset_blit() {
+        let backend = OffsetBackend {
+            surface: vec![99u8; 64], // stale garbage
+        };
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8)
+            .unwrap()
+            .with_clear_color([10, 20, 30, 255]);
+
+        presenter.present_frame_at(&[200u8; 16], 0.0).unwrap();
+
+        for (i, pixel) in presenter.backend.surface.chunks_exact(4).enumerate() {
+            let (x, y) = (i % 4, i / 4);
+            if (1..3).contains(&x) && (1..3).contains(&y) {
+                assert_eq!(pixel, [200, 200, 200, 200]);
+            } else {
+                assert_eq!(pixel, [10, 20, 30, 255], "pixel ({}, {}) not cleared", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_clear_color_leaves_surface() {
+        let backend = OffsetBackend {
+            surface: vec![99u8; 64],
+        };
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+
+        presenter.present_frame_at(&[200u8; 16], 0.0).unwrap();
+
+        assert_eq!(&presenter.backend.surface[..4], &[99, 99, 99, 99]);
+    }
+
+    #[test]
+    fn test_present_accumulated_averages_to_mid_gray() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+
+        presenter
+            .present_accumulated(&[0, 0, 0, 255].repeat(4), false, 0.0)
+            .unwrap();
+        presenter
+            .present_accumulated(&[255, 255, 255, 255].repeat(4), false, 16.0)
+            .unwrap();
+
+        assert_eq!(presenter.backend.last_frame, [128, 128, 128, 255].repeat(4));
+    }
+
+    #[test]
+    fn test_present_accumulated_reset_starts_new_average() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+
+        presenter
+            .present_accumulated(&[0, 0, 0, 255].repeat(4), false, 0.0)
+            .unwrap();
+        presenter
+            .present_accumulated(&[40, 40, 40, 255].repeat(4), true, 16.0)
+            .unwrap();
+
+        assert_eq!(presenter.backend.last_frame, [40, 40, 40, 255].repeat(4));
+    }
+
+    #[test]
+    fn test_tap_sees_converted_bytes() {
+        let captured = Rc::new(RefCell::new(Vec::new()));
+        let tap_captured = Rc::clone(&captured);
+
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Prgb8)
+            .unwrap()
+            .with_tap(Box::new(move |frame| {
+                tap_captured.borrow_mut().push(frame.to_vec())
+            }));
+
+        // PRGB (A, R, G, B) -> RGBA
+        presenter.present_frame_at(&[255, 10, 20, 30], 0.0).unwrap();
+
+        assert_eq!(*captured.borrow(), vec![vec![10, 20, 30, 255]]);
+        assert_eq!(presenter.backend.last_frame, captured.borrow()[0]);
+    }
+
+    #[test]
+    fn test_on_present_skips_rate_limited_frames() {
+        let presents = Rc::new(RefCell::new(Vec::new()));
+        let recorded = Rc::clone(&presents);
+        let mut presenter = DisplayPresenter::new(MockBackend::new(), 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(10.0)
+            .with_on_present(Box::new(move |frame_no, now_ms| {
+                recorded.borrow_mut().push((frame_no, now_ms))
+            }));
+
+        for now_ms in [100.0, 150.0, 200.0] {
+            presenter.present_frame_at(&[0; 4], now_ms).unwrap();
+        }
+
+        assert_eq!(*presents.borrow(), [(0, 100.0), (1, 200.0)]);
+    }
+
+    #[test]
+    fn test_present_next_or_hold_presents_ready_frame() {
+        let mut queue = FrameQueue::new(4);
+        queue.push(0, vec![1; 16]);
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+
+        assert!(presenter
+            .present_next_or_hold(&mut queue, 100.0, 0.0)
+            .unwrap());
+        assert_eq!(presenter.backend.frame_numbers, vec![0]);
+        assert_eq!(presenter.backend.last_frame, vec![1; 16]);
+    }
+
+    #[test]
+    fn test_present_next_or_hold_waits_before_deadline() {
+        let mut queue = FrameQueue::new(4);
+        queue.push(0, vec![1; 16]);
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+        presenter
+            .present_next_or_hold(&mut queue, 100.0, 0.0)
+            .unwrap();
+
+        assert!(!presenter
+            .present_next_or_hold(&mut queue, 100.0, 50.0)
+            .unwrap());
+        assert_eq!(presenter.backend.present_count, 1);
+    }
+
+    #[test]
+    fn test_present_next_or_hold_repeats_after_deadline() {
+        let mut queue = FrameQueue::new(4);
+        queue.push(0, vec![1; 16]);
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+        presenter
+            .present_next_or_hold(&mut queue, 100.0, 0.0)
+            .unwrap();
+
+        assert!(presenter
+            .present_next_or_hold(&mut queue, 100.0, 120.0)
+            .unwrap());
+        assert_eq!(presenter.backend.frame_numbers, vec![0, 0]);
+        assert_eq!(presenter.backend.last_frame, vec![1; 16]);
+
+        // A newly ready frame takes over from the repeated one
+        queue.push(1, vec![2; 16]);
+        assert!(presenter
+            .present_next_or_hold(&mut queue, 300.0, 140.0)
+            .unwrap());
+        assert_eq!(presenter.backend.frame_numbers, vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn test_vsync_selects_blocking_present() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8)
+            .unwrap()
+            .with_vsync(true);
+
+        presenter.present_frame_at(&[0u8; 16], 0.0).unwrap();
+        presenter
+            .present_numbered_frame(7, &[0u8; 16], 16.0)
+            .unwrap();
+
+        assert_eq!(presenter.backend.blocking_presents, 2);
+        assert_eq!(presenter.backend.present_count, 2);
+    }
+
+    #[test]
+    fn test_without_vsync_presents_without_blocking() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+
+        presenter.present_frame_at(&[0u8; 16], 0.0).unwrap();
+
+        assert_eq!(presenter.backend.blocking_presents, 0);
+        assert_eq!(presenter.backend.present_count, 1);
+    }
+
+    #[test]
+    fn test_present_still_converts_once() {
+        let shared = Arc::new(Mutex::new(Vec::new()));
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Prgb8)
+            .unwrap()
+            .with_max_fps(60.0)
+            .with_shared_convert_buffer(Arc::clone(&shared));
+
+        assert!(!presenter.present_still(0.0).unwrap());
+        presenter.set_still(&[255, 10, 20, 30]);
+
+        assert!(presenter.present_still(100.0).unwrap());
+        assert!(!presenter.present_still(101.0).unwrap()); // rate limited
+        assert!(presenter.present_still(200.0).unwrap());
+        assert!(presenter.present_still(300.0).unwrap());
+
+        assert_eq!(presenter.backend.present_count, 3);
+        assert_eq!(presenter.backend.last_frame, vec![10, 20, 30, 255]);
+        // Presents reuse the cached bytes and never touch the convert buffer
+        assert!(shared.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_present_frame_timed_drops_stale_frame() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8)
+            .unwrap()
+            .with_skip_policy(SkipPolicy::HoldAsCurrent);
+
+        assert!(!presenter
+            .present_frame_timed(&[1u8; 16], 0.0, 100.0, 50.0)
+            .unwrap());
+        assert_eq!(presenter.backend.present_count, 0);
+        // Stale frames are not held for later either
+        assert!(!presenter.present_held(200.0).unwrap());
+
+        assert!(presenter
+            .present_frame_timed(&[2u8; 16], 90.0, 100.0, 50.0)
+            .unwrap());
+        assert_eq!(presenter.backend.last_frame, vec![2u8; 16]);
+    }
+
+    struct MismatchedSurfaceBackend;
+
+    impl DisplayBackend for MismatchedSurfaceBackend {
+        const FORMAT: PixelFormat = PixelFormat::Rgba8;
+
+        fn init(&mut self, _width: u32, _height: u32) -> Result<(), VideoBufferError> {
+            Ok(())
+        }
+
+        fn surface_format(&self) -> Option<PixelFormat> {
+            Some(PixelFormat::Prgb8)
+        }
+
+        fn present(&mut self, _frame: &[u8]) -> Result<(), VideoBufferError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_mismatched_surface_format_fails_init() {
+        let result = DisplayPresenter::new(MismatchedSurfaceBackend, 2, 2, PixelFormat::Rgba8);
+        assert!(matches!(result, Err(VideoBufferError::InitFailed(_))));
+
+        let result = DisplayBridge::new(MismatchedSurfaceBackend, 2, 2, PixelFormat::Rgba8);
+        assert!(matches!(result, Err(VideoBufferError::InitFailed(_))));
+    }
+
+    struct Rgb565Backend {
+        last_frame: Vec<u8>,
+    }
+
+    impl DisplayBackend for Rgb565Backend {
+        const FORMAT: PixelFormat = PixelFormat::Rgb565;
+
+        fn init(&mut self, _width: u32, _height: u32) -> Result<(), VideoBufferError> {
+            Ok(())
+        }
+
+        fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+            self.last_frame = frame.to_vec();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_with_dithering_dithers_rgb565_output() {
+        // Flat red between two RGB565 levels
+        let frame = [100, 0, 0, 255].repeat(16);
+        let present = |dithering| {
+            let backend = Rgb565Backend {
+                last_frame: Vec::new(),
+            };
+            let mut presenter = DisplayPresenter::new(backend, 4, 4, PixelFormat::Rgba8)
+                .unwrap()
+                .with_dithering(dithering);
+            presenter.present_frame_at(&frame, 0.0).unwrap();
+            let mut levels: Vec<u8> = presenter
+                .backend
+                .last_frame
+                .chunks_exact(2)
+                .map(|pixel| pixel[1] >> 3)
+                .collect();
+            levels.sort_unstable();
+            levels.dedup();
+            levels
+        };
+
+        assert_eq!(present(false).len(), 1);
+        assert_eq!(present(true).len(), 2);
+    }
+
+    #[test]
+    fn test_present_pull_only_fills_presented_frames() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(30.0);
+        let mut fills = Vec::new();
+
+        for tick in 1..=6 {
+            let now_ms = tick as f64 * 17.0;
+            presenter
+                .present_pull(
+                    |frame, frame_no| {
+                        fills.push(frame_no);
+                        frame.fill(frame_no as u8);
+                    },
+                    now_ms,
+                )
+                .unwrap();
+        }
+
+        // 60Hz ticks against a 30fps cap: every other tick presents
+        assert_eq!(fills, vec![0, 1, 2]);
+        assert_eq!(presenter.backend.present_count, 3);
+        assert_eq!(presenter.backend.frame_numbers, vec![0, 1, 2]);
+        assert_eq!(presenter.backend.last_frame, vec![2u8; 16]);
+    }
+
+    #[test]
+    fn test_rgb8_triple_buffer_presents_as_rgba() {
+        let buffer = TripleBuffer::new(2, 1, PixelFormat::Rgb8);
+        buffer
+            .render_buffer()
+            .copy_from_slice(&[10, 20, 30, 40, 50, 60]);
+        buffer.commit_render();
+
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 1, PixelFormat::Rgb8).unwrap();
+        presenter.present_at(&buffer, 0.0).unwrap();
+
+        assert_eq!(
+            presenter.backend.last_frame,
+            vec![10, 20, 30, 255, 40, 50, 60, 255]
+        );
+    }
+
+    #[test]
+    fn test_present_with_deadline_reports_lateness() {
+        let buffer = TripleBuffer::new(2, 2, PixelFormat::Rgba8);
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+
+        let on_time = presenter
+            .present_with_deadline(&buffer, 16.0, 10.0)
+            .unwrap();
+        let late = presenter
+            .present_with_deadline(&buffer, 32.0, 36.5)
+            .unwrap();
+
+        assert_eq!(on_time, PresentOutcome::OnTime);
+        assert_eq!(late, PresentOutcome::Late(4.5));
+        // Late frames are still shown
+        assert_eq!(presenter.backend.present_count, 2);
+    }
+
+    #[test]
+    fn test_present_with_deadline_reports_skipped_frames() {
+        let buffer = TripleBuffer::new(2, 2, PixelFormat::Rgba8);
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(30.0);
+
+        presenter
+            .present_with_deadline(&buffer, 40.0, 40.0)
+            .unwrap();
+        let outcome = presenter
+            .present_with_deadline(&buffer, 50.0, 50.0)
+            .unwrap();
+
+        assert_eq!(outcome, PresentOutcome::Skipped);
+        assert_eq!(presenter.backend.present_count, 1);
+    }
+
+    #[test]
+    fn test_present_partial_updates_completed_rows() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 4, PixelFormat::Prgb8).unwrap();
+
+        // Opaque pixels with R = row index + 1, top half arrived first
+        let full: Vec<u8> = (1..=4).flat_map(|r| [255, r, 0, 0]).collect();
+        let mut arriving = vec![0u8; 16];
+        arriving[..8].copy_from_slice(&full[..8]);
+
+        presenter.present_partial(&arriving, 2, 10.0).unwrap();
+        assert_eq!(
+            presenter.backend.last_frame,
+            vec![1, 0, 0, 255, 2, 0, 0, 255, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+
+        presenter.present_partial(&full, 4, 20.0).unwrap();
+        assert_eq!(
+            presenter.backend.last_frame,
+            vec![1, 0, 0, 255, 2, 0, 0, 255, 3, 0, 0, 255, 4, 0, 0, 255]
+        );
+    }
+
+    #[test]
+    fn test_present_region_rejects_bad_input() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+        let frame = [0u8; 16];
+
+        let outside = presenter.present_region(&frame, Rect::new(1, 1, 2, 1), 10.0);
+        assert!(matches!(
+            outside,
+            Err(VideoBufferError::RegionOutOfBounds { .. })
+        ));
+        let short = presenter.present_region(&frame[..8], Rect::new(0, 0, 1, 1), 10.0);
+        assert!(matches!(
+            short,
+            Err(VideoBufferError::SizeMismatch {
+                expected: 16,
+                actual: 8
+            })
+        ));
+        assert_eq!(presenter.backend.present_count, 0);
+
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::I420).unwrap();
+        let result = presenter.present_region(&[0; 6], Rect::full(2, 2), 10.0);
+        assert!(matches!(
+            result,
+            Err(VideoBufferError::UnsupportedFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_effective_output_size_matches_backend_frames() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 3, 2, PixelFormat::Prgb8)
+            .unwrap()
+            .with_fade_in(100.0);
+        presenter.present_frame_at(&[0u8; 24], 10.0).unwrap();
+
+        let (width, height) = presenter.effective_output_size();
+        assert_eq!((width, height), (3, 2));
+        assert_eq!(
+            presenter.backend.last_frame.len(),
+            PixelFormat::Rgba8.buffer_size(width, height)
+        );
+    }
+
+    #[test]
+    fn test_judder_reduction_blends_24fps_source_at_60fps() {
+        let present_all = |judder_reduction: bool| {
+            let mut queue = FrameQueue::new(8);
+            for frame_no in 0..4u8 {
+                queue.push(frame_no as u64, vec![frame_no * 50; 4]);
+            }
+            let backend = MockBackend::new();
+            let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8)
+                .unwrap()
+                .with_judder_reduction(judder_reduction);
+
+            let mut shown = Vec::new();
+            for tick in 0..5 {
+                let now_ms = 100.0 + tick as f64 * 1000.0 / 60.0;
+                presenter
+                    .present_at_source_rate(&mut queue, 24.0, now_ms)
+                    .unwrap();
+                shown.push(presenter.backend.last_frame[0]);
+            }
+            shown
+        };
+
+        // Source positions 0.0, 0.4, 0.8, 1.2 and 1.6 frames
+        assert_eq!(present_all(false), vec![0, 0, 0, 50, 50]);
+        assert_eq!(present_all(true), vec![0, 20, 40, 60, 80]);
+    }
+
+    #[test]
+    fn test_source_rate_starts_at_queue_position() {
+        let mut queue = FrameQueue::new(8);
+        queue.reset(50);
+        queue.push(50, vec![50; 4]);
+        queue.push(51, vec![51; 4]);
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8).unwrap();
+
+        assert!(presenter
+            .present_at_source_rate(&mut queue, 24.0, 100.0)
+            .unwrap());
+        assert_eq!(presenter.backend.last_frame, vec![50; 4]);
+        assert!(presenter
+            .present_at_source_rate(&mut queue, 24.0, 150.0)
+            .unwrap());
+        assert_eq!(presenter.backend.last_frame, vec![51; 4]);
+    }
+
+    #[test]
+    fn test_source_rate_after_seek() {
+        let mut queue = FrameQueue::new(8);
+        for frame_no in 0..2u8 {
+            queue.push(frame_no as u64, vec![frame_no; 4]);
+        }
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8).unwrap();
+        presenter
+            .present_at_source_rate(&mut queue, 24.0, 100.0)
+            .unwrap();
+        presenter
+            .present_at_source_rate(&mut queue, 24.0, 150.0)
+            .unwrap();
+        assert_eq!(presenter.backend.last_frame, vec![1; 4]);
+
+        // Seek back to frame 0 ten seconds in
+        queue.reset(0);
+        presenter.reset_source_clock();
+        queue.push(0, vec![10; 4]);
+        queue.push(1, vec![11; 4]);
+
+        assert!(presenter
+            .present_at_source_rate(&mut queue, 24.0, 10_000.0)
+            .unwrap());
+        assert_eq!(presenter.backend.last_frame, vec![10; 4]);
+        assert!(presenter
+            .present_at_source_rate(&mut queue, 24.0, 10_050.0)
+            .unwrap());
+        assert_eq!(presenter.backend.last_frame, vec![11; 4]);
+    }
+
+    #[test]
+    fn test_present_viewport_rejects_bad_input() {
+        let canvas = [0u8; 24];
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+
+        let too_small = presenter.present_viewport(&canvas, 3, 2, Rect::new(0, 0, 1, 2), 10.0);
+        assert!(matches!(
+            too_small,
+            Err(VideoBufferError::SizeMismatch {
+                expected: 16,
+                actual: 8
+            })
+        ));
+        let short_canvas =
+            presenter.present_viewport(&canvas[..20], 3, 2, Rect::new(0, 0, 2, 2), 10.0);
+        assert!(matches!(
+            short_canvas,
+            Err(VideoBufferError::SizeMismatch { .. })
+        ));
+        assert_eq!(presenter.backend.present_count, 0);
+    }
+
+    #[test]
+    fn test_present_viewport_fills_beyond_canvas_edge() {
+        // 3x2 opaque white canvas, viewport hanging one pixel off the right and bottom
+        let canvas = [255u8; 24];
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 2, 2, PixelFormat::Rgba8).unwrap();
+
+        presenter
+            .present_viewport(&canvas, 3, 2, Rect::new(2, 1, 2, 2), 10.0)
+            .unwrap();
+
+        let white = [255u8; 4];
+        let fill = [0u8; 4];
+        assert_eq!(
+            presenter.backend.last_frame,
+            [white, fill, fill, fill].concat()
+        );
+    }
+
+    #[test]
+    fn test_pacing_statistics() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(50.0);
+        assert_eq!(presenter.fps_estimate(), 0.0);
+
+        // Offered every 10 ms, so every other frame is rate limited
+        for tick in 1..=9 {
+            presenter
+                .present_frame_at(&[0; 4], tick as f64 * 10.0 + 10.0)
+                .unwrap();
+        }
+
+        assert_eq!(presenter.presented_count(), 5);
+        assert_eq!(presenter.skipped_count(), 4);
+        assert_eq!(presenter.fps_estimate(), 50.0);
+    }
+
+    #[test]
+    fn test_idle_presents_are_not_counted_as_skipped() {
+        let buffer = TripleBuffer::new(1, 1, PixelFormat::Rgba8);
+        let mut queue = FrameQueue::new(4);
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(50.0);
+        presenter.present_frame_at(&[0; 4], 100.0).unwrap();
+
+        // Rate limited, but there is no new frame that could be dropped
+        presenter.present_at(&buffer, 105.0).unwrap();
+        presenter.present_held(105.0).unwrap();
+        presenter
+            .present_next_or_hold(&mut queue, 0.0, 105.0)
+            .unwrap();
+        presenter.present_pull(|_, _| {}, 105.0).unwrap();
+        assert_eq!(presenter.skipped_count(), 0);
+
+        presenter.present_frame_at(&[0; 4], 110.0).unwrap();
+        assert_eq!(presenter.skipped_count(), 1);
+    }
+
+    #[test]
+    fn test_held_frame_is_counted_as_skipped_once_replaced() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(50.0)
+            .with_skip_policy(SkipPolicy::HoldAsCurrent);
+        presenter.present_frame_at(&[0; 4], 100.0).unwrap();
+
+        presenter.present_frame_at(&[1; 4], 105.0).unwrap();
+        assert_eq!(presenter.skipped_count(), 0);
+
+        presenter.present_frame_at(&[2; 4], 110.0).unwrap();
+        assert_eq!(presenter.skipped_count(), 1);
+
+        presenter.present_held(120.0).unwrap();
+        assert_eq!(presenter.backend.last_frame, vec![2; 4]);
+        assert_eq!(presenter.skipped_count(), 1);
+
+        // A new frame presented over a held one drops it too
+        presenter.present_frame_at(&[3; 4], 125.0).unwrap();
+        presenter.present_frame_at(&[4; 4], 140.0).unwrap();
+        assert_eq!(presenter.backend.last_frame, vec![4; 4]);
+        assert_eq!(presenter.skipped_count(), 2);
+    }
+
+    #[test]
+    fn test_frames_passed_in_and_turned_away_are_counted_as_skipped() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(50.0);
+        presenter.present_frame_at(&[0; 4], 100.0).unwrap();
+
+        let frame = [0u8; 4];
+        presenter
+            .present_stereo(&frame, &frame, StereoLayout::SideBySide, 105.0)
+            .unwrap();
+        presenter
+            .present_viewport(&frame, 1, 1, Rect::full(1, 1), 105.0)
+            .unwrap();
+        presenter
+            .present_region(&frame, Rect::full(1, 1), 105.0)
+            .unwrap();
+        presenter
+            .present_frame_timed(&frame, 0.0, 200.0, 50.0)
+            .unwrap();
+        assert_eq!(presenter.skipped_count(), 4);
+
+        // Accumulated frames stay in the average
+        presenter.present_accumulated(&frame, false, 105.0).unwrap();
+        assert_eq!(presenter.skipped_count(), 4);
+    }
+
+    #[test]
+    fn test_source_frames_overtaken_before_shown_are_counted_as_skipped() {
+        let mut queue = FrameQueue::new(8);
+        for frame_no in 0..6u8 {
+            queue.push(frame_no as u64, vec![frame_no; 4]);
+        }
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8).unwrap();
+
+        presenter
+            .present_at_source_rate(&mut queue, 24.0, 100.0)
+            .unwrap();
+        // Frames 1 to 3 fall due and are passed over before any present shows them
+        presenter
+            .present_at_source_rate(&mut queue, 24.0, 100.0 + 4.5 * 1000.0 / 24.0)
+            .unwrap();
+
+        assert_eq!(presenter.backend.last_frame, vec![4; 4]);
+        assert_eq!(presenter.skipped_count(), 3);
+    }
+
+    #[test]
+    fn test_set_max_fps_applies_new_rate_immediately() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(30.0);
+        presenter.present_frame_at(&[0; 4], 100.0).unwrap();
+
+        // 20 ms is too soon at 30 fps but enough at 50 fps
+        presenter.set_max_fps(Some(50.0));
+        assert!(presenter.present_frame_at(&[0; 4], 120.0).unwrap());
+
+        presenter.set_max_fps(None);
+        assert!(presenter.present_frame_at(&[0; 4], 121.0).unwrap());
+        assert_eq!(presenter.backend.present_count, 3);
+    }
+
+    #[test]
+    fn test_time_until_next_frame() {
+        let backend = MockBackend::new();
+        let mut presenter = DisplayPresenter::new(backend, 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(50.0);
+        presenter.present_frame_at(&[0; 4], 100.0).unwrap();
+
+        assert_eq!(presenter.time_until_next_frame(105.0), Some(15.0));
+        assert_eq!(presenter.time_until_next_frame(120.0), None);
+        assert!(presenter.present_frame_at(&[0; 4], 120.0).unwrap());
+
+        presenter.set_max_fps(None);
+        assert_eq!(presenter.time_until_next_frame(120.0), None);
+    }
+
+    #[cfg(feature = "png-capture")]
+    #[test]
+    fn test_capture_png_round_trips_presented_frame() {
+        let backend = Rgb565Backend {
+            last_frame: Vec::new(),
+        };
+        let mut presenter = DisplayPresenter::new(backend, 2, 1, PixelFormat::Rgba8).unwrap();
+        assert!(presenter.capture_png(Vec::new()).is_err());
+
+        presenter
+            .present_frame_at(&[255, 0, 0, 255, 0, 0, 255, 255], 10.0)
+            .unwrap();
+        let mut png_bytes = Vec::new();
+        presenter.capture_png(&mut png_bytes).unwrap();
+
+        let mut reader = png::Decoder::new(png_bytes.as_slice()).read_info().unwrap();
+        let mut decoded = vec![0u8; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut decoded).unwrap();
+        assert_eq!((info.width, info.height), (2, 1));
+        assert_eq!(decoded, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_present_and_present_frame_behave_identically() {
+        let frame = [255, 10, 20, 30];
+        let buffer = TripleBuffer::new(1, 1, PixelFormat::Prgb8);
+        buffer.render_buffer().copy_from_slice(&frame);
+        buffer.commit_render();
+
+        let new_presenter = || {
+            DisplayPresenter::new(MockBackend::new(), 1, 1, PixelFormat::Prgb8)
+                .unwrap()
+                .with_max_fps(50.0)
+                .with_fade_in(40.0)
+        };
+        let mut from_buffer = new_presenter();
+        let mut from_bytes = new_presenter();
+        for now_ms in [20.0, 30.0, 40.0] {
+            from_buffer.present_at(&buffer, now_ms).unwrap();
+            from_bytes.present_frame_at(&frame, now_ms).unwrap();
+        }
+
+        assert_eq!(
+            from_buffer.backend.last_frame,
+            from_bytes.backend.last_frame
+        );
+        assert_eq!(from_buffer.presented_count(), from_bytes.presented_count());
+        // The rate limited buffer frame stays buffered, the raw one is dropped
+        assert_eq!(from_buffer.skipped_count(), 0);
+        assert_eq!(from_bytes.skipped_count(), 1);
+    }
+
+    #[test]
+    fn test_present_i420_triple_buffer() {
+        let buffer = TripleBuffer::new(2, 2, PixelFormat::I420);
+        buffer
+            .render_buffer()
+            .copy_from_slice(&[235, 235, 235, 235, 128, 128]);
+        buffer.commit_render();
+
+        let mut presenter = DisplayPresenter::new(
+            MemoryBackend::<memory::Rgba8>::new(),
+            2,
+            2,
+            PixelFormat::I420,
+        )
+        .unwrap();
+        assert!(presenter.present_at(&buffer, 0.0).unwrap());
+        assert_eq!(presenter.backend.last_frame(), &[255; 16][..]);
+    }
+
+    #[test]
+    fn test_planar_source_needs_rgba8_backend() {
+        let backend = Rgb565Backend {
+            last_frame: Vec::new(),
+        };
+        let result = DisplayPresenter::new(backend, 2, 2, PixelFormat::I420);
+        assert!(matches!(result, Err(VideoBufferError::InitFailed(_))));
+    }
+
+    #[test]
+    fn test_bridge_rejects_unsupported_conversion() {
+        let backend = Rgb565Backend {
+            last_frame: Vec::new(),
+        };
+        let result = DisplayBridge::new(backend, 2, 2, PixelFormat::Gray8);
+        assert!(matches!(result, Err(VideoBufferError::InitFailed(_))));
+    }
+
+    #[test]
+    fn test_unsupported_conversion_is_rejected() {
+        for source_format in [PixelFormat::Gray8, PixelFormat::Bgr8, PixelFormat::Rgba16] {
+            let backend = Rgb565Backend {
+                last_frame: Vec::new(),
+            };
+            let result = DisplayPresenter::new(backend, 2, 2, source_format);
+            assert!(matches!(result, Err(VideoBufferError::InitFailed(_))));
+        }
+    }
+
+    #[test]
+    fn test_scaled_presenter_upscales_to_display_size() {
+        let mut presenter = DisplayPresenter::new_scaled(
+            MockBackend::new(),
+            (2, 1),
+            (4, 2),
+            PixelFormat::Prgb8,
+            ScaleMode::Nearest,
+        )
+        .unwrap();
+        assert_eq!(presenter.effective_output_size(), (4, 2));
+
+        presenter
+            .present_frame_at(&[255, 10, 0, 0, 255, 20, 0, 0], 0.0)
+            .unwrap();
+        let red: Vec<u8> = presenter
+            .backend
+            .last_frame
+            .chunks_exact(4)
+            .map(|pixel| pixel[0])
+            .collect();
+        assert_eq!(red, [10, 10, 20, 20, 10, 10, 20, 20]);
+    }
+
+    #[derive(Default)]
+    struct RegionBackend {
+        last_frame: Vec<u8>,
+        regions: Vec<Rect>,
+        full_presents: usize,
+    }
+
+    impl DisplayBackend for RegionBackend {
+        const FORMAT: PixelFormat = PixelFormat::Rgba8;
+
+        fn init(&mut self, _width: u32, _height: u32) -> Result<(), VideoBufferError> {
+            Ok(())
+        }
+
+        fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+            self.last_frame = frame.to_vec();
+            self.full_presents += 1;
+            Ok(())
+        }
+
+        fn present_region(&mut self, frame: &[u8], rect: Rect) -> Result<(), VideoBufferError> {
+            self.last_frame = frame.to_vec();
+            self.regions.push(rect);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_present_region_converts_only_dirty_pixels() {
+        let mut presenter =
+            DisplayPresenter::new(RegionBackend::default(), 2, 1, PixelFormat::Prgb8).unwrap();
+
+        presenter
+            .present_region(&[255, 1, 1, 1, 255, 2, 2, 2], Rect::full(2, 1), 0.0)
+            .unwrap();
+        // Only the second pixel is dirty, so the first keeps its old color
+        presenter
+            .present_region(&[255, 9, 9, 9, 255, 3, 3, 3], Rect::new(1, 0, 1, 1), 0.0)
+            .unwrap();
+
+        assert_eq!(presenter.backend.last_frame, [1, 1, 1, 255, 3, 3, 3, 255]);
+        assert_eq!(
+            presenter.backend.regions,
+            [Rect::full(2, 1), Rect::new(1, 0, 1, 1)]
+        );
+        assert_eq!(presenter.backend.full_presents, 0);
+    }
+
+    #[test]
+    fn test_present_region_during_fade_presents_full_frame() {
+        let mut presenter =
+            DisplayPresenter::new(RegionBackend::default(), 1, 1, PixelFormat::Rgba8)
+                .unwrap()
+                .with_fade_in(100.0);
+
+        presenter
+            .present_region(&[200, 200, 200, 255], Rect::full(1, 1), 0.0)
+            .unwrap();
+
+        assert!(presenter.backend.regions.is_empty());
+        assert_eq!(presenter.backend.full_presents, 1);
+    }
+
+    #[test]
+    fn test_double_buffered_bridge_and_presenter() {
+        let mut bridge = DisplayBridge::with_swap_buffer(
+            MockBackend::new(),
+            DoubleBuffer::new(1, 1, PixelFormat::Prgb8),
+        )
+        .unwrap();
+        let mut renderer = MockRenderer::new();
+        bridge.render_frame(&mut renderer).unwrap();
+        assert_eq!((bridge.width(), bridge.height()), (1, 1));
+        assert_eq!(bridge.next_frame_no(), 1);
+
+        let buffer = Arc::new(DoubleBuffer::new(1, 1, PixelFormat::Prgb8));
+        buffer.render_buffer().copy_from_slice(&[255, 1, 2, 3]);
+        buffer.commit_render();
+        let mut presenter =
+            DisplayPresenter::new(MockBackend::new(), 1, 1, PixelFormat::Prgb8).unwrap();
+        assert!(presenter.is_converting());
+        assert!(presenter.present_at(&buffer, 0.0).unwrap());
+        assert_eq!(presenter.backend.last_frame, [1, 2, 3, 255]);
+    }
+
+    #[test]
+    fn test_present_with_overlay() {
+        let mut presenter =
+            DisplayPresenter::new(MockBackend::new(), 2, 1, PixelFormat::Rgba8).unwrap();
+        let base = [10, 10, 10, 255, 20, 20, 20, 255];
+
+        let presented = presenter
+            .present_with_overlay(&base, &[200, 0, 0, 255], Rect::new(1, 0, 1, 1), 0.0)
+            .unwrap();
+
+        assert!(presented);
+        assert_eq!(
+            presenter.backend.last_frame,
+            [10, 10, 10, 255, 200, 0, 0, 255]
+        );
+    }
+
+    #[test]
+    fn test_present_with_overlay_rejects_bad_input() {
+        let mut presenter =
+            DisplayPresenter::new(MockBackend::new(), 2, 1, PixelFormat::Rgba8).unwrap();
+        let base = [0u8; 8];
+        let pixel = [200, 0, 0, 255];
+
+        let short_base =
+            presenter.present_with_overlay(&base[..4], &pixel, Rect::new(1, 0, 1, 1), 0.0);
+        assert!(matches!(
+            short_base,
+            Err(VideoBufferError::SizeMismatch {
+                expected: 8,
+                actual: 4
+            })
+        ));
+        let outside = presenter.present_with_overlay(&base, &pixel, Rect::new(2, 0, 1, 1), 0.0);
+        assert!(matches!(
+            outside,
+            Err(VideoBufferError::RegionOutOfBounds { .. })
+        ));
+        let short_overlay =
+            presenter.present_with_overlay(&base, &pixel, Rect::new(0, 0, 2, 1), 0.0);
+        assert!(matches!(
+            short_overlay,
+            Err(VideoBufferError::SizeMismatch { .. })
+        ));
+        assert_eq!(presenter.backend.present_count, 0);
+
+        let mut presenter =
+            DisplayPresenter::new(MockBackend::new(), 2, 1, PixelFormat::Prgb8).unwrap();
+        let result = presenter.present_with_overlay(&base, &pixel, Rect::new(1, 0, 1, 1), 0.0);
+        assert!(matches!(
+            result,
+            Err(VideoBufferError::UnsupportedFormat {
+                format: PixelFormat::Prgb8,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_present_packs_padded_rows() {
+        let buffer = TripleBuffer::new(1, 2, PixelFormat::Prgb8).with_stride(8);
+        buffer
+            .render_buffer()
+            .copy_from_slice(&[255, 1, 2, 3, 9, 9, 9, 9, 255, 4, 5, 6, 9, 9, 9, 9]);
+        buffer.commit_render();
+        let mut presenter =
+            DisplayPresenter::new(MockBackend::new(), 1, 2, PixelFormat::Prgb8).unwrap();
+
+        assert!(presenter.present_at(&buffer, 0.0).unwrap());
+        assert_eq!(presenter.backend.last_frame, [1, 2, 3, 255, 4, 5, 6, 255]);
+    }
+
+    #[test]
+    fn test_bridge_packs_padded_rows() {
+        let buffer = TripleBuffer::new(1, 2, PixelFormat::Prgb8).with_stride(8);
+        let mut bridge = DisplayBridge::with_swap_buffer(MockBackend::new(), buffer).unwrap();
+        let mut renderer = FnRenderer::new(PixelFormat::Prgb8, |buf: &mut [u8], _, _, _| {
+            buf.copy_from_slice(&[255, 1, 2, 3, 9, 9, 9, 9, 255, 4, 5, 6, 9, 9, 9, 9]);
+        });
+
+        bridge.render_frame(&mut renderer).unwrap();
+
+        assert_eq!(bridge.backend.last_frame, [1, 2, 3, 255, 4, 5, 6, 255]);
+    }
+
+    #[test]
+    fn test_bridge_packs_padded_rows_without_conversion() {
+        let buffer = TripleBuffer::new(1, 2, PixelFormat::Rgba8).with_stride(8);
+        let mut bridge = DisplayBridge::with_swap_buffer(MockBackend::new(), buffer).unwrap();
+        let mut renderer = FnRenderer::new(PixelFormat::Rgba8, |buf: &mut [u8], _, _, _| {
+            buf.copy_from_slice(&[1, 2, 3, 255, 9, 9, 9, 9, 4, 5, 6, 255, 9, 9, 9, 9]);
+        });
+
+        bridge.render_frame(&mut renderer).unwrap();
+
+        assert_eq!(bridge.backend.last_frame, [1, 2, 3, 255, 4, 5, 6, 255]);
+    }
+
+    /// One slot shared by both sides, the simplest possible `SwapBuffer`
+    struct SingleBuffer(Mutex<Vec<u8>>);
+
+    impl SwapBuffer for SingleBuffer {
+        fn size(&self) -> (u32, u32) {
+            (1, 1)
+        }
+
+        fn format(&self) -> PixelFormat {
+            PixelFormat::Rgba8
+        }
+
+        fn render_buffer(&self) -> std::sync::MutexGuard<'_, Vec<u8>> {
+            self.0.lock().unwrap()
+        }
+
+        fn commit_render(&self) {}
+
+        fn present_buffer(&self) -> std::sync::MutexGuard<'_, Vec<u8>> {
+            self.0.lock().unwrap()
+        }
+
+        fn commit_present(&self) {}
+    }
+
+    #[test]
+    fn test_custom_swap_buffer() {
+        let buffer = SingleBuffer(Mutex::new(vec![9, 8, 7, 6]));
+        let mut presenter =
+            DisplayPresenter::new(MockBackend::new(), 1, 1, PixelFormat::Rgba8).unwrap();
+        assert!(presenter.present_at(&buffer, 0.0).unwrap());
+        assert_eq!(presenter.backend.last_frame, [9, 8, 7, 6]);
+
+        let mut bridge = DisplayBridge::with_swap_buffer(MockBackend::new(), buffer).unwrap();
+        assert_eq!((bridge.width(), bridge.height()), (1, 1));
+        bridge.render_frame(&mut MockRenderer::new()).unwrap();
+    }
 }
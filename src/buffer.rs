@@ -1,44 +1,149 @@
-use crate::PixelFormat;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Mutex;
+use crate::{PixelBufferMut, PixelFormat, SwapBuffer, VideoBufferError};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::Duration;
 
-pub struct TripleBuffer {
-    buffers: [Mutex<Vec<u8>>; 3],
+/// Set in `ready_idx` while the ready slot holds a frame that hasn't been presented yet.
+const FRESH_FRAME: usize = 0b100;
+const INDEX_MASK: usize = 0b011;
+
+/// Three frame buffers rotated between one render thread and one present thread.
+///
+/// The render thread only touches the render slot and the present thread only the
+/// present slot; `commit_render` and `commit_present` exchange them with the ready
+/// slot through a single atomic swap each, so neither side can steal the other's slot.
+///
+/// Slots hold elements of type `T`, e.g. `u16` for `PixelFormat::Rgba16` frames, and are
+/// sized to cover `format.buffer_size` bytes. Only byte buffers (`TripleBuffer`)
+/// implement `SwapBuffer` and plug into presenters.
+///
+/// # Locking and memory ordering
+///
+/// Each slot has its own mutex, and at any moment the render and present slots are
+/// different buffers, so `render_buffer` and `present_buffer` never wait on each other
+/// and neither commit takes a slot lock. The mutexes are only contended by `resize`,
+/// or by a thread that keeps a guard alive across its own commit.
+///
+/// The swaps on the ready index are `AcqRel`: a frame's bytes, written under the render
+/// slot's lock, happen-before the `commit_present` that picks it up, and everything the
+/// presenter did with a slot happens-before the renderer reusing it.
+pub struct TypedTripleBuffer<T> {
+    buffers: [Mutex<Vec<T>>; 3],
     render_idx: AtomicUsize,
     ready_idx: AtomicUsize,
     present_idx: AtomicUsize,
-    width: u32,
-    height: u32,
+    generation: AtomicU64,
+    dropped_frames: AtomicU64,
+    frame_signal: Mutex<()>,
+    frame_ready: Condvar,
+    /// Width in the high and height in the low 32 bits, so both change together
+    dimensions: AtomicU64,
     format: PixelFormat,
+    /// Bytes from one row to the next, if rows are padded
+    stride: Option<usize>,
 }
 
-impl TripleBuffer {
+/// Triple buffer of byte frames, the kind presenters and render threads work with
+pub type TripleBuffer = TypedTripleBuffer<u8>;
+
+/// Number of `T` elements needed to hold `bytes` bytes
+const fn element_count<T>(bytes: usize) -> usize {
+    bytes.div_ceil(std::mem::size_of::<T>())
+}
+
+impl<T: Copy + Default> TypedTripleBuffer<T> {
     pub fn new(width: u32, height: u32, format: PixelFormat) -> Self {
         assert!(width > 0, "width must be greater than 0");
         assert!(height > 0, "height must be greater than 0");
 
-        let size = format.buffer_size(width, height);
+        let size = element_count::<T>(format.buffer_size(width, height));
         Self {
             buffers: [
-                Mutex::new(vec![0u8; size]),
-                Mutex::new(vec![0u8; size]),
-                Mutex::new(vec![0u8; size]),
+                Mutex::new(vec![T::default(); size]),
+                Mutex::new(vec![T::default(); size]),
+                Mutex::new(vec![T::default(); size]),
             ],
             render_idx: AtomicUsize::new(0),
             ready_idx: AtomicUsize::new(1),
             present_idx: AtomicUsize::new(2),
-            width,
-            height,
+            generation: AtomicU64::new(0),
+            dropped_frames: AtomicU64::new(0),
+            frame_signal: Mutex::new(()),
+            frame_ready: Condvar::new(),
+            dimensions: AtomicU64::new(pack_dimensions(width, height)),
             format,
+            stride: None,
+        }
+    }
+
+    /// Space rows `stride` bytes apart instead of packing them, e.g. to match the row
+    /// alignment a GPU texture upload demands
+    ///
+    /// Renderers write packed rows, so fill padded buffers directly rather than from a
+    /// `RenderThread` or `DisplayBridge`. Presenters repack them before presenting.
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        assert!(!self.format.is_planar(), "planar formats can't be padded");
+        let (width, height) = self.size();
+        assert!(
+            stride >= self.format.stride(width),
+            "stride is smaller than a row"
+        );
+
+        self.stride = Some(stride);
+        let size = element_count::<T>(stride * height as usize);
+        for buffer in self.buffers.iter_mut() {
+            buffer.get_mut().unwrap().resize(size, T::default());
         }
+        self
+    }
+
+    /// Bytes from the start of one row to the next
+    pub fn stride(&self) -> usize {
+        self.stride
+            .unwrap_or_else(|| self.format.stride(self.width()))
     }
 
     pub fn width(&self) -> u32 {
-        self.width
+        self.size().0
     }
 
     pub fn height(&self) -> u32 {
-        self.height
+        self.size().1
+    }
+
+    /// Current (width, height), read together so a concurrent `resize` can't tear them
+    pub fn size(&self) -> (u32, u32) {
+        let dimensions = self.dimensions.load(Ordering::Acquire);
+        ((dimensions >> 32) as u32, dimensions as u32)
+    }
+
+    /// Reallocate all three buffers for a new frame size
+    ///
+    /// Every slot is locked while it is resized, so frame contents are discarded and a
+    /// render or present running concurrently sees the new size from its next
+    /// `render_buffer` or `present_buffer` call on. Padded buffers keep their stride, so
+    /// rows wider than it are rejected.
+    pub fn resize(&self, width: u32, height: u32) -> Result<(), VideoBufferError> {
+        let too_wide = self
+            .stride
+            .is_some_and(|stride| self.format.stride(width) > stride);
+        if width == 0 || height == 0 || too_wide {
+            return Err(VideoBufferError::InvalidDimensions { width, height });
+        }
+
+        let size = element_count::<T>(match self.stride {
+            Some(stride) => stride * height as usize,
+            None => self.format.buffer_size(width, height),
+        });
+        // Lock in slot order so concurrent resizes can't deadlock
+        let mut guards: Vec<_> = self.buffers.iter().map(|b| b.lock().unwrap()).collect();
+        for buffer in guards.iter_mut() {
+            buffer.clear();
+            buffer.resize(size, T::default());
+        }
+        self.dimensions
+            .store(pack_dimensions(width, height), Ordering::Release);
+        Ok(())
     }
 
     pub fn format(&self) -> PixelFormat {
@@ -46,37 +151,141 @@ impl TripleBuffer {
     }
 
     /// Get the buffer for rendering
-    pub fn render_buffer(&self) -> std::sync::MutexGuard<'_, Vec<u8>> {
+    pub fn render_buffer(&self) -> MutexGuard<'_, Vec<T>> {
         let idx = self.render_idx.load(Ordering::Acquire);
         self.buffers[idx].lock().unwrap()
     }
 
     /// Commit the rendered buffer
+    ///
+    /// Replaces any ready frame that hasn't been presented yet.
     pub fn commit_render(&self) {
         let render = self.render_idx.load(Ordering::Acquire);
-        let ready = self.ready_idx.load(Ordering::Acquire);
-        self.render_idx.store(ready, Ordering::Release);
-        self.ready_idx.store(render, Ordering::Release);
+        let ready = self.ready_idx.swap(render | FRESH_FRAME, Ordering::AcqRel);
+        self.render_idx.store(ready & INDEX_MASK, Ordering::Release);
+
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        if ready & FRESH_FRAME != 0 {
+            // The replaced frame was never picked up by `commit_present`
+            self.dropped_frames.fetch_add(1, Ordering::Relaxed);
+        }
+
+        // Taking the lock orders the notify after any waiter's check of `has_new_frame`
+        let _signal = self.frame_signal.lock().unwrap();
+        self.frame_ready.notify_all();
+    }
+
+    /// Whether a frame has been rendered since the last `commit_present`
+    pub fn has_new_frame(&self) -> bool {
+        self.ready_idx.load(Ordering::Acquire) & FRESH_FRAME != 0
+    }
+
+    /// Number of frames committed with `commit_render` so far
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Number of committed frames replaced before they could be presented
+    ///
+    /// Grows when the render thread outpaces the present thread.
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped_frames.load(Ordering::Relaxed)
+    }
+
+    /// Current (render, ready, present) slot indices
+    pub(crate) fn slot_indices(&self) -> (usize, usize, usize) {
+        (
+            self.render_idx.load(Ordering::Acquire),
+            self.ready_idx.load(Ordering::Acquire) & INDEX_MASK,
+            self.present_idx.load(Ordering::Acquire),
+        )
+    }
+
+    /// Block until a new frame is committed or `timeout` elapses
+    ///
+    /// Returns `true` if a new frame is ready to present. Returns immediately if one
+    /// already is.
+    pub fn wait_for_frame(&self, timeout: Duration) -> bool {
+        let signal = self.frame_signal.lock().unwrap();
+        let _signal = self
+            .frame_ready
+            .wait_timeout_while(signal, timeout, |_| !self.has_new_frame())
+            .unwrap();
+        self.has_new_frame()
     }
 
     /// Get the buffer for presentation
-    pub fn present_buffer(&self) -> std::sync::MutexGuard<'_, Vec<u8>> {
+    pub fn present_buffer(&self) -> MutexGuard<'_, Vec<T>> {
         let idx = self.present_idx.load(Ordering::Acquire);
         self.buffers[idx].lock().unwrap()
     }
 
     /// Commit the presentation completed
+    ///
+    /// Moves the most recently rendered frame into the present slot. If nothing was
+    /// rendered since the last call, the present slot keeps its current frame.
     pub fn commit_present(&self) {
-        let ready = self.ready_idx.load(Ordering::Acquire);
+        // Only the render thread can set FRESH_FRAME, so it can't be cleared under us
+        if !self.has_new_frame() {
+            return;
+        }
+
         let present = self.present_idx.load(Ordering::Acquire);
-        self.ready_idx.store(present, Ordering::Release);
-        self.present_idx.store(ready, Ordering::Release);
+        let ready = self.ready_idx.swap(present, Ordering::AcqRel);
+        self.present_idx
+            .store(ready & INDEX_MASK, Ordering::Release);
     }
 }
 
+impl TripleBuffer {
+    /// Lock the render slot as a pixel-addressed view
+    pub fn render_view(&self) -> PixelBufferMut<MutexGuard<'_, Vec<u8>>> {
+        let (width, height) = self.size();
+        let stride = self.stride.unwrap_or(self.format.stride(width));
+        PixelBufferMut::with_stride(self.render_buffer(), width, height, stride, self.format)
+    }
+}
+
+impl SwapBuffer for TripleBuffer {
+    fn size(&self) -> (u32, u32) {
+        TripleBuffer::size(self)
+    }
+
+    fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    fn stride(&self) -> usize {
+        TripleBuffer::stride(self)
+    }
+
+    fn render_buffer(&self) -> MutexGuard<'_, Vec<u8>> {
+        TripleBuffer::render_buffer(self)
+    }
+
+    fn commit_render(&self) {
+        TripleBuffer::commit_render(self)
+    }
+
+    fn present_buffer(&self) -> MutexGuard<'_, Vec<u8>> {
+        TripleBuffer::present_buffer(self)
+    }
+
+    fn commit_present(&self) {
+        TripleBuffer::commit_present(self)
+    }
+}
+
+const fn pack_dimensions(width: u32, height: u32) -> u64 {
+    ((width as u64) << 32) | height as u64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Instant;
 
     #[test]
     fn test_triple_buffer_creation() {
@@ -138,6 +347,41 @@ mod tests {
         assert_eq!(present[0], 3);
     }
 
+    #[test]
+    fn test_present_without_new_render_keeps_frame() {
+        let tb = TripleBuffer::new(10, 10, PixelFormat::Rgba8);
+
+        {
+            let mut render = tb.render_buffer();
+            render[0] = 1;
+        }
+        tb.commit_render();
+        tb.commit_present();
+
+        // No new frame rendered: presenting again must not go back to an older buffer
+        tb.commit_present();
+        assert_eq!(tb.present_buffer()[0], 1);
+    }
+
+    #[test]
+    fn test_render_slot_never_aliases_present_slot() {
+        let tb = TripleBuffer::new(10, 10, PixelFormat::Rgba8);
+
+        for _ in 0..5 {
+            tb.commit_render();
+            tb.commit_render();
+            tb.commit_present();
+            tb.commit_present();
+
+            let render = tb.render_idx.load(Ordering::Acquire);
+            let ready = tb.ready_idx.load(Ordering::Acquire) & INDEX_MASK;
+            let present = tb.present_idx.load(Ordering::Acquire);
+            assert_ne!(render, ready);
+            assert_ne!(render, present);
+            assert_ne!(ready, present);
+        }
+    }
+
     #[test]
     fn test_prgb8_format() {
         let tb = TripleBuffer::new(100, 100, PixelFormat::Prgb8);
@@ -166,4 +410,151 @@ mod tests {
         let render = tb.render_buffer();
         assert_eq!(render.len(), 1920 * 1080 * 4);
     }
+
+    #[test]
+    fn test_wait_for_frame_wakes_on_commit() {
+        let tb = Arc::new(TripleBuffer::new(4, 4, PixelFormat::Rgba8));
+        let producer_tb = Arc::clone(&tb);
+
+        let start = Instant::now();
+        let producer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            producer_tb.commit_render();
+        });
+
+        assert!(tb.wait_for_frame(Duration::from_secs(5)));
+        let waited = start.elapsed();
+        producer.join().unwrap();
+
+        assert!(waited >= Duration::from_millis(20));
+        assert!(waited < Duration::from_secs(5), "wait ran to its timeout");
+    }
+
+    #[test]
+    fn test_wait_for_frame_times_out() {
+        let tb = TripleBuffer::new(4, 4, PixelFormat::Rgba8);
+        assert!(!tb.wait_for_frame(Duration::from_millis(5)));
+
+        tb.commit_render();
+        assert!(tb.wait_for_frame(Duration::from_millis(5)));
+
+        tb.commit_present();
+        assert!(!tb.has_new_frame());
+    }
+
+    #[test]
+    fn test_dropped_frames_counts_overwritten_renders() {
+        let tb = TripleBuffer::new(4, 4, PixelFormat::Rgba8);
+
+        tb.commit_render();
+        tb.commit_render();
+        tb.commit_render();
+        tb.commit_present();
+        assert_eq!(tb.generation(), 3);
+        assert_eq!(tb.dropped_frames(), 2);
+
+        // Presented in step: nothing more is dropped
+        tb.commit_render();
+        tb.commit_present();
+        assert_eq!(tb.generation(), 4);
+        assert_eq!(tb.dropped_frames(), 2);
+    }
+
+    #[test]
+    fn test_resize_reallocates_every_slot() {
+        let tb = TripleBuffer::new(4, 4, PixelFormat::Rgba8);
+        tb.resize(8, 2).unwrap();
+
+        assert_eq!(tb.size(), (8, 2));
+        for _ in 0..3 {
+            assert_eq!(tb.render_buffer().len(), 64);
+            assert_eq!(tb.present_buffer().len(), 64);
+            tb.commit_render();
+            tb.commit_present();
+        }
+    }
+
+    #[test]
+    fn test_resize_rejects_zero_dimensions() {
+        let tb = TripleBuffer::new(4, 4, PixelFormat::Rgba8);
+        let result = tb.resize(0, 4);
+
+        assert!(matches!(
+            result,
+            Err(VideoBufferError::InvalidDimensions {
+                width: 0,
+                height: 4
+            })
+        ));
+        assert_eq!(tb.size(), (4, 4));
+    }
+
+    #[test]
+    fn test_padded_stride() {
+        let tb = TripleBuffer::new(3, 2, PixelFormat::Rgba8).with_stride(16);
+        assert_eq!(tb.stride(), 16);
+        assert_eq!(tb.render_buffer().len(), 32);
+
+        tb.resize(4, 3).unwrap();
+        assert_eq!(tb.present_buffer().len(), 48);
+        assert!(matches!(
+            tb.resize(5, 3),
+            Err(VideoBufferError::InvalidDimensions {
+                width: 5,
+                height: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_render_view_writes_render_slot() {
+        let tb = TripleBuffer::new(2, 2, PixelFormat::Rgba8).with_stride(12);
+        tb.render_view().set_rgba(1, 1, 1, 2, 3, 4);
+        tb.commit_render();
+        tb.commit_present();
+
+        assert_eq!(&tb.present_buffer()[16..20], &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_typed_slots_cover_the_frame() {
+        let tb = TypedTripleBuffer::<u16>::new(3, 2, PixelFormat::Rgba16);
+        tb.render_buffer()[23] = 0xffff;
+        tb.commit_render();
+        tb.commit_present();
+
+        assert_eq!(tb.present_buffer().len(), 3 * 2 * 4);
+        assert_eq!(tb.present_buffer()[23], 0xffff);
+
+        tb.resize(1, 1).unwrap();
+        assert_eq!(tb.render_buffer().len(), 4);
+    }
+
+    #[test]
+    fn test_render_never_waits_on_held_present_slot() {
+        let tb = Arc::new(TripleBuffer::new(4, 4, PixelFormat::Rgba8));
+        let render_tb = Arc::clone(&tb);
+
+        let present_guard = tb.present_buffer();
+        let renderer = thread::spawn(move || {
+            for frame in 0..100u8 {
+                render_tb.render_buffer().fill(frame);
+                render_tb.commit_render();
+            }
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !renderer.is_finished() {
+            assert!(
+                Instant::now() < deadline,
+                "renderer blocked on the present slot"
+            );
+            thread::sleep(Duration::from_millis(1));
+        }
+        drop(present_guard);
+        renderer.join().unwrap();
+
+        tb.commit_present();
+        assert_eq!(tb.present_buffer()[0], 99);
+    }
 }
@@ -0,0 +1,78 @@
+/// Source of the current time for a `DisplayPresenter`
+///
+/// Times are milliseconds on any monotonic timeline; only differences between them matter.
+pub trait Clock {
+    fn now_ms(&self) -> f64;
+}
+
+/// Milliseconds since the clock was created, from `std::time::Instant`
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug)]
+pub struct InstantClock {
+    start: std::time::Instant,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl InstantClock {
+    pub fn new() -> Self {
+        Self {
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for InstantClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for InstantClock {
+    fn now_ms(&self) -> f64 {
+        self.start.elapsed().as_secs_f64() * 1000.0
+    }
+}
+
+/// Milliseconds since the Unix epoch from JavaScript's `Date.now()`
+///
+/// Works on the main thread and in workers alike.
+#[cfg(feature = "wasm-canvas-backend")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DateClock;
+
+#[cfg(feature = "wasm-canvas-backend")]
+impl Clock for DateClock {
+    fn now_ms(&self) -> f64 {
+        js_sys::Date::now()
+    }
+}
+
+/// The clock a new presenter starts with, if this target has one
+pub(crate) fn default_clock() -> Option<Box<dyn Clock>> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return Some(Box::new(InstantClock::new()));
+
+    #[cfg(all(target_arch = "wasm32", feature = "wasm-canvas-backend"))]
+    return Some(Box::new(DateClock));
+
+    #[cfg(all(target_arch = "wasm32", not(feature = "wasm-canvas-backend")))]
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instant_clock_counts_up_from_zero() {
+        let clock = InstantClock::new();
+        let first = clock.now_ms();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let second = clock.now_ms();
+
+        assert!((0.0..5.0).contains(&first));
+        assert!(second >= first + 5.0);
+    }
+}
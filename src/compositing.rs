@@ -0,0 +1,164 @@
+use crate::{PixelFormat, Rect};
+
+/// Blend a straight-alpha RGBA8 `overlay` over the `rect` region of `frame` (source-over)
+///
+/// `frame` is a tightly packed RGBA8 frame `width` pixels wide and `overlay` is a tightly
+/// packed `rect.width` x `rect.height` one. Pixels outside `rect` are left untouched.
+pub(crate) fn composite_over(frame: &mut [u8], width: u32, overlay: &[u8], rect: Rect) {
+    let format = PixelFormat::Rgba8;
+    let height = (frame.len() / format.stride(width).max(1)) as u32;
+    assert!(
+        rect.fits_within(width, height),
+        "overlay {:?} exceeds the {}x{} frame",
+        rect,
+        width,
+        height
+    );
+    assert_eq!(
+        overlay.len(),
+        format.buffer_size(rect.width, rect.height),
+        "overlay has the wrong size"
+    );
+
+    let frame_stride = format.stride(width);
+    let overlay_stride = format.stride(rect.width);
+    for y in 0..rect.height {
+        let src = &overlay[format.row_range(y, rect.width, overlay_stride)];
+        let dst_start = format.pixel_offset(rect.x, rect.y + y, frame_stride);
+        let dst = &mut frame[dst_start..dst_start + overlay_stride];
+
+        for (dst, src) in dst.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+            blend_pixel(dst, src);
+        }
+    }
+}
+
+/// Alpha-blend a straight-alpha RGBA8 `src` image onto `dst` with its top-left corner
+/// at (`dest_x`, `dest_y`)
+///
+/// Both images are tightly packed RGBA8. The position may be negative or past the edges
+/// of `dst`; parts of `src` that fall outside it are clipped.
+pub fn blit_rgba(
+    dst: &mut [u8],
+    (dst_width, dst_height): (u32, u32),
+    src: &[u8],
+    (src_width, src_height): (u32, u32),
+    (dest_x, dest_y): (i32, i32),
+) {
+    let format = PixelFormat::Rgba8;
+    assert_eq!(
+        dst.len(),
+        format.buffer_size(dst_width, dst_height),
+        "destination has the wrong size"
+    );
+    assert_eq!(
+        src.len(),
+        format.buffer_size(src_width, src_height),
+        "source has the wrong size"
+    );
+
+    // Visible span of `src` along one axis, as (first src index, first dst index, length)
+    let clip = |pos: i32, src_len: u32, dst_len: u32| {
+        let start = (-(pos as i64)).clamp(0, src_len as i64);
+        let end = (dst_len as i64 - pos as i64).clamp(start, src_len as i64);
+        (
+            start as u32,
+            (pos as i64 + start) as u32,
+            (end - start) as u32,
+        )
+    };
+    let (src_x, x, width) = clip(dest_x, src_width, dst_width);
+    let (src_y, y, height) = clip(dest_y, src_height, dst_height);
+    if width == 0 || height == 0 {
+        return;
+    }
+
+    let (src_stride, dst_stride) = (format.stride(src_width), format.stride(dst_width));
+    for row in 0..height {
+        let src_start = format.pixel_offset(src_x, src_y + row, src_stride);
+        let dst_start = format.pixel_offset(x, y + row, dst_stride);
+        let len = format.stride(width);
+
+        let src_row = &src[src_start..src_start + len];
+        let dst_row = &mut dst[dst_start..dst_start + len];
+        for (dst, src) in dst_row.chunks_exact_mut(4).zip(src_row.chunks_exact(4)) {
+            blend_pixel(dst, src);
+        }
+    }
+}
+
+/// Source-over for one straight-alpha pixel, in 8-bit fixed point
+#[inline]
+fn blend_pixel(dst: &mut [u8], src: &[u8]) {
+    let src_alpha = src[3] as u32;
+    match src_alpha {
+        0 => return,
+        255 => return dst.copy_from_slice(src),
+        _ => {}
+    }
+
+    // Weight of the destination after the source covers it, scaled by 255 * 255
+    let dst_weight = dst[3] as u32 * (255 - src_alpha);
+    let out_alpha = src_alpha * 255 + dst_weight;
+    for channel in 0..3 {
+        let mixed = src[channel] as u32 * src_alpha * 255 + dst[channel] as u32 * dst_weight;
+        dst[channel] = ((mixed + out_alpha / 2) / out_alpha) as u8;
+    }
+    dst[3] = ((out_alpha + 127) / 255) as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_half_transparent_over_opaque() {
+        let mut frame = [0, 0, 200, 255];
+        composite_over(&mut frame, 1, &[255, 0, 0, 128], Rect::new(0, 0, 1, 1));
+        assert_eq!(frame, [128, 0, 100, 255]);
+    }
+
+    #[test]
+    fn test_over_transparent_keeps_overlay_color() {
+        let mut frame = [9, 9, 9, 0];
+        composite_over(&mut frame, 1, &[10, 20, 30, 64], Rect::new(0, 0, 1, 1));
+        assert_eq!(frame, [10, 20, 30, 64]);
+    }
+
+    #[test]
+    fn test_smaller_overlay_only_touches_its_rect() {
+        let mut frame = [0u8; 2 * 2 * 4];
+        composite_over(&mut frame, 2, &[1, 2, 3, 255], Rect::new(1, 1, 1, 1));
+        assert_eq!(&frame[..12], &[0; 12]);
+        assert_eq!(&frame[12..], &[1, 2, 3, 255]);
+    }
+
+    /// 2x2 opaque sprite numbered 1..=4 blitted onto a 3x3 transparent frame
+    fn blit_at(x: i32, y: i32) -> Vec<u8> {
+        let sprite: Vec<u8> = (1..=4).flat_map(|i| [i, i, i, 255]).collect();
+        let mut frame = vec![0u8; 3 * 3 * 4];
+        blit_rgba(&mut frame, (3, 3), &sprite, (2, 2), (x, y));
+        frame.chunks_exact(4).map(|pixel| pixel[0]).collect()
+    }
+
+    #[test]
+    fn test_blit_clips_each_edge() {
+        assert_eq!(blit_at(-1, -1), [4, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(blit_at(2, 2), [0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(blit_at(1, -1), [0, 3, 4, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(blit_at(0, 1), [0, 0, 0, 1, 2, 0, 3, 4, 0]);
+    }
+
+    #[test]
+    fn test_blit_fully_off_screen() {
+        assert_eq!(blit_at(3, 0), [0; 9]);
+        assert_eq!(blit_at(-2, 0), [0; 9]);
+        assert_eq!(blit_at(i32::MIN, i32::MAX), [0; 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds")]
+    fn test_overlay_out_of_bounds() {
+        composite_over(&mut [0; 4], 1, &[0; 8], Rect::new(0, 0, 2, 1));
+    }
+}
@@ -1,21 +1,362 @@
-use crate::PixelFormat;
+use crate::simd::shuffle_pixels;
+use crate::yuv::{yuv420_to_rgba8, ChromaUpsampling};
+use crate::{PixelFormat, Rect};
 
 #[inline]
 pub fn needs_conversion(src_format: PixelFormat, dst_format: PixelFormat) -> bool {
     src_format != dst_format
 }
 
+/// Whether `convert_frame` can convert `src_format` frames to `dst_format`
+///
+/// `convert` handles the same pairs apart from the planar ones.
+pub fn supports(src_format: PixelFormat, dst_format: PixelFormat) -> bool {
+    use PixelFormat::*;
+
+    match (src_format, dst_format) {
+        _ if src_format == dst_format => true,
+        _ if src_format.channel_order().is_some() && dst_format.channel_order().is_some() => true,
+        (Rgb565 | Gray8 | A2Rgb10 | Rgba16 | I420, Rgba8)
+        | (Rgba8, Rgb565 | Gray8 | A2Rgb10 | Rgba16) => true,
+        _ => false,
+    }
+}
+
+/// Converts `src` into `dst`, copying unchanged when the formats are the same.
 #[inline]
 pub fn convert(src: &[u8], dst: &mut [u8], src_format: PixelFormat, dst_format: PixelFormat) {
     match (src_format, dst_format) {
-        (PixelFormat::Prgb8, PixelFormat::Rgba8) => convert_prgb_to_rgba(src, dst),
-        (PixelFormat::Rgba8, PixelFormat::Prgb8) => convert_rgba_to_prgb(src, dst),
-        _ => unreachable!("convert should only be called when formats differ"),
+        _ if src_format == dst_format => {
+            assert_eq!(
+                src.len(),
+                dst.len(),
+                "source and destination buffers must have the same length"
+            );
+            dst.copy_from_slice(src);
+        }
+        _ if src_format.channel_order().is_some() && dst_format.channel_order().is_some() => {
+            convert_channels(src, dst, src_format, dst_format)
+        }
+        (PixelFormat::Rgb565, PixelFormat::Rgba8) => convert_rgb565_to_rgba(src, dst),
+        (PixelFormat::Rgba8, PixelFormat::Rgb565) => convert_rgba_to_rgb565(src, dst),
+        (PixelFormat::Gray8, PixelFormat::Rgba8) => convert_gray8_to_rgba8(src, dst),
+        (PixelFormat::Rgba8, PixelFormat::Gray8) => convert_rgba8_to_gray8(src, dst),
+        (PixelFormat::A2Rgb10, PixelFormat::Rgba8) => convert_a2rgb10_to_rgba8(src, dst),
+        (PixelFormat::Rgba8, PixelFormat::A2Rgb10) => convert_rgba8_to_a2rgb10(src, dst),
+        (PixelFormat::Rgba16, PixelFormat::Rgba8) => {
+            convert_rgba16_to_rgba8(src, dst, ToneMapping::Truncate)
+        }
+        (PixelFormat::Rgba8, PixelFormat::Rgba16) => convert_rgba8_to_rgba16(src, dst),
+        _ if src_format.is_planar() || dst_format.is_planar() => panic!(
+            "conversion from {:?} to {:?} needs the frame size, use convert_frame",
+            src_format, dst_format
+        ),
+        _ => panic!(
+            "conversion from {:?} to {:?} is not supported",
+            src_format, dst_format
+        ),
+    }
+}
+
+/// Like `convert`, but also handles planar formats, whose layout depends on the frame size.
+#[inline]
+pub fn convert_frame(
+    src: &[u8],
+    dst: &mut [u8],
+    src_format: PixelFormat,
+    dst_format: PixelFormat,
+    width: u32,
+    height: u32,
+) {
+    match (src_format, dst_format) {
+        (PixelFormat::I420, PixelFormat::Rgba8) => convert_i420_to_rgba8(src, dst, width, height),
+        _ => convert(src, dst, src_format, dst_format),
+    }
+}
+
+/// Moves every channel of a one-byte-per-channel format to its place in another
+///
+/// Alpha is dropped if `dst_format` has none and set to 255 if `src_format` has none.
+/// Between two 4-byte formats this is a `Swizzle`, which takes the vectorized path.
+fn convert_channels(src: &[u8], dst: &mut [u8], src_format: PixelFormat, dst_format: PixelFormat) {
+    let (Some(src_order), Some(dst_order)) =
+        (src_format.channel_order(), dst_format.channel_order())
+    else {
+        unreachable!("both formats have one byte per channel");
+    };
+    let (src_bpp, dst_bpp) = (src_format.bytes_per_pixel(), dst_format.bytes_per_pixel());
+
+    if let (Some(src_alpha), Some(dst_alpha)) = (src_order.alpha, dst_order.alpha) {
+        let mut pattern = [0u8; 4];
+        pattern[dst_alpha] = src_alpha as u8;
+        for (&dst_offset, &src_offset) in dst_order.rgb.iter().zip(&src_order.rgb) {
+            pattern[dst_offset] = src_offset as u8;
+        }
+        return convert_swizzle(src, dst, Swizzle(pattern));
+    }
+
+    assert_eq!(
+        src.len() % src_bpp,
+        0,
+        "buffer length must be a multiple of {}",
+        src_bpp
+    );
+    assert_eq!(
+        dst.len() % dst_bpp,
+        0,
+        "buffer length must be a multiple of {}",
+        dst_bpp
+    );
+    assert_eq!(
+        src.len() / src_bpp,
+        dst.len() / dst_bpp,
+        "source and destination buffers must hold the same number of pixels"
+    );
+
+    for (src_pixel, dst_pixel) in src.chunks_exact(src_bpp).zip(dst.chunks_exact_mut(dst_bpp)) {
+        for (&dst_offset, &src_offset) in dst_order.rgb.iter().zip(&src_order.rgb) {
+            dst_pixel[dst_offset] = src_pixel[src_offset];
+        }
+        if let Some(dst_alpha) = dst_order.alpha {
+            dst_pixel[dst_alpha] = src_order.alpha.map_or(255, |alpha| src_pixel[alpha]);
+        }
+    }
+}
+
+/// Converts only the pixels inside `rect`, leaving the rest of `dst` untouched
+///
+/// `src` and `dst` are full frames `width` pixels wide. Keep `dst` alive between frames so
+/// the unconverted area still holds the previously converted pixels.
+pub fn convert_region(
+    src: &[u8],
+    dst: &mut [u8],
+    rect: Rect,
+    width: u32,
+    src_format: PixelFormat,
+    dst_format: PixelFormat,
+) {
+    let src_stride = src_format.stride(width);
+    let dst_stride = dst_format.stride(width);
+    let height = src.len().checked_div(src_stride).unwrap_or(0) as u32;
+    assert!(
+        rect.fits_within(width, height),
+        "region {:?} exceeds the {}x{} frame",
+        rect,
+        width,
+        height
+    );
+    assert!(
+        dst.len() >= dst_format.buffer_size(width, height),
+        "destination buffer is too small"
+    );
+
+    for y in rect.y..rect.y + rect.height {
+        let src_start = src_format.pixel_offset(rect.x, y, src_stride);
+        let dst_start = dst_format.pixel_offset(rect.x, y, dst_stride);
+        convert(
+            &src[src_start..src_start + src_format.stride(rect.width)],
+            &mut dst[dst_start..dst_start + dst_format.stride(rect.width)],
+            src_format,
+            dst_format,
+        );
+    }
+}
+
+/// Converts a frame `width` pixels wide whose rows are `src_stride` bytes apart into one
+/// whose rows are `dst_stride` bytes apart
+///
+/// The number of rows is taken from `src`, whose last row may omit its padding. Padding
+/// bytes in `dst` are left untouched.
+pub fn convert_strided(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    width: u32,
+    src_format: PixelFormat,
+    dst_format: PixelFormat,
+) {
+    assert!(
+        !src_format.is_planar() && !dst_format.is_planar(),
+        "planar formats can't be strided"
+    );
+    let (src_row, dst_row) = (src_format.stride(width), dst_format.stride(width));
+    assert!(
+        src_stride >= src_row && dst_stride >= dst_row,
+        "stride is smaller than a row"
+    );
+
+    if src_stride == src_row && dst_stride == dst_row {
+        let rows = src.len().checked_div(src_row).unwrap_or(0);
+        return convert(src, &mut dst[..rows * dst_row], src_format, dst_format);
+    }
+
+    let height = (src.len() + src_stride - src_row) / src_stride;
+    assert!(
+        dst.len() + dst_stride - dst_row >= height * dst_stride,
+        "destination buffer is too small"
+    );
+    for y in 0..height as u32 {
+        convert(
+            &src[src_format.row_range(y, width, src_stride)],
+            &mut dst[dst_format.row_range(y, width, dst_stride)],
+            src_format,
+            dst_format,
+        );
+    }
+}
+
+/// Channel remapping for 4-byte pixels
+///
+/// Entry `i` is the source channel index written to destination channel `i`, so
+/// `Swizzle([1, 2, 3, 0])` turns PRGB (A, R, G, B) into RGBA.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Swizzle(pub [u8; 4]);
+
+impl Swizzle {
+    pub const IDENTITY: Swizzle = Swizzle([0, 1, 2, 3]);
+    pub const PRGB_TO_RGBA: Swizzle = Swizzle([1, 2, 3, 0]);
+    pub const RGBA_TO_PRGB: Swizzle = Swizzle([3, 0, 1, 2]);
+    /// RGBA to ABGR and back, also PRGB (A, R, G, B) to BGRA and back
+    pub const REVERSE: Swizzle = Swizzle([3, 2, 1, 0]);
+    /// RGBA to BGRA and back
+    pub const SWAP_RED_BLUE: Swizzle = Swizzle([2, 1, 0, 3]);
+}
+
+/// Reorders the channels of every 4-byte pixel in `src` into `dst`
+#[inline]
+pub fn convert_swizzle(src: &[u8], dst: &mut [u8], swizzle: Swizzle) {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination buffers must have the same length"
+    );
+    assert_eq!(src.len() % 4, 0, "buffer length must be a multiple of 4");
+    assert!(
+        swizzle.0.iter().all(|&channel| channel < 4),
+        "swizzle channel indices must be less than 4"
+    );
+
+    let done = shuffle_pixels(src, dst, swizzle.0);
+    let [c0, c1, c2, c3] = swizzle.0.map(usize::from);
+    for (src_pixel, dst_pixel) in src[done..]
+        .chunks_exact(4)
+        .zip(dst[done..].chunks_exact_mut(4))
+    {
+        dst_pixel[0] = src_pixel[c0];
+        dst_pixel[1] = src_pixel[c1];
+        dst_pixel[2] = src_pixel[c2];
+        dst_pixel[3] = src_pixel[c3];
     }
 }
 
+/// Reorders PRGB8 (A, R, G, B) to RGBA8, same as `convert` between the two.
 #[inline]
 pub fn convert_prgb_to_rgba(src: &[u8], dst: &mut [u8]) {
+    convert(src, dst, PixelFormat::Prgb8, PixelFormat::Rgba8);
+}
+
+/// Reorders RGBA8 to PRGB8 (A, R, G, B), same as `convert` between the two.
+#[inline]
+pub fn convert_rgba_to_prgb(src: &[u8], dst: &mut [u8]) {
+    convert(src, dst, PixelFormat::Rgba8, PixelFormat::Prgb8);
+}
+
+/// Expands RGB8 to RGBA8 with opaque alpha.
+#[inline]
+pub fn convert_rgb8_to_rgba8(src: &[u8], dst: &mut [u8]) {
+    convert(src, dst, PixelFormat::Rgb8, PixelFormat::Rgba8);
+}
+
+/// Reduces RGBA8 to RGB8 by dropping alpha.
+#[inline]
+pub fn convert_rgba8_to_rgb8(src: &[u8], dst: &mut [u8]) {
+    convert(src, dst, PixelFormat::Rgba8, PixelFormat::Rgb8);
+}
+
+/// Expands RGB565 to RGBA8 by bit replication, so full-scale channels map to 255.
+///
+/// Alpha is set to 255 since RGB565 has no alpha channel.
+#[inline]
+pub fn convert_rgb565_to_rgba(src: &[u8], dst: &mut [u8]) {
+    assert_eq!(src.len() % 2, 0, "buffer length must be a multiple of 2");
+    assert_eq!(dst.len() % 4, 0, "buffer length must be a multiple of 4");
+    assert_eq!(
+        src.len() / 2,
+        dst.len() / 4,
+        "source and destination buffers must hold the same number of pixels"
+    );
+
+    for (src_pixel, dst_pixel) in src.chunks_exact(2).zip(dst.chunks_exact_mut(4)) {
+        let packed = u16::from_le_bytes([src_pixel[0], src_pixel[1]]);
+        let r = ((packed >> 11) & 0x1f) as u8;
+        let g = ((packed >> 5) & 0x3f) as u8;
+        let b = (packed & 0x1f) as u8;
+
+        dst_pixel[0] = (r << 3) | (r >> 2); // R
+        dst_pixel[1] = (g << 2) | (g >> 4); // G
+        dst_pixel[2] = (b << 3) | (b >> 2); // B
+        dst_pixel[3] = 255; // A
+    }
+}
+
+/// Reduces RGBA8 to RGB565, rounding each channel to the nearest representable value.
+///
+/// Alpha is discarded.
+#[inline]
+pub fn convert_rgba_to_rgb565(src: &[u8], dst: &mut [u8]) {
+    assert_eq!(src.len() % 4, 0, "buffer length must be a multiple of 4");
+    assert_eq!(dst.len() % 2, 0, "buffer length must be a multiple of 2");
+    assert_eq!(
+        src.len() / 4,
+        dst.len() / 2,
+        "source and destination buffers must hold the same number of pixels"
+    );
+
+    for (src_pixel, dst_pixel) in src.chunks_exact(4).zip(dst.chunks_exact_mut(2)) {
+        let r = (src_pixel[0] as u16 * 31 + 127) / 255;
+        let g = (src_pixel[1] as u16 * 63 + 127) / 255;
+        let b = (src_pixel[2] as u16 * 31 + 127) / 255;
+
+        let packed = (r << 11) | (g << 5) | b;
+        dst_pixel.copy_from_slice(&packed.to_le_bytes());
+    }
+}
+
+/// Expands Gray8 to RGBA8 by replicating the luma into R, G and B with opaque alpha.
+#[inline]
+pub fn convert_gray8_to_rgba8(src: &[u8], dst: &mut [u8]) {
+    assert_eq!(dst.len() % 4, 0, "buffer length must be a multiple of 4");
+    assert_eq!(
+        src.len(),
+        dst.len() / 4,
+        "source and destination buffers must hold the same number of pixels"
+    );
+
+    for (&luma, dst_pixel) in src.iter().zip(dst.chunks_exact_mut(4)) {
+        dst_pixel.copy_from_slice(&[luma, luma, luma, 255]);
+    }
+}
+
+/// Reduces RGBA8 to Gray8 with BT.601 luma weights (0.299, 0.587, 0.114), dropping alpha.
+#[inline]
+pub fn convert_rgba8_to_gray8(src: &[u8], dst: &mut [u8]) {
+    assert_eq!(src.len() % 4, 0, "buffer length must be a multiple of 4");
+    assert_eq!(
+        src.len() / 4,
+        dst.len(),
+        "source and destination buffers must hold the same number of pixels"
+    );
+
+    for (src_pixel, luma) in src.chunks_exact(4).zip(dst.iter_mut()) {
+        let [r, g, b] = [src_pixel[0], src_pixel[1], src_pixel[2]].map(u32::from);
+        *luma = ((299 * r + 587 * g + 114 * b + 500) / 1000) as u8;
+    }
+}
+
+/// Unpacks A2B10G10R10 into RGBA8, keeping the top 8 bits of each color channel.
+#[inline]
+pub fn convert_a2rgb10_to_rgba8(src: &[u8], dst: &mut [u8]) {
     assert_eq!(
         src.len(),
         dst.len(),
@@ -24,15 +365,17 @@ pub fn convert_prgb_to_rgba(src: &[u8], dst: &mut [u8]) {
     assert_eq!(src.len() % 4, 0, "buffer length must be a multiple of 4");
 
     for (src_pixel, dst_pixel) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
-        dst_pixel[0] = src_pixel[1]; // R
-        dst_pixel[1] = src_pixel[2]; // G
-        dst_pixel[2] = src_pixel[3]; // B
-        dst_pixel[3] = src_pixel[0]; // A
+        let packed = u32::from_le_bytes([src_pixel[0], src_pixel[1], src_pixel[2], src_pixel[3]]);
+        dst_pixel[0] = ((packed >> 2) & 0xff) as u8; // R
+        dst_pixel[1] = ((packed >> 12) & 0xff) as u8; // G
+        dst_pixel[2] = ((packed >> 22) & 0xff) as u8; // B
+        dst_pixel[3] = ((packed >> 30) * 85) as u8; // A, 0..=3 expanded to 0..=255
     }
 }
 
+/// Packs RGBA8 into A2B10G10R10, replicating the top bits to fill the 10-bit channels.
 #[inline]
-pub fn convert_rgba_to_prgb(src: &[u8], dst: &mut [u8]) {
+pub fn convert_rgba8_to_a2rgb10(src: &[u8], dst: &mut [u8]) {
     assert_eq!(
         src.len(),
         dst.len(),
@@ -41,17 +384,236 @@ pub fn convert_rgba_to_prgb(src: &[u8], dst: &mut [u8]) {
     assert_eq!(src.len() % 4, 0, "buffer length must be a multiple of 4");
 
     for (src_pixel, dst_pixel) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
-        dst_pixel[0] = src_pixel[3]; // A
-        dst_pixel[1] = src_pixel[0]; // R
-        dst_pixel[2] = src_pixel[1]; // G
-        dst_pixel[3] = src_pixel[2]; // B
+        let expand = |c: u8| ((c as u32) << 2) | ((c as u32) >> 6);
+        let packed = expand(src_pixel[0])
+            | (expand(src_pixel[1]) << 10)
+            | (expand(src_pixel[2]) << 20)
+            | (((src_pixel[3] as u32) >> 6) << 30);
+        dst_pixel.copy_from_slice(&packed.to_le_bytes());
+    }
+}
+
+/// How `convert_rgba16_to_rgba8` maps 16-bit channels down to 8 bits
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ToneMapping {
+    /// Keep the top 8 bits of each channel. The cheapest option, and what `convert` uses.
+    #[default]
+    Truncate,
+    /// Round each channel to the nearest 8-bit value.
+    Round,
+    /// Treat the 16-bit range as linear light up to `peak` times SDR white and compress
+    /// color with extended Reinhard, so `peak` maps to 255 and highlights roll off
+    /// instead of clipping. Alpha is rounded.
+    Reinhard { peak: f32 },
+}
+
+/// Reduces RGBA16 to RGBA8 with the given tone mapping.
+#[inline]
+pub fn convert_rgba16_to_rgba8(src: &[u8], dst: &mut [u8], tone_mapping: ToneMapping) {
+    assert_eq!(src.len() % 8, 0, "buffer length must be a multiple of 8");
+    assert_eq!(
+        src.len() / 2,
+        dst.len(),
+        "source and destination buffers must hold the same number of pixels"
+    );
+
+    let round = |c: u16| ((c as u32 * 255 + 32767) / 65535) as u8;
+    let channels = src
+        .chunks_exact(2)
+        .map(|bytes| u16::from_ne_bytes([bytes[0], bytes[1]]));
+    match tone_mapping {
+        ToneMapping::Truncate => {
+            for (out, c) in dst.iter_mut().zip(channels) {
+                *out = (c >> 8) as u8;
+            }
+        }
+        ToneMapping::Round => {
+            for (out, c) in dst.iter_mut().zip(channels) {
+                *out = round(c);
+            }
+        }
+        ToneMapping::Reinhard { peak } => {
+            let peak = peak.max(f32::MIN_POSITIVE);
+            let white_squared = peak * peak;
+            let map = |c: u16| {
+                let light = c as f32 / 65535.0 * peak;
+                let mapped = light * (1.0 + light / white_squared) / (1.0 + light);
+                (mapped.clamp(0.0, 1.0) * 255.0).round() as u8
+            };
+            for (i, (out, c)) in dst.iter_mut().zip(channels).enumerate() {
+                *out = if i % 4 == 3 { round(c) } else { map(c) };
+            }
+        }
+    }
+}
+
+/// Expands RGBA8 to RGBA16 by byte replication, so 255 maps to 65535.
+#[inline]
+pub fn convert_rgba8_to_rgba16(src: &[u8], dst: &mut [u8]) {
+    assert_eq!(src.len() % 4, 0, "buffer length must be a multiple of 4");
+    assert_eq!(
+        src.len() * 2,
+        dst.len(),
+        "source and destination buffers must hold the same number of pixels"
+    );
+
+    for (&c, out) in src.iter().zip(dst.chunks_exact_mut(2)) {
+        out.copy_from_slice(&(c as u16 * 257).to_ne_bytes());
+    }
+}
+
+/// Converts planar I420 to RGBA8 with BT.601 limited-range coefficients and opaque alpha.
+///
+/// Each chroma sample is repeated over its 2x2 luma block; use `yuv420_to_rgba8` directly
+/// for smoother chroma upsampling.
+#[inline]
+pub fn convert_i420_to_rgba8(src: &[u8], dst: &mut [u8], width: u32, height: u32) {
+    assert_eq!(
+        src.len(),
+        PixelFormat::I420.buffer_size(width, height),
+        "source buffer has the wrong size"
+    );
+
+    let luma_size = width as usize * height as usize;
+    let (y_plane, chroma) = src.split_at(luma_size);
+    let (u_plane, v_plane) = chroma.split_at(chroma.len() / 2);
+    yuv420_to_rgba8(
+        y_plane,
+        u_plane,
+        v_plane,
+        dst,
+        width,
+        height,
+        ChromaUpsampling::Nearest,
+    );
+}
+
+/// 4x4 Bayer threshold matrix
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Like `convert`, but applies ordered dithering when reducing bit depth
+///
+/// Pairs without a bit-depth reduction convert exactly as `convert` does.
+#[inline]
+pub fn convert_dithered(
+    src: &[u8],
+    dst: &mut [u8],
+    src_format: PixelFormat,
+    dst_format: PixelFormat,
+    width: u32,
+) {
+    match (src_format, dst_format) {
+        (PixelFormat::Rgba8, PixelFormat::Rgb565) => {
+            convert_rgba_to_rgb565_dithered(src, dst, width)
+        }
+        _ => convert(src, dst, src_format, dst_format),
+    }
+}
+
+/// Converts RGBA8 to RGB565 with 4x4 ordered (Bayer) dithering to reduce banding.
+///
+/// `width` is the frame width in pixels, used to position the dither pattern.
+#[inline]
+pub fn convert_rgba_to_rgb565_dithered(src: &[u8], dst: &mut [u8], width: u32) {
+    assert_eq!(src.len() % 4, 0, "buffer length must be a multiple of 4");
+    assert_eq!(dst.len() % 2, 0, "buffer length must be a multiple of 2");
+    assert_eq!(
+        src.len() / 4,
+        dst.len() / 2,
+        "source and destination buffers must hold the same number of pixels"
+    );
+    let width = (width as usize).max(1);
+
+    for (i, (src_pixel, dst_pixel)) in src.chunks_exact(4).zip(dst.chunks_exact_mut(2)).enumerate()
+    {
+        // Threshold in (0, 1) as a fraction of 32, offset to stay centered on the cell
+        let threshold = 2 * BAYER_4X4[(i / width) % 4][(i % width) % 4] as u32 + 1;
+        let quantize = |c: u8, max: u32| (c as u32 * max * 32 + threshold * 255) / (255 * 32);
+
+        let r = quantize(src_pixel[0], 31) as u16;
+        let g = quantize(src_pixel[1], 63) as u16;
+        let b = quantize(src_pixel[2], 31) as u16;
+
+        let packed = (r << 11) | (g << 5) | b;
+        dst_pixel.copy_from_slice(&packed.to_le_bytes());
+    }
+}
+
+/// Converts straight-alpha RGBA8 to premultiplied alpha in place.
+///
+/// Each color channel is scaled by A/255, rounding to nearest.
+#[inline]
+pub fn premultiply_rgba8(buf: &mut [u8]) {
+    assert_eq!(buf.len() % 4, 0, "buffer length must be a multiple of 4");
+
+    for pixel in buf.chunks_exact_mut(4) {
+        let a = pixel[3] as u32;
+        if a < 255 {
+            for channel in &mut pixel[..3] {
+                *channel = ((*channel as u32 * a + 127) / 255) as u8;
+            }
+        }
+    }
+}
+
+/// Converts premultiplied RGBA8 to straight alpha in place.
+///
+/// Pixels with zero alpha carry no color and are cleared to transparent black.
+#[inline]
+pub fn unpremultiply_rgba8(buf: &mut [u8]) {
+    assert_eq!(buf.len() % 4, 0, "buffer length must be a multiple of 4");
+
+    for pixel in buf.chunks_exact_mut(4) {
+        let a = pixel[3] as u32;
+        if a == 0 {
+            pixel[..3].fill(0);
+        } else if a < 255 {
+            for channel in &mut pixel[..3] {
+                *channel = ((*channel as u32 * 255 + a / 2) / a).min(255) as u8;
+            }
+        }
     }
 }
 
+/// Converts genuinely premultiplied PRGB8 (A, R, G, B) to straight-alpha RGBA8.
+///
+/// Unlike `convert` from `Prgb8`, which only reorders channels, color is divided by alpha
+/// so edges don't darken, and fully transparent pixels become transparent black.
+pub fn convert_premultiplied_argb_to_straight_rgba(src: &[u8], dst: &mut [u8]) {
+    convert_swizzle(src, dst, Swizzle::PRGB_TO_RGBA);
+    unpremultiply_rgba8(dst);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_supports_matches_convert_frame() {
+        use PixelFormat::*;
+        let formats = [
+            Rgba8, Prgb8, Rgb565, Rgb8, Bgra8, A2Rgb10, Gray8, Bgr8, Argb8, Abgr8, I420, Rgba16,
+        ];
+
+        for src_format in formats {
+            for dst_format in formats {
+                let src = vec![0u8; src_format.buffer_size(2, 2)];
+                let mut dst = vec![0u8; dst_format.buffer_size(2, 2)];
+                let converts = std::panic::catch_unwind(move || {
+                    convert_frame(&src, &mut dst, src_format, dst_format, 2, 2)
+                })
+                .is_ok();
+                assert_eq!(
+                    supports(src_format, dst_format),
+                    converts,
+                    "{:?} to {:?}",
+                    src_format,
+                    dst_format
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_needs_conversion() {
         assert!(needs_conversion(PixelFormat::Rgba8, PixelFormat::Prgb8));
@@ -60,6 +622,30 @@ mod tests {
         assert!(!needs_conversion(PixelFormat::Prgb8, PixelFormat::Prgb8));
     }
 
+    #[test]
+    fn test_convert_same_format_copies() {
+        let src = [1, 2, 3, 4, 5, 6, 7, 8];
+        let mut dst = [0u8; 8];
+        convert(&src, &mut dst, PixelFormat::Rgba8, PixelFormat::Rgba8);
+        assert_eq!(dst, src);
+    }
+
+    #[test]
+    #[should_panic(expected = "source and destination buffers must have the same length")]
+    fn test_convert_same_format_mismatched_lengths() {
+        let src = [0u8; 8];
+        let mut dst = [0u8; 4];
+        convert(&src, &mut dst, PixelFormat::Prgb8, PixelFormat::Prgb8);
+    }
+
+    #[test]
+    #[should_panic(expected = "conversion from Prgb8 to Rgb565 is not supported")]
+    fn test_convert_unsupported_pair() {
+        let src = [0u8; 4];
+        let mut dst = [0u8; 2];
+        convert(&src, &mut dst, PixelFormat::Prgb8, PixelFormat::Rgb565);
+    }
+
     #[test]
     fn test_prgb_to_rgba_single_pixel() {
         let src = [255, 128, 64, 32]; // A=255, R=128, G=64, B=32
@@ -140,7 +726,7 @@ mod tests {
             for x in 0..width {
                 let idx = (y * width + x) * 4;
                 let checker = ((x / 8) + (y / 8)) % 2;
-                original[idx + 0] = (x % 256) as u8; // A: horizontal gradient
+                original[idx] = (x % 256) as u8; // A: horizontal gradient
                 original[idx + 1] = (y % 256) as u8; // R: vertical gradient
                 original[idx + 2] = if checker == 0 { 128 } else { 64 }; // G: checkerboard
                 original[idx + 3] = ((x + y) % 256) as u8; // B: diagonal gradient
@@ -170,7 +756,7 @@ mod tests {
         for y in 0..height {
             for x in 0..width {
                 let idx = (y * width + x) * 4;
-                original[idx + 0] = ((x * y) % 256) as u8; // R: xy product
+                original[idx] = ((x * y) % 256) as u8; // R: xy product
                 original[idx + 1] = (x % 256) as u8; // G: horizontal gradient
                 original[idx + 2] = (y % 256) as u8; // B: vertical gradient
                 original[idx + 3] = ((x ^ y) % 256) as u8; // A: XOR pattern
@@ -187,6 +773,117 @@ mod tests {
         assert_eq!(original, final_result);
     }
 
+    #[test]
+    fn test_rgba_to_rgb565_primaries() {
+        let src = [
+            255, 0, 0, 255, // red
+            0, 255, 0, 255, // green
+            0, 0, 255, 255, // blue
+        ];
+        let mut dst = [0u8; 6];
+        convert_rgba_to_rgb565(&src, &mut dst);
+
+        assert_eq!(u16::from_le_bytes([dst[0], dst[1]]), 0xf800);
+        assert_eq!(u16::from_le_bytes([dst[2], dst[3]]), 0x07e0);
+        assert_eq!(u16::from_le_bytes([dst[4], dst[5]]), 0x001f);
+    }
+
+    #[test]
+    fn test_rgb565_to_rgba_primaries() {
+        let src = [
+            0x00, 0xf8, // red
+            0xe0, 0x07, // green
+            0x1f, 0x00, // blue
+        ];
+        let mut dst = [0u8; 12];
+        convert_rgb565_to_rgba(&src, &mut dst);
+
+        let expected = [
+            255, 0, 0, 255, // red
+            0, 255, 0, 255, // green
+            0, 0, 255, 255, // blue
+        ];
+        assert_eq!(dst, expected);
+    }
+
+    #[test]
+    fn test_rgba_to_rgb565_rounding() {
+        // 5/255 is closer to 1/31 than 0/31, 4/255 is closer to 0/31
+        let src = [5, 0, 4, 255];
+        let mut dst = [0u8; 2];
+        convert_rgba_to_rgb565(&src, &mut dst);
+        assert_eq!(u16::from_le_bytes(dst), 0x0800);
+    }
+
+    #[test]
+    fn test_rgb565_round_trip_drops_alpha() {
+        let original = [255, 255, 255, 128];
+        let mut packed = [0u8; 2];
+        let mut result = [0u8; 4];
+
+        convert(
+            &original,
+            &mut packed,
+            PixelFormat::Rgba8,
+            PixelFormat::Rgb565,
+        );
+        convert(
+            &packed,
+            &mut result,
+            PixelFormat::Rgb565,
+            PixelFormat::Rgba8,
+        );
+
+        assert_eq!(result, [255, 255, 255, 255]);
+    }
+
+    #[test]
+    #[should_panic(expected = "source and destination buffers must hold the same number of pixels")]
+    fn test_rgb565_mismatched_pixel_counts() {
+        let src = [0u8; 8];
+        let mut dst = [0u8; 2];
+        convert_rgba_to_rgb565(&src, &mut dst);
+    }
+
+    #[test]
+    fn test_unpremultiply_half_alpha() {
+        // 50% alpha orange, premultiplied
+        let mut buf = [64, 32, 0, 128];
+        unpremultiply_rgba8(&mut buf);
+        assert_eq!(buf, [128, 64, 0, 128]);
+    }
+
+    #[test]
+    fn test_premultiply_half_alpha() {
+        let mut buf = [128, 64, 0, 128, 10, 20, 30, 255, 10, 20, 30, 0];
+        premultiply_rgba8(&mut buf);
+        assert_eq!(buf, [64, 32, 0, 128, 10, 20, 30, 255, 0, 0, 0, 0]);
+
+        unpremultiply_rgba8(&mut buf[..4]);
+        assert_eq!(buf[..4], [128, 64, 0, 128]);
+    }
+
+    #[test]
+    fn test_unpremultiply_opaque_and_transparent() {
+        let mut buf = [
+            10, 20, 30, 255, // opaque: unchanged
+            10, 20, 30, 0, // transparent: color cleared
+        ];
+        unpremultiply_rgba8(&mut buf);
+        assert_eq!(buf, [10, 20, 30, 255, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_premultiplied_argb_to_straight_rgba() {
+        let src = [
+            128, 64, 32, 0, // half alpha: color divided by alpha
+            0, 10, 20, 30, // transparent: stray color cleared
+        ];
+        let mut dst = [0xffu8; 8];
+        convert_premultiplied_argb_to_straight_rgba(&src, &mut dst);
+        assert_eq!(dst, [128, 64, 0, 128, 0, 0, 0, 0]);
+    }
+
     #[test]
     #[should_panic(expected = "source and destination buffers must have the same length")]
     fn test_mismatched_buffer_lengths() {
@@ -202,4 +899,413 @@ mod tests {
         let mut dst = [0u8; 7];
         convert_prgb_to_rgba(&src, &mut dst);
     }
+
+    #[test]
+    fn test_swizzle_reverse() {
+        let src = [10, 20, 30, 40, 1, 2, 3, 4];
+        let mut dst = [0u8; 8];
+        convert_swizzle(&src, &mut dst, Swizzle([3, 2, 1, 0]));
+        assert_eq!(dst, [40, 30, 20, 10, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_swizzle_matches_fixed_conversions() {
+        let prgb = [200, 10, 20, 30];
+        let mut expected = [0u8; 4];
+        let mut swizzled = [0u8; 4];
+
+        convert_prgb_to_rgba(&prgb, &mut expected);
+        convert_swizzle(&prgb, &mut swizzled, Swizzle::PRGB_TO_RGBA);
+        assert_eq!(swizzled, expected);
+
+        convert_rgba_to_prgb(&expected, &mut swizzled);
+        let mut round_trip = [0u8; 4];
+        convert_swizzle(&expected, &mut round_trip, Swizzle::RGBA_TO_PRGB);
+        assert_eq!(round_trip, swizzled);
+    }
+
+    #[test]
+    #[should_panic(expected = "less than 4")]
+    fn test_swizzle_rejects_out_of_range_channel() {
+        convert_swizzle(&[0; 4], &mut [0; 4], Swizzle([0, 1, 2, 4]));
+    }
+
+    #[test]
+    fn test_convert_region_middle_rows() {
+        // 2x4 PRGB frame, every pixel opaque with R = row index
+        let mut src = Vec::new();
+        for y in 0..4u8 {
+            for _ in 0..2 {
+                src.extend_from_slice(&[255, y, 0, 0]);
+            }
+        }
+        let mut dst = vec![9u8; 32];
+
+        convert_region(
+            &src,
+            &mut dst,
+            Rect::new(0, 1, 2, 2),
+            2,
+            PixelFormat::Prgb8,
+            PixelFormat::Rgba8,
+        );
+
+        assert_eq!(&dst[..8], &[9; 8]);
+        assert_eq!(&dst[8..16], &[1, 0, 0, 255, 1, 0, 0, 255]);
+        assert_eq!(&dst[16..24], &[2, 0, 0, 255, 2, 0, 0, 255]);
+        assert_eq!(&dst[24..], &[9; 8]);
+    }
+
+    #[test]
+    fn test_convert_region_column_span() {
+        let src = [255, 10, 20, 30].repeat(3);
+        let mut dst = vec![0u8; 12];
+
+        convert_region(
+            &src,
+            &mut dst,
+            Rect::new(1, 0, 1, 1),
+            3,
+            PixelFormat::Prgb8,
+            PixelFormat::Rgba8,
+        );
+
+        assert_eq!(dst, vec![0, 0, 0, 0, 10, 20, 30, 255, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds")]
+    fn test_convert_region_out_of_bounds() {
+        let mut dst = vec![0u8; 16];
+        convert_region(
+            &[0; 16],
+            &mut dst,
+            Rect::new(0, 1, 2, 2),
+            2,
+            PixelFormat::Rgba8,
+            PixelFormat::Rgba8,
+        );
+    }
+
+    #[test]
+    fn test_convert_strided_repads_rows() {
+        // 1x2 PRGB frame with 4 padding bytes per row, the last row unpadded
+        let src = [255, 1, 2, 3, 9, 9, 9, 9, 255, 4, 5, 6];
+        let mut dst = vec![7u8; 12];
+
+        convert_strided(
+            &src,
+            8,
+            &mut dst,
+            6,
+            1,
+            PixelFormat::Prgb8,
+            PixelFormat::Rgb8,
+        );
+
+        assert_eq!(dst, [1, 2, 3, 7, 7, 7, 4, 5, 6, 7, 7, 7]);
+    }
+
+    /// 64x4 RGBA frame whose red channel ramps slowly from dark to slightly less dark
+    fn red_gradient() -> Vec<u8> {
+        (0..4)
+            .flat_map(|_| (0..64u32).flat_map(|x| [(x * 24 / 64) as u8, 0, 0, 255]))
+            .collect()
+    }
+
+    fn red_levels(rgb565: &[u8]) -> Vec<u16> {
+        rgb565
+            .chunks_exact(2)
+            .map(|pixel| u16::from_le_bytes([pixel[0], pixel[1]]) >> 11)
+            .collect()
+    }
+
+    #[test]
+    fn test_dithering_breaks_up_banding() {
+        let src = red_gradient();
+        let mut plain = vec![0u8; 512];
+        let mut dithered = vec![0u8; 512];
+
+        convert(&src, &mut plain, PixelFormat::Rgba8, PixelFormat::Rgb565);
+        convert_dithered(
+            &src,
+            &mut dithered,
+            PixelFormat::Rgba8,
+            PixelFormat::Rgb565,
+            64,
+        );
+
+        let transitions =
+            |levels: &[u16]| levels.windows(2).filter(|pair| pair[0] != pair[1]).count();
+        let plain_levels = red_levels(&plain);
+        let dithered_levels = red_levels(&dithered);
+
+        // Rounding yields a few wide bands, dithering interleaves neighboring levels
+        assert!(transitions(&dithered_levels[..64]) > 2 * transitions(&plain_levels[..64]));
+
+        // Over a full 4x4 pattern the dithered average tracks the source gradient
+        let source_mean: f64 = (0..64)
+            .map(|x| (x * 24 / 64) as f64 * 31.0 / 255.0)
+            .sum::<f64>()
+            / 64.0;
+        let dithered_mean =
+            dithered_levels.iter().map(|&l| l as f64).sum::<f64>() / dithered_levels.len() as f64;
+        assert!((dithered_mean - source_mean).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_dithering_keeps_extremes() {
+        let src = [0, 0, 0, 255, 255, 255, 255, 255];
+        let mut dst = [0u8; 4];
+        convert_rgba_to_rgb565_dithered(&src, &mut dst, 2);
+        assert_eq!(u16::from_le_bytes([dst[0], dst[1]]), 0x0000);
+        assert_eq!(u16::from_le_bytes([dst[2], dst[3]]), 0xffff);
+    }
+
+    #[test]
+    fn test_rgb8_to_rgba8_inserts_opaque_alpha() {
+        let src = [10, 20, 30, 40, 50, 60];
+        let mut dst = [0u8; 8];
+        convert(&src, &mut dst, PixelFormat::Rgb8, PixelFormat::Rgba8);
+        assert_eq!(dst, [10, 20, 30, 255, 40, 50, 60, 255]);
+    }
+
+    #[test]
+    fn test_rgba8_to_rgb8_drops_alpha() {
+        let src = [10, 20, 30, 128, 40, 50, 60, 0];
+        let mut dst = [0u8; 6];
+        convert(&src, &mut dst, PixelFormat::Rgba8, PixelFormat::Rgb8);
+        assert_eq!(dst, [10, 20, 30, 40, 50, 60]);
+    }
+
+    #[test]
+    #[should_panic(expected = "same number of pixels")]
+    fn test_rgb8_pixel_count_mismatch() {
+        let mut dst = [0u8; 4];
+        convert_rgb8_to_rgba8(&[0; 6], &mut dst);
+    }
+
+    #[test]
+    fn test_bgra8_round_trips() {
+        let rgba = [10, 20, 30, 40];
+        let mut bgra = [0u8; 4];
+        convert(&rgba, &mut bgra, PixelFormat::Rgba8, PixelFormat::Bgra8);
+        assert_eq!(bgra, [30, 20, 10, 40]);
+
+        let mut prgb = [0u8; 4];
+        convert(&bgra, &mut prgb, PixelFormat::Bgra8, PixelFormat::Prgb8);
+        assert_eq!(prgb, [40, 10, 20, 30]);
+
+        let mut back = [0u8; 4];
+        convert(&prgb, &mut back, PixelFormat::Prgb8, PixelFormat::Bgra8);
+        assert_eq!(back, bgra);
+    }
+
+    #[test]
+    fn test_a2rgb10_unpacks_to_rgba8() {
+        // R = 1023, G = 512, B = 3, A = 2
+        let packed: u32 = 1023 | (512 << 10) | (3 << 20) | (2 << 30);
+        let mut dst = [0u8; 4];
+        convert(
+            &packed.to_le_bytes(),
+            &mut dst,
+            PixelFormat::A2Rgb10,
+            PixelFormat::Rgba8,
+        );
+        assert_eq!(dst, [255, 128, 0, 170]);
+    }
+
+    #[test]
+    fn test_rgba8_a2rgb10_round_trip() {
+        let src = [0, 1, 128, 255, 255, 254, 127, 64];
+        let mut packed = [0u8; 8];
+        let mut back = [0u8; 8];
+        convert(&src, &mut packed, PixelFormat::Rgba8, PixelFormat::A2Rgb10);
+        convert(&packed, &mut back, PixelFormat::A2Rgb10, PixelFormat::Rgba8);
+
+        // Color survives exactly, alpha is quantized to 2 bits
+        assert_eq!(back, [0, 1, 128, 255, 255, 254, 127, 85]);
+        assert_eq!(
+            u32::from_le_bytes([packed[4], packed[5], packed[6], packed[7]]) & 0x3ff,
+            1023
+        );
+    }
+
+    #[test]
+    fn test_rgba16_tone_mapping() {
+        let src: Vec<u8> = [65535u16, 16384, 200, 0]
+            .iter()
+            .flat_map(|c| c.to_ne_bytes())
+            .collect();
+        let mut dst = [0u8; 4];
+
+        convert(&src, &mut dst, PixelFormat::Rgba16, PixelFormat::Rgba8);
+        assert_eq!(dst, [255, 64, 0, 0]);
+
+        convert_rgba16_to_rgba8(&src, &mut dst, ToneMapping::Round);
+        assert_eq!(dst, [255, 64, 1, 0]);
+
+        // Peak maps to white and SDR white (a quarter of the range) lands mid-scale
+        convert_rgba16_to_rgba8(&src, &mut dst, ToneMapping::Reinhard { peak: 4.0 });
+        assert_eq!(dst[0], 255);
+        assert!((128..160).contains(&dst[1]));
+        assert_eq!(dst[3], 0);
+    }
+
+    #[test]
+    fn test_rgba8_rgba16_round_trip() {
+        let src = [0, 1, 128, 255];
+        let mut wide = [0u8; 8];
+        let mut back = [0u8; 4];
+        convert(&src, &mut wide, PixelFormat::Rgba8, PixelFormat::Rgba16);
+        assert_eq!(u16::from_ne_bytes([wide[6], wide[7]]), 65535);
+
+        for tone_mapping in [ToneMapping::Truncate, ToneMapping::Round] {
+            convert_rgba16_to_rgba8(&wide, &mut back, tone_mapping);
+            assert_eq!(back, src);
+        }
+    }
+
+    #[test]
+    fn test_gray8_to_rgba8_replicates_luma() {
+        let mut dst = [0u8; 8];
+        convert(&[0, 200], &mut dst, PixelFormat::Gray8, PixelFormat::Rgba8);
+        assert_eq!(dst, [0, 0, 0, 255, 200, 200, 200, 255]);
+    }
+
+    #[test]
+    fn test_rgba8_to_gray8_uses_luma_weights() {
+        let src = [
+            255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 255, 255, 255, 0,
+        ];
+        let mut dst = [0u8; 4];
+        convert(&src, &mut dst, PixelFormat::Rgba8, PixelFormat::Gray8);
+        assert_eq!(dst, [76, 150, 29, 255]);
+    }
+
+    #[test]
+    #[should_panic(expected = "same number of pixels")]
+    fn test_gray8_pixel_count_mismatch() {
+        let mut dst = [0u8; 4];
+        convert_gray8_to_rgba8(&[0; 4], &mut dst);
+    }
+
+    #[test]
+    fn test_prgb_rgba_round_trip_beyond_vector_width() {
+        // Long enough for the vector path, with a tail of scalar pixels
+        let prgb: Vec<u8> = (0..=255).cycle().take(4 * 37).collect();
+        let mut rgba = vec![0u8; prgb.len()];
+        let mut back = vec![0u8; prgb.len()];
+
+        convert(&prgb, &mut rgba, PixelFormat::Prgb8, PixelFormat::Rgba8);
+        convert(&rgba, &mut back, PixelFormat::Rgba8, PixelFormat::Prgb8);
+
+        for (src_pixel, dst_pixel) in prgb.chunks_exact(4).zip(rgba.chunks_exact(4)) {
+            assert_eq!(
+                dst_pixel,
+                [src_pixel[1], src_pixel[2], src_pixel[3], src_pixel[0]]
+            );
+        }
+        assert_eq!(back, prgb);
+    }
+
+    #[test]
+    fn test_i420_to_rgba8_reads_each_plane() {
+        // 4x2 frame: black and white luma under gray chroma, then a red chroma block
+        let src = [16, 235, 81, 81, 16, 235, 81, 81, 128, 90, 128, 240];
+        let mut dst = [0u8; 32];
+        convert_frame(&src, &mut dst, PixelFormat::I420, PixelFormat::Rgba8, 4, 2);
+
+        for row in dst.chunks_exact(16) {
+            assert_eq!(row[..4], [0, 0, 0, 255]);
+            assert_eq!(row[4..8], [255, 255, 255, 255]);
+            assert_eq!(row[8..12], [255, 0, 0, 255]);
+            assert_eq!(row[12..], [255, 0, 0, 255]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "use convert_frame")]
+    fn test_planar_convert_needs_frame_size() {
+        let mut dst = [0u8; 16];
+        convert(&[0; 6], &mut dst, PixelFormat::I420, PixelFormat::Rgba8);
+    }
+
+    /// Every byte value in every channel position, over more pixels than one vector
+    fn test_pattern(bytes_per_pixel: usize) -> Vec<u8> {
+        (0..=255u8).cycle().take(bytes_per_pixel * 67).collect()
+    }
+
+    #[test]
+    fn test_channel_order_matches_hand_written_conversions() {
+        // Expected bytes are what the hand-written per-pair loops produced
+        let prgb = [1, 2, 3, 4, 5, 6, 7, 8];
+        let rgba = [2, 3, 4, 1, 6, 7, 8, 5];
+        let rgb = [2, 3, 4, 6, 7, 8];
+        let opaque = [2, 3, 4, 255, 6, 7, 8, 255];
+        let cases: [(PixelFormat, &[u8], PixelFormat, &[u8]); 4] = [
+            (PixelFormat::Prgb8, &prgb, PixelFormat::Rgba8, &rgba),
+            (PixelFormat::Rgba8, &rgba, PixelFormat::Prgb8, &prgb),
+            (PixelFormat::Rgb8, &rgb, PixelFormat::Rgba8, &opaque),
+            (PixelFormat::Rgba8, &rgba, PixelFormat::Rgb8, &rgb),
+        ];
+
+        for (src_format, src, dst_format, expected) in cases {
+            let mut dst = vec![0u8; expected.len()];
+            convert(src, &mut dst, src_format, dst_format);
+            assert_eq!(dst, expected, "{:?} to {:?}", src_format, dst_format);
+        }
+    }
+
+    #[test]
+    fn test_channel_order_matches_hand_written_conversions_beyond_vector_width() {
+        let src = test_pattern(4);
+        let mut generic = vec![0u8; src.len()];
+
+        convert(&src, &mut generic, PixelFormat::Prgb8, PixelFormat::Rgba8);
+        for (src_pixel, dst_pixel) in src.chunks_exact(4).zip(generic.chunks_exact(4)) {
+            assert_eq!(
+                dst_pixel,
+                [src_pixel[1], src_pixel[2], src_pixel[3], src_pixel[0]]
+            );
+        }
+    }
+
+    #[test]
+    fn test_channel_order_matches_previous_bgra8_swizzles() {
+        let src = test_pattern(4);
+        let mut expected = vec![0u8; src.len()];
+        let mut generic = vec![0u8; src.len()];
+
+        convert_swizzle(&src, &mut expected, Swizzle::SWAP_RED_BLUE);
+        convert(&src, &mut generic, PixelFormat::Rgba8, PixelFormat::Bgra8);
+        assert_eq!(generic, expected);
+
+        convert_swizzle(&src, &mut expected, Swizzle::REVERSE);
+        convert(&src, &mut generic, PixelFormat::Prgb8, PixelFormat::Bgra8);
+        assert_eq!(generic, expected);
+    }
+
+    #[test]
+    fn test_byte_order_variants() {
+        let rgba = [10, 20, 30, 40];
+        let convert_to = |format: PixelFormat| {
+            let mut dst = vec![0u8; format.bytes_per_pixel()];
+            convert(&rgba, &mut dst, PixelFormat::Rgba8, format);
+            dst
+        };
+
+        assert_eq!(convert_to(PixelFormat::Argb8), [40, 10, 20, 30]);
+        assert_eq!(convert_to(PixelFormat::Abgr8), [40, 30, 20, 10]);
+        assert_eq!(convert_to(PixelFormat::Bgr8), [30, 20, 10]);
+
+        let mut argb = [0u8; 4];
+        convert(
+            &[30, 20, 10],
+            &mut argb,
+            PixelFormat::Bgr8,
+            PixelFormat::Argb8,
+        );
+        assert_eq!(argb, [255, 10, 20, 30]);
+    }
 }
@@ -0,0 +1,147 @@
+use crate::{DisplayBackend, DisplayPresenter, FrameQueue, TripleBuffer};
+
+/// Point-in-time view of a render/present pipeline for debugging stalls
+///
+/// Build one from whichever parts the pipeline uses and print it with `{:#?}`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PipelineSnapshot {
+    pub buffer: Option<BufferSnapshot>,
+    pub queue: Option<QueueSnapshot>,
+    pub presenter: Option<PresenterSnapshot>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferSnapshot {
+    pub render_idx: usize,
+    pub ready_idx: usize,
+    pub present_idx: usize,
+    /// A committed frame is waiting for `commit_present`.
+    pub has_new_frame: bool,
+    pub generation: u64,
+    pub dropped_frames: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueueSnapshot {
+    pub next_frame: u64,
+    pub len: usize,
+    pub oldest_pending: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PresenterSnapshot {
+    pub last_present_time_ms: Option<f64>,
+    pub presented_count: u64,
+    pub skipped_count: u64,
+    pub has_held_frame: bool,
+}
+
+impl PipelineSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_buffer(mut self, buffer: &TripleBuffer) -> Self {
+        let (render_idx, ready_idx, present_idx) = buffer.slot_indices();
+        self.buffer = Some(BufferSnapshot {
+            render_idx,
+            ready_idx,
+            present_idx,
+            has_new_frame: buffer.has_new_frame(),
+            generation: buffer.generation(),
+            dropped_frames: buffer.dropped_frames(),
+        });
+        self
+    }
+
+    pub fn with_queue(mut self, queue: &FrameQueue) -> Self {
+        self.queue = Some(QueueSnapshot {
+            next_frame: queue.next_frame_number(),
+            len: queue.len(),
+            oldest_pending: queue.oldest_pending(),
+        });
+        self
+    }
+
+    pub fn with_presenter<B: DisplayBackend>(mut self, presenter: &DisplayPresenter<B>) -> Self {
+        self.presenter = Some(PresenterSnapshot {
+            last_present_time_ms: presenter.last_present_time_ms(),
+            presented_count: presenter.presented_count(),
+            skipped_count: presenter.skipped_count(),
+            has_held_frame: presenter.has_held_frame(),
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::{memory, MemoryBackend};
+    use crate::PixelFormat;
+
+    #[test]
+    fn test_snapshot_reflects_live_state() {
+        let buffer = TripleBuffer::new(2, 2, PixelFormat::Rgba8);
+        let mut queue = FrameQueue::new(8);
+        let mut presenter = DisplayPresenter::new(
+            MemoryBackend::<memory::Rgba8>::new(),
+            2,
+            2,
+            PixelFormat::Rgba8,
+        )
+        .unwrap();
+
+        buffer.commit_render();
+        queue.push(0, vec![0; 16]);
+        queue.push(2, vec![0; 16]);
+        queue.push(3, vec![0; 16]);
+        let frame = queue.pop_ready().unwrap();
+        presenter.present_frame_at(&frame, 40.0).unwrap();
+
+        let snapshot = PipelineSnapshot::new()
+            .with_buffer(&buffer)
+            .with_queue(&queue)
+            .with_presenter(&presenter);
+
+        assert_eq!(
+            snapshot.buffer,
+            Some(BufferSnapshot {
+                render_idx: 1,
+                ready_idx: 0,
+                present_idx: 2,
+                has_new_frame: true,
+                generation: 1,
+                dropped_frames: 0,
+            })
+        );
+        assert_eq!(
+            snapshot.queue,
+            Some(QueueSnapshot {
+                next_frame: 1,
+                len: 2,
+                oldest_pending: Some(2),
+            })
+        );
+        assert_eq!(
+            snapshot.presenter,
+            Some(PresenterSnapshot {
+                last_present_time_ms: Some(40.0),
+                presented_count: 1,
+                skipped_count: 0,
+                has_held_frame: false,
+            })
+        );
+        assert!(format!("{:?}", snapshot).contains("oldest_pending: Some(2)"));
+    }
+
+    #[test]
+    fn test_partial_snapshot() {
+        let queue = FrameQueue::new(8);
+        let snapshot = PipelineSnapshot::new().with_queue(&queue);
+
+        assert!(snapshot.buffer.is_none());
+        assert!(snapshot.presenter.is_none());
+        assert_eq!(snapshot.queue.unwrap().len, 0);
+    }
+}
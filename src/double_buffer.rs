@@ -0,0 +1,146 @@
+use crate::{PixelFormat, SwapBuffer};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// Two frame buffers: one being rendered, one being presented.
+///
+/// `commit_render` swaps them, so the presenter always shows the latest finished frame
+/// with no ready slot queued in between. That saves up to a frame of latency over
+/// `TripleBuffer`, at the cost of the renderer waiting in `render_buffer` while the
+/// presenter still holds the slot it is about to draw into.
+pub struct DoubleBuffer {
+    buffers: [Mutex<Vec<u8>>; 2],
+    present_idx: AtomicUsize,
+    fresh_frame: AtomicBool,
+    generation: AtomicU64,
+    width: u32,
+    height: u32,
+    format: PixelFormat,
+}
+
+impl DoubleBuffer {
+    pub fn new(width: u32, height: u32, format: PixelFormat) -> Self {
+        assert!(width > 0, "width must be greater than 0");
+        assert!(height > 0, "height must be greater than 0");
+
+        let size = format.buffer_size(width, height);
+        Self {
+            buffers: [Mutex::new(vec![0u8; size]), Mutex::new(vec![0u8; size])],
+            present_idx: AtomicUsize::new(1),
+            fresh_frame: AtomicBool::new(false),
+            generation: AtomicU64::new(0),
+            width,
+            height,
+            format,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    /// Get the buffer for rendering
+    ///
+    /// Waits if the presenter still holds this slot from before the last swap.
+    pub fn render_buffer(&self) -> MutexGuard<'_, Vec<u8>> {
+        let idx = 1 - self.present_idx.load(Ordering::Acquire);
+        self.buffers[idx].lock().unwrap()
+    }
+
+    /// Swap the render and present slots, making the rendered frame the one to present
+    pub fn commit_render(&self) {
+        let present = self.present_idx.load(Ordering::Acquire);
+        self.present_idx.store(1 - present, Ordering::Release);
+        self.fresh_frame.store(true, Ordering::Release);
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Whether a frame has been rendered since the last `commit_present`
+    pub fn has_new_frame(&self) -> bool {
+        self.fresh_frame.load(Ordering::Acquire)
+    }
+
+    /// Number of frames committed with `commit_render` so far
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Get the buffer for presentation
+    pub fn present_buffer(&self) -> MutexGuard<'_, Vec<u8>> {
+        let idx = self.present_idx.load(Ordering::Acquire);
+        self.buffers[idx].lock().unwrap()
+    }
+
+    /// Mark the current frame as picked up
+    ///
+    /// `commit_render` already moved it into the present slot, so there is nothing to swap.
+    pub fn commit_present(&self) {
+        self.fresh_frame.store(false, Ordering::Release);
+    }
+}
+
+impl SwapBuffer for DoubleBuffer {
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    fn render_buffer(&self) -> MutexGuard<'_, Vec<u8>> {
+        DoubleBuffer::render_buffer(self)
+    }
+
+    fn commit_render(&self) {
+        DoubleBuffer::commit_render(self)
+    }
+
+    fn present_buffer(&self) -> MutexGuard<'_, Vec<u8>> {
+        DoubleBuffer::present_buffer(self)
+    }
+
+    fn commit_present(&self) {
+        DoubleBuffer::commit_present(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_render_presents_latest_frame() {
+        let db = DoubleBuffer::new(2, 2, PixelFormat::Rgba8);
+
+        db.render_buffer()[0] = 1;
+        db.commit_render();
+        db.render_buffer()[0] = 2;
+        db.commit_render();
+        assert!(db.has_new_frame());
+
+        db.commit_present();
+        assert_eq!(db.present_buffer()[0], 2);
+        assert!(!db.has_new_frame());
+        assert_eq!(db.generation(), 2);
+    }
+
+    #[test]
+    fn test_render_and_present_slots_differ() {
+        let db = DoubleBuffer::new(1, 1, PixelFormat::Gray8);
+        db.render_buffer()[0] = 7;
+        db.commit_render();
+
+        // Drawing the next frame leaves the presented one alone
+        db.render_buffer()[0] = 8;
+        assert_eq!(db.present_buffer()[0], 7);
+    }
+}
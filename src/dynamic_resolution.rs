@@ -0,0 +1,190 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// Lowers the render resolution while frames are being dropped and raises it again
+/// once presentation is stable.
+///
+/// The controller runs on the presenting thread and publishes its scale factor through
+/// a [`ResolutionScale`] handle the render thread reads before each render. Buffers stay
+/// allocated at full size; the renderer draws into the top-left `render_size` region and
+/// upscales to the full frame before committing.
+pub struct DynamicResolution {
+    scale: ResolutionScale,
+    min_scale: f64,
+    step: f64,
+    drop_threshold: u32,
+    recover_frames: u32,
+    drops: u32,
+    stable_frames: u32,
+}
+
+/// Shared, lock-free view of the current render scale factor
+#[derive(Clone, Debug)]
+pub struct ResolutionScale(Arc<AtomicU32>);
+
+impl ResolutionScale {
+    fn new(scale: f64) -> Self {
+        Self(Arc::new(AtomicU32::new((scale as f32).to_bits())))
+    }
+
+    fn set(&self, scale: f64) {
+        self.0.store((scale as f32).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Current scale factor in `min_scale..=1.0`
+    pub fn get(&self) -> f64 {
+        f32::from_bits(self.0.load(Ordering::Relaxed)) as f64
+    }
+
+    /// Size to render at for a full-resolution frame of `width` x `height`
+    pub fn render_size(&self, width: u32, height: u32) -> (u32, u32) {
+        let scale = self.get();
+        let scaled = |size: u32| ((size as f64 * scale).round() as u32).clamp(1, size.max(1));
+        (scaled(width), scaled(height))
+    }
+}
+
+impl DynamicResolution {
+    pub fn new(min_scale: f64) -> Self {
+        assert!(
+            min_scale > 0.0 && min_scale <= 1.0,
+            "min_scale must be in 0.0..=1.0"
+        );
+
+        Self {
+            scale: ResolutionScale::new(1.0),
+            min_scale,
+            step: 0.1,
+            drop_threshold: 3,
+            recover_frames: 120,
+            drops: 0,
+            stable_frames: 0,
+        }
+    }
+
+    /// Configure how much the scale factor changes per adjustment
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = step.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Configure how many dropped frames without a stable run lower the scale
+    pub fn with_drop_threshold(mut self, drops: u32) -> Self {
+        self.drop_threshold = drops.max(1);
+        self
+    }
+
+    /// Configure how many consecutive on-time frames raise the scale again
+    pub fn with_recover_frames(mut self, frames: u32) -> Self {
+        self.recover_frames = frames.max(1);
+        self
+    }
+
+    /// Handle the render thread uses to read the current scale factor
+    pub fn scale_handle(&self) -> ResolutionScale {
+        self.scale.clone()
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale.get()
+    }
+
+    /// Record whether the latest display refresh dropped a frame and return the new scale
+    pub fn record_frame(&mut self, dropped: bool) -> f64 {
+        let scale = self.scale.get();
+
+        if dropped {
+            self.drops += 1;
+            self.stable_frames = 0;
+            if self.drops >= self.drop_threshold {
+                self.drops = 0;
+                self.scale.set((scale - self.step).max(self.min_scale));
+            }
+        } else {
+            self.stable_frames += 1;
+            if self.stable_frames >= self.recover_frames {
+                self.stable_frames = 0;
+                self.drops = 0;
+                self.scale.set((scale + self.step).min(1.0));
+            }
+        }
+
+        self.scale.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_sustained_drops_lower_scale() {
+        let mut controller = DynamicResolution::new(0.5);
+
+        for _ in 0..3 {
+            controller.record_frame(true);
+        }
+        assert_close(controller.scale(), 0.9);
+
+        for _ in 0..30 {
+            controller.record_frame(true);
+        }
+        assert_close(controller.scale(), 0.5);
+    }
+
+    #[test]
+    fn test_isolated_drops_keep_scale() {
+        let mut controller = DynamicResolution::new(0.5).with_recover_frames(10);
+
+        for _ in 0..20 {
+            controller.record_frame(true);
+            for _ in 0..10 {
+                controller.record_frame(false);
+            }
+        }
+        assert_close(controller.scale(), 1.0);
+    }
+
+    #[test]
+    fn test_stable_frames_recover_scale() {
+        let mut controller = DynamicResolution::new(0.5)
+            .with_drop_threshold(1)
+            .with_recover_frames(4);
+
+        controller.record_frame(true);
+        controller.record_frame(true);
+        assert_close(controller.scale(), 0.8);
+
+        for _ in 0..4 {
+            controller.record_frame(false);
+        }
+        assert_close(controller.scale(), 0.9);
+
+        for _ in 0..100 {
+            controller.record_frame(false);
+        }
+        assert_close(controller.scale(), 1.0);
+    }
+
+    #[test]
+    fn test_handle_render_size() {
+        let mut controller = DynamicResolution::new(0.5)
+            .with_drop_threshold(1)
+            .with_step(0.5);
+        let handle = controller.scale_handle();
+        assert_eq!(handle.render_size(640, 480), (640, 480));
+
+        controller.record_frame(true);
+        assert_eq!(handle.render_size(640, 480), (320, 240));
+        assert_eq!(handle.render_size(1, 1), (1, 1));
+    }
+}
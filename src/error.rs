@@ -1,3 +1,4 @@
+use crate::{PixelFormat, Rect};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -8,6 +9,30 @@ pub enum VideoBufferError {
     NotInitialized,
     #[error("Present failed: {0}")]
     PresentFailed(String),
+    #[error("Frame is {actual} bytes but the backend expects {expected}")]
+    SizeMismatch { expected: usize, actual: usize },
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Frame {index} is out of range ({frame_count} frames)")]
+    FrameOutOfRange { index: u64, frame_count: u64 },
+    #[error("Invalid dimensions {width}x{height}")]
+    InvalidDimensions { width: u32, height: u32 },
+    #[error("Renderer panicked: {0}")]
+    RenderPanicked(String),
+    #[error("Frame capture failed: {0}")]
+    CaptureFailed(String),
+    #[error("{frame:?} frames don't fit the {buffer:?} buffer")]
+    FormatMismatch {
+        frame: PixelFormat,
+        buffer: PixelFormat,
+    },
+    #[error("{operation} doesn't support {format:?} frames")]
+    UnsupportedFormat {
+        format: PixelFormat,
+        operation: &'static str,
+    },
+    #[error("Region {rect:?} exceeds the {width}x{height} frame")]
+    RegionOutOfBounds { rect: Rect, width: u32, height: u32 },
 }
 
 #[cfg(feature = "wasm-canvas-backend")]
@@ -0,0 +1,64 @@
+use crate::{PixelFormat, Renderer};
+
+/// `Renderer` that draws with a closure, for draw logic too small for its own type
+///
+/// `FnRenderer::new(PixelFormat::Rgba8, |frame, width, height, frame_no| ...)`
+pub struct FnRenderer<F> {
+    format: PixelFormat,
+    render: F,
+}
+
+impl<F: FnMut(&mut [u8], u32, u32, u64)> FnRenderer<F> {
+    /// Draw `format` frames with `render`, which gets the frame, its width and height,
+    /// and its frame number
+    pub fn new(format: PixelFormat, render: F) -> Self {
+        Self { format, render }
+    }
+}
+
+impl<F: FnMut(&mut [u8], u32, u32, u64)> Renderer for FnRenderer<F> {
+    fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    fn render(&mut self, frame: &mut [u8], width: u32, height: u32, frame_no: u64) {
+        (self.render)(frame, width, height, frame_no)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::{memory, MemoryBackend};
+    use crate::DisplayBridge;
+
+    #[test]
+    fn test_closure_renders_frames() {
+        let mut frame_numbers = Vec::new();
+        let mut renderer = FnRenderer::new(PixelFormat::Bgra8, |frame, width, height, frame_no| {
+            assert_eq!((frame.len(), width, height), (8, 2, 1));
+            frame_numbers.push(frame_no);
+        });
+        let backend = MemoryBackend::<memory::Rgba8>::new();
+        let mut bridge = DisplayBridge::for_renderer(backend, 2, 1, &renderer).unwrap();
+
+        for _ in 0..3 {
+            bridge.render_frame(&mut renderer).unwrap();
+        }
+        assert_eq!(frame_numbers, [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_format_of_other_size_is_an_error() {
+        let backend = MemoryBackend::<memory::Rgba8>::new();
+        let mut bridge = DisplayBridge::new(backend, 2, 1, PixelFormat::Rgba8).unwrap();
+        let mut renderer = FnRenderer::new(PixelFormat::Rgb8, |_: &mut [u8], _, _, _| {
+            panic!("rendered into a buffer of the wrong size")
+        });
+
+        let err = bridge.render_frame(&mut renderer).unwrap_err();
+
+        assert_eq!(err.to_string(), "Rgb8 frames don't fit the Rgba8 buffer");
+        assert_eq!(bridge.next_frame_no(), 0);
+    }
+}
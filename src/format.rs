@@ -1,21 +1,99 @@
+use std::ops::Range;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PixelFormat {
     /// 8-bit channels in R, G, B, A order.
     Rgba8,
-    /// 8-bit channels in premultiplied A, R, G, B order (P = Premultiplied Alpha).
+    /// 8-bit channels in A, R, G, B order.
+    ///
+    /// Despite the name (P = Premultiplied), this only describes the channel order:
+    /// conversions to and from it reorder bytes and never touch the alpha math. Use
+    /// `premultiply_rgba8` and `unpremultiply_rgba8` when the values must change.
     Prgb8,
+    /// 16-bit packed pixels with 5 bits red, 6 bits green and 5 bits blue, stored little-endian.
+    Rgb565,
+    /// 8-bit channels in R, G, B order with no alpha channel.
+    Rgb8,
+    /// 8-bit channels in B, G, R, A order, i.e. little-endian 0xAARRGGBB words.
+    Bgra8,
+    /// 32-bit packed pixels with 10 bits each of red, green and blue plus 2 bits of alpha,
+    /// stored little-endian with red in the lowest bits (A2B10G10R10).
+    A2Rgb10,
+    /// A single 8-bit luminance channel.
+    Gray8,
+    /// 8-bit channels in B, G, R order with no alpha channel.
+    Bgr8,
+    /// 8-bit channels in A, R, G, B order, the same layout as `Prgb8`.
+    Argb8,
+    /// 8-bit channels in A, B, G, R order, i.e. little-endian 0xRRGGBBAA words.
+    Abgr8,
+    /// Planar YUV 4:2:0: a full-resolution Y plane followed by `ceil(width / 2)` x
+    /// `ceil(height / 2)` U and V planes, 8 bits per sample.
+    I420,
+    /// 16-bit channels in R, G, B, A order, each a native-endian `u16`.
+    ///
+    /// Frames are usually produced in memory on the same machine, so they are read
+    /// with `u16::from_ne_bytes`; byte-swap data that comes from a fixed-endian file.
+    Rgba16,
+}
+
+/// Byte offsets of the channels within a pixel of a one-byte-per-channel format
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ChannelOrder {
+    /// Offsets of red, green and blue
+    pub(crate) rgb: [usize; 3],
+    /// Offset of alpha, `None` for formats without one
+    pub(crate) alpha: Option<usize>,
 }
 
 impl PixelFormat {
     /// Returns the number of bytes per pixel for this format.
+    ///
+    /// For planar formats this is the size of one luma sample; the chroma planes are
+    /// accounted for by `buffer_size`.
     #[inline]
     pub const fn bytes_per_pixel(self) -> usize {
         match self {
-            PixelFormat::Rgba8 | PixelFormat::Prgb8 => 4,
+            PixelFormat::Rgba8
+            | PixelFormat::Prgb8
+            | PixelFormat::Bgra8
+            | PixelFormat::Argb8
+            | PixelFormat::Abgr8
+            | PixelFormat::A2Rgb10 => 4,
+            PixelFormat::Rgba16 => 8,
+            PixelFormat::Rgb565 => 2,
+            PixelFormat::Rgb8 | PixelFormat::Bgr8 => 3,
+            PixelFormat::Gray8 | PixelFormat::I420 => 1,
         }
     }
 
+    /// Where each channel sits within a pixel, for formats with one byte per channel.
+    ///
+    /// Formats listed here convert between each other by permuting bytes, so adding
+    /// another byte-order variant only needs an entry here.
+    #[inline]
+    pub(crate) const fn channel_order(self) -> Option<ChannelOrder> {
+        let (rgb, alpha) = match self {
+            PixelFormat::Rgba8 => ([0, 1, 2], Some(3)),
+            PixelFormat::Bgra8 => ([2, 1, 0], Some(3)),
+            PixelFormat::Prgb8 | PixelFormat::Argb8 => ([1, 2, 3], Some(0)),
+            PixelFormat::Abgr8 => ([3, 2, 1], Some(0)),
+            PixelFormat::Rgb8 => ([0, 1, 2], None),
+            PixelFormat::Bgr8 => ([2, 1, 0], None),
+            _ => return None,
+        };
+        Some(ChannelOrder { rgb, alpha })
+    }
+
+    /// Whether the format stores its channels in separate planes rather than per pixel.
+    #[inline]
+    pub const fn is_planar(self) -> bool {
+        matches!(self, PixelFormat::I420)
+    }
+
     /// Calculates the stride (bytes per row) for the given width.
+    ///
+    /// For planar formats this is the stride of the luma plane.
     #[inline]
     pub const fn stride(self, width: u32) -> usize {
         width as usize * self.bytes_per_pixel()
@@ -24,7 +102,27 @@ impl PixelFormat {
     /// Calculates the total buffer size needed for the given dimensions.
     #[inline]
     pub const fn buffer_size(self, width: u32, height: u32) -> usize {
-        self.stride(width) * height as usize
+        let luma_size = self.stride(width) * height as usize;
+        match self {
+            PixelFormat::I420 => {
+                let chroma_size = (width as usize).div_ceil(2) * (height as usize).div_ceil(2);
+                luma_size + 2 * chroma_size
+            }
+            _ => luma_size,
+        }
+    }
+
+    /// Byte offset of pixel (`x`, `y`) in a buffer whose rows are `stride` bytes apart.
+    #[inline]
+    pub const fn pixel_offset(self, x: u32, y: u32, stride: usize) -> usize {
+        y as usize * stride + x as usize * self.bytes_per_pixel()
+    }
+
+    /// Byte range of the `width` pixels of row `y` in a buffer whose rows are `stride` bytes apart.
+    #[inline]
+    pub const fn row_range(self, y: u32, width: u32, stride: usize) -> Range<usize> {
+        let start = y as usize * stride;
+        start..start + self.stride(width)
     }
 }
 
@@ -36,17 +134,59 @@ mod tests {
     fn test_bytes_per_pixel() {
         assert_eq!(PixelFormat::Rgba8.bytes_per_pixel(), 4);
         assert_eq!(PixelFormat::Prgb8.bytes_per_pixel(), 4);
+        assert_eq!(PixelFormat::Rgb565.bytes_per_pixel(), 2);
+        assert_eq!(PixelFormat::Rgb8.bytes_per_pixel(), 3);
+        assert_eq!(PixelFormat::Bgra8.bytes_per_pixel(), 4);
+        assert_eq!(PixelFormat::A2Rgb10.bytes_per_pixel(), 4);
+        assert_eq!(PixelFormat::Gray8.bytes_per_pixel(), 1);
+        assert_eq!(PixelFormat::Rgba16.bytes_per_pixel(), 8);
     }
 
     #[test]
     fn test_stride() {
         assert_eq!(PixelFormat::Rgba8.stride(320), 1280);
         assert_eq!(PixelFormat::Prgb8.stride(100), 400);
+        assert_eq!(PixelFormat::Rgb565.stride(320), 640);
+        assert_eq!(PixelFormat::Rgb8.stride(320), 960);
+        assert_eq!(PixelFormat::Rgba16.stride(320), 2560);
     }
 
     #[test]
     fn test_buffer_size() {
         assert_eq!(PixelFormat::Rgba8.buffer_size(320, 200), 256_000);
         assert_eq!(PixelFormat::Prgb8.buffer_size(640, 480), 1_228_800);
+        assert_eq!(PixelFormat::Rgb565.buffer_size(320, 240), 153_600);
+        assert_eq!(PixelFormat::Rgb8.buffer_size(320, 240), 230_400);
+        assert_eq!(PixelFormat::Rgba16.buffer_size(320, 240), 614_400);
+    }
+
+    #[test]
+    fn test_subsampled_buffer_size() {
+        assert_eq!(PixelFormat::I420.buffer_size(320, 240), 115_200);
+        // Odd dimensions round the chroma planes up: 3x3 luma plus two 2x2 chroma planes
+        assert_eq!(PixelFormat::I420.buffer_size(3, 3), 17);
+        assert!(PixelFormat::I420.is_planar());
+        assert!(!PixelFormat::Rgba8.is_planar());
+    }
+
+    #[test]
+    fn test_pixel_offset_with_padded_stride() {
+        // 3 RGBA pixels per row padded to 16 bytes
+        let stride = 16;
+        assert_eq!(PixelFormat::Rgba8.pixel_offset(0, 0, stride), 0);
+        assert_eq!(PixelFormat::Rgba8.pixel_offset(1, 0, stride), 4);
+        assert_eq!(PixelFormat::Rgba8.pixel_offset(0, 1, stride), 16);
+        assert_eq!(PixelFormat::Rgb565.pixel_offset(1, 0, stride), 2);
+        assert_eq!(PixelFormat::Rgb565.pixel_offset(2, 3, stride), 52);
+    }
+
+    #[test]
+    fn test_row_range_with_padded_stride() {
+        assert_eq!(PixelFormat::Rgba8.row_range(0, 3, 16), 0..12);
+        assert_eq!(PixelFormat::Rgba8.row_range(2, 3, 16), 32..44);
+        assert_eq!(
+            PixelFormat::Rgba8.row_range(1, 3, PixelFormat::Rgba8.stride(3)),
+            12..24
+        );
     }
 }
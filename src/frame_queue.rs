@@ -1,10 +1,67 @@
 use std::collections::HashMap;
 
+/// Result of `FrameQueue::push_checked`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PushOutcome {
+    /// The frame was added to the queue.
+    Queued,
+    /// A frame with the same number and identical bytes is already queued.
+    Duplicate,
+    /// A frame with the same number but different bytes is already queued and was kept.
+    Conflict,
+    /// The frame was already displayed or the queue is full (and its overflow policy
+    /// rejects new frames).
+    Rejected,
+}
+
+/// What `FrameQueue` does with a new frame when it is full
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Keep the queued frames and reject the new one.
+    #[default]
+    RejectNew,
+    /// Evict the lowest-numbered queued frame to make room, skipping the sequence past
+    /// it. Suits live streams, where the freshest frames matter most.
+    DropOldest,
+}
+
 /// Stores frames keyed by their sequence number and yields them in order.
+///
+/// # Buffer reuse
+///
+/// The queue keeps a free list of frame buffers so a steady stream doesn't allocate
+/// per frame: take a buffer with `acquire`, fill it and `push` it, then `release` it
+/// once the frame popped from the queue has been presented. Frames the queue drops
+/// itself (rejected, evicted, replaced or cleared) go back on the free list too.
+///
+/// Buffers move in and out by value, so one can't be in the free list and in use at
+/// the same time. What can go wrong is stale data: `acquire` hands back bytes of an
+/// earlier frame, so overwrite the whole buffer before pushing it.
+///
+/// # Frame numbers
+///
+/// Frame numbers must increase: anything below `next_frame_number` counts as already
+/// shown and is rejected, so producers that wrap their IDs or restart numbering must
+/// `reset` or `rebase` the queue first. Once frame `u64::MAX` has been consumed the queue
+/// is exhausted and rejects every push until it is reset or rebased; long-running
+/// consumers can `rebase` periodically to keep numbers small.
 pub struct FrameQueue {
     next_frame: u64,
-    frames: HashMap<u64, Vec<u8>>,
+    /// Frame `u64::MAX` has been consumed, so no frame number is left to accept
+    exhausted: bool,
+    frames: HashMap<u64, QueuedFrame>,
     max_len: usize,
+    max_bytes: Option<usize>,
+    bytes_queued: usize,
+    overflow_policy: OverflowPolicy,
+    free_buffers: Vec<Vec<u8>>,
+}
+
+/// A frame waiting in a `FrameQueue`
+struct QueuedFrame {
+    data: Vec<u8>,
+    /// When to show the frame, for frames pushed with `push_with_pts`
+    pts_ms: Option<f64>,
 }
 
 impl FrameQueue {
@@ -13,34 +70,548 @@ impl FrameQueue {
 
         Self {
             next_frame: 0,
+            exhausted: false,
             frames: HashMap::new(),
             max_len,
+            max_bytes: None,
+            bytes_queued: 0,
+            overflow_policy: OverflowPolicy::RejectNew,
+            free_buffers: Vec::new(),
         }
     }
 
+    /// Also cap the total size of queued frames at `max_bytes`
+    ///
+    /// A frame that would push the total over the cap is handled like one arriving at a
+    /// full queue, according to the overflow policy.
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Choose what happens to new frames once `max_len` frames are queued
+    pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow_policy = policy;
+        self
+    }
+
+    /// Number of the next frame `pop_ready` returns
+    ///
+    /// Stays at `u64::MAX` once that frame has been consumed; see `is_exhausted`.
     pub fn next_frame_number(&self) -> u64 {
         self.next_frame
     }
 
+    /// Whether frame `u64::MAX` has been consumed, so every push is rejected
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Number of frames waiting in the queue, including ones blocked by gaps
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Total length of all queued frames in bytes
+    pub fn bytes_queued(&self) -> usize {
+        self.bytes_queued
+    }
+
+    /// Drop every queued frame, keeping the current position in the sequence
+    pub fn clear(&mut self) {
+        let frames: Vec<_> = self.frames.drain().map(|(_, frame)| frame.data).collect();
+        for frame in frames {
+            self.release(frame);
+        }
+        self.bytes_queued = 0;
+    }
+
+    /// Take a `size`-byte buffer from the free list, allocating only if it's empty
+    ///
+    /// A recycled buffer still holds an earlier frame's bytes.
+    pub fn acquire(&mut self, size: usize) -> Vec<u8> {
+        let mut buffer = self.free_buffers.pop().unwrap_or_default();
+        buffer.resize(size, 0);
+        buffer
+    }
+
+    /// Return a buffer, typically a presented frame, to the free list for `acquire`
+    ///
+    /// The free list holds at most `max_len` buffers; extra ones are dropped.
+    pub fn release(&mut self, buffer: Vec<u8>) {
+        if self.free_buffers.len() < self.max_len {
+            self.free_buffers.push(buffer);
+        }
+    }
+
+    /// Drop every queued frame and continue the sequence at `new_next_frame`
+    ///
+    /// Use after seeking, when frames before the current position become valid again.
+    pub fn reset(&mut self, new_next_frame: u64) {
+        self.clear();
+        self.next_frame = new_next_frame;
+        self.exhausted = false;
+    }
+
+    /// Subtract `offset` from `next_frame_number` and every queued frame number
+    ///
+    /// Queued frames stay in order and ready to pop; producers continue with numbers
+    /// `offset` lower than before. An `offset` past `next_frame_number` is clamped to it,
+    /// so the sequence continues at frame 0.
+    pub fn rebase(&mut self, offset: u64) {
+        let offset = if self.exhausted {
+            offset
+        } else {
+            offset.min(self.next_frame)
+        };
+
+        if self.exhausted && offset > 0 {
+            // The position was one past u64::MAX
+            self.next_frame = u64::MAX - offset + 1;
+            self.exhausted = false;
+        } else {
+            self.next_frame -= offset;
+        }
+        self.frames = self
+            .frames
+            .drain()
+            .map(|(frame_no, frame)| (frame_no - offset, frame))
+            .collect();
+    }
+
+    /// Lowest queued frame number, which is the next frame to show unless there's a gap
+    pub fn oldest_pending(&self) -> Option<u64> {
+        self.frames.keys().min().copied()
+    }
+
+    /// Whether `frame_no` is queued and not yet popped
+    pub fn contains(&self, frame_no: u64) -> bool {
+        self.frames.contains_key(&frame_no)
+    }
+
+    /// Earliest PTS among the queued frames, i.e. when `pop_due` will next return one
+    pub fn next_pts_ms(&self) -> Option<f64> {
+        self.frames
+            .values()
+            .filter_map(|frame| frame.pts_ms)
+            .min_by(f64::total_cmp)
+    }
+
     pub fn push(&mut self, frame_no: u64, frame: Vec<u8>) -> bool {
-        if frame_no < self.next_frame {
+        self.push_frame(frame_no, None, frame)
+    }
+
+    /// Push a frame that should be shown once the clock reaches `pts_ms`
+    ///
+    /// `frame_no` only orders the frames and may skip numbers. Take these frames out
+    /// with `pop_due`, which follows the timestamps instead of waiting for gaps.
+    pub fn push_with_pts(&mut self, frame_no: u64, pts_ms: f64, frame: Vec<u8>) -> bool {
+        self.push_frame(frame_no, Some(pts_ms), frame)
+    }
+
+    fn push_frame(&mut self, frame_no: u64, pts_ms: Option<f64>, data: Vec<u8>) -> bool {
+        if self.is_consumed(frame_no) || !self.make_room(frame_no, data.len()) {
+            self.release(data);
             return false;
         }
 
-        if self.frames.len() >= self.max_len && !self.frames.contains_key(&frame_no) {
+        self.bytes_queued += data.len();
+        if let Some(replaced) = self.frames.insert(frame_no, QueuedFrame { data, pts_ms }) {
+            self.bytes_queued -= replaced.data.len();
+            self.release(replaced.data);
+        }
+        true
+    }
+
+    /// Ensure `len` bytes stored as `frame_no` fit, evicting per the overflow policy
+    ///
+    /// Returns `false` if the frame must be rejected.
+    fn make_room(&mut self, frame_no: u64, len: usize) -> bool {
+        if self.max_bytes.is_some_and(|max_bytes| len > max_bytes) {
             return false;
         }
 
-        self.frames.insert(frame_no, frame);
-        true
+        loop {
+            let replaced = self.frames.get(&frame_no).map(|frame| frame.data.len());
+            let count_fits = replaced.is_some() || self.frames.len() < self.max_len;
+            let bytes_fit = self.max_bytes.is_none_or(|max_bytes| {
+                self.bytes_queued - replaced.unwrap_or(0) + len <= max_bytes
+            });
+            if count_fits && bytes_fit {
+                return true;
+            }
+
+            match (self.overflow_policy, self.oldest_pending()) {
+                // A frame older than everything queued would be the one evicted
+                (OverflowPolicy::DropOldest, Some(oldest)) if oldest < frame_no => {
+                    if let Some(evicted) = self.remove(oldest) {
+                        self.release(evicted);
+                    }
+                    self.next_frame = self.next_frame.max(oldest + 1);
+                }
+                _ => return false,
+            }
+        }
     }
 
+    /// Whether `frame_no` was already shown or skipped
+    fn is_consumed(&self, frame_no: u64) -> bool {
+        self.exhausted || frame_no < self.next_frame
+    }
+
+    /// Continue the sequence after `frame_no`
+    fn advance_past(&mut self, frame_no: u64) {
+        match frame_no.checked_add(1) {
+            Some(next_frame) => self.next_frame = next_frame,
+            None => self.exhausted = true,
+        }
+    }
+
+    fn remove(&mut self, frame_no: u64) -> Option<Vec<u8>> {
+        let frame = self.frames.remove(&frame_no)?;
+        self.bytes_queued -= frame.data.len();
+        Some(frame.data)
+    }
+
+    /// Push a frame, comparing against any queued frame with the same number
+    ///
+    /// Unlike `push`, an already queued frame is never replaced, so a `Conflict`
+    /// keeps the first delivery and signals transport corruption to the caller.
+    pub fn push_checked(&mut self, frame_no: u64, frame: Vec<u8>) -> PushOutcome {
+        let outcome = match self.frames.get(&frame_no) {
+            _ if self.is_consumed(frame_no) => PushOutcome::Rejected,
+            Some(existing) if existing.data == frame => PushOutcome::Duplicate,
+            Some(_) => PushOutcome::Conflict,
+            None => PushOutcome::Queued,
+        };
+        if outcome == PushOutcome::Queued && self.make_room(frame_no, frame.len()) {
+            self.bytes_queued += frame.len();
+            let queued = QueuedFrame {
+                data: frame,
+                pts_ms: None,
+            };
+            self.frames.insert(frame_no, queued);
+            return PushOutcome::Queued;
+        }
+
+        self.release(frame);
+        if outcome == PushOutcome::Queued {
+            // No room was made for it
+            return PushOutcome::Rejected;
+        }
+        outcome
+    }
+
+    /// Pop frame `next_frame_number`, if it has arrived
     pub fn pop_ready(&mut self) -> Option<Vec<u8>> {
-        if let Some(frame) = self.frames.remove(&self.next_frame) {
-            self.next_frame += 1;
-            Some(frame)
-        } else {
-            None
+        self.pop_ready_numbered().map(|(_, frame)| frame)
+    }
+
+    /// Like `pop_ready`, also returning the popped frame's number
+    pub fn pop_ready_numbered(&mut self) -> Option<(u64, Vec<u8>)> {
+        if self.exhausted {
+            return None;
         }
+
+        let frame_no = self.next_frame;
+        let frame = self.remove(frame_no)?;
+        self.advance_past(frame_no);
+        Some((frame_no, frame))
+    }
+
+    /// Pop the newest frame whose PTS is at or before `now_ms`
+    ///
+    /// Gaps in the numbering are skipped and earlier frames are dropped, so a presenter
+    /// that falls behind catches up instead of showing every late frame. Frames pushed
+    /// without a PTS count as always due. Returns `None` if nothing is due yet.
+    pub fn pop_due(&mut self, now_ms: f64) -> Option<Vec<u8>> {
+        if self.exhausted {
+            return None;
+        }
+
+        let due = self
+            .frames
+            .iter()
+            .filter(|(_, frame)| frame.pts_ms.is_none_or(|pts_ms| pts_ms <= now_ms))
+            .map(|(&frame_no, _)| frame_no)
+            .max()?;
+
+        let skipped: Vec<u64> = self.frames.keys().copied().filter(|&n| n < due).collect();
+        for frame_no in skipped {
+            if let Some(frame) = self.remove(frame_no) {
+                self.release(frame);
+            }
+        }
+        self.advance_past(due);
+        self.remove(due)
+    }
+
+    /// Skip to the newest frame reachable without a gap and return it
+    ///
+    /// Frames between `next_frame` and that one are dropped. Returns `None` if
+    /// `next_frame` itself hasn't arrived, like `pop_ready`.
+    pub fn pop_latest(&mut self) -> Option<Vec<u8>> {
+        let mut latest = self.pop_ready()?;
+        while let Some(frame) = self.pop_ready() {
+            let skipped = std::mem::replace(&mut latest, frame);
+            self.release(skipped);
+        }
+        Some(latest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_checked_queues_new_frame() {
+        let mut queue = FrameQueue::new(4);
+        assert_eq!(queue.push_checked(0, vec![1, 2, 3]), PushOutcome::Queued);
+        assert_eq!(queue.pop_ready(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_push_checked_detects_duplicate() {
+        let mut queue = FrameQueue::new(4);
+        queue.push_checked(0, vec![1, 2, 3]);
+        assert_eq!(queue.push_checked(0, vec![1, 2, 3]), PushOutcome::Duplicate);
+        assert_eq!(queue.pop_ready(), Some(vec![1, 2, 3]));
+        assert_eq!(queue.pop_ready(), None);
+    }
+
+    #[test]
+    fn test_push_checked_detects_conflict_and_keeps_first() {
+        let mut queue = FrameQueue::new(4);
+        queue.push_checked(0, vec![1, 2, 3]);
+        assert_eq!(queue.push_checked(0, vec![1, 2, 4]), PushOutcome::Conflict);
+        assert_eq!(queue.pop_ready(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_push_checked_rejects_displayed_and_overflow() {
+        let mut queue = FrameQueue::new(1);
+        queue.push_checked(0, vec![0]);
+        queue.pop_ready();
+        assert_eq!(queue.push_checked(0, vec![0]), PushOutcome::Rejected);
+
+        assert_eq!(queue.push_checked(1, vec![1]), PushOutcome::Queued);
+        assert_eq!(queue.push_checked(2, vec![2]), PushOutcome::Rejected);
+        // A full queue still reports duplicates of frames it holds
+        assert_eq!(queue.push_checked(1, vec![1]), PushOutcome::Duplicate);
+    }
+
+    #[test]
+    fn test_contains() {
+        let mut queue = FrameQueue::new(4);
+        queue.push(2, vec![2]);
+
+        assert!(queue.contains(2));
+        assert!(!queue.contains(0));
+        assert!(!queue.contains(3));
+    }
+
+    #[test]
+    fn test_len_and_oldest_pending() {
+        let mut queue = FrameQueue::new(4);
+        assert!(queue.is_empty());
+        assert_eq!(queue.oldest_pending(), None);
+
+        queue.push(3, vec![3]);
+        queue.push(1, vec![1]);
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.oldest_pending(), Some(1));
+    }
+
+    #[test]
+    fn test_reset_accepts_earlier_frames() {
+        let mut queue = FrameQueue::new(4);
+        queue.push(0, vec![0]);
+        queue.pop_ready();
+        queue.push(5, vec![5]);
+        assert!(!queue.push(0, vec![0]));
+
+        queue.reset(0);
+        assert!(queue.is_empty());
+        assert!(queue.push(0, vec![0]));
+        assert_eq!(queue.pop_ready(), Some(vec![0]));
+    }
+
+    #[test]
+    fn test_clear_keeps_position() {
+        let mut queue = FrameQueue::new(4);
+        queue.push(0, vec![0]);
+        queue.pop_ready();
+        queue.push(1, vec![1]);
+        queue.push(2, vec![2]);
+
+        queue.clear();
+        assert_eq!(queue.len(), 0);
+        assert_eq!(queue.next_frame_number(), 1);
+    }
+
+    #[test]
+    fn test_pop_latest_skips_to_end_of_contiguous_run() {
+        let mut queue = FrameQueue::new(8);
+        for frame_no in [0, 1, 2, 4] {
+            queue.push(frame_no, vec![frame_no as u8]);
+        }
+
+        assert_eq!(queue.pop_latest(), Some(vec![2]));
+        assert_eq!(queue.next_frame_number(), 3);
+        // Frame 3 is missing, so the queued frame 4 is out of reach
+        assert_eq!(queue.pop_latest(), None);
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn test_drop_oldest_keeps_freshest_frames() {
+        let mut queue = FrameQueue::new(2).with_overflow_policy(OverflowPolicy::DropOldest);
+        assert!(queue.push(1, vec![1]));
+        assert!(queue.push(2, vec![2]));
+        assert!(queue.push(3, vec![3]));
+
+        // Frame 1 was evicted, so the sequence continues after it
+        assert_eq!(queue.next_frame_number(), 2);
+        assert_eq!(queue.pop_latest(), Some(vec![3]));
+
+        // Frames older than everything queued are still rejected
+        queue.push(5, vec![5]);
+        queue.push(6, vec![6]);
+        assert_eq!(queue.push_checked(4, vec![4]), PushOutcome::Rejected);
+        assert_eq!(queue.push_checked(7, vec![7]), PushOutcome::Queued);
+        assert!(!queue.contains(5));
+    }
+
+    #[test]
+    fn test_max_bytes_bounds_queued_frames() {
+        let mut queue = FrameQueue::new(60).with_max_bytes(8);
+        assert!(queue.push(0, vec![0; 4]));
+        assert!(queue.push(1, vec![1; 4]));
+        assert_eq!(queue.bytes_queued(), 8);
+        assert!(!queue.push(2, vec![2; 4]));
+        // Replacing a queued frame only counts the difference
+        assert!(queue.push(1, vec![1; 3]));
+        assert_eq!(queue.bytes_queued(), 7);
+
+        queue.pop_ready();
+        assert_eq!(queue.bytes_queued(), 3);
+
+        let mut queue = FrameQueue::new(60)
+            .with_max_bytes(8)
+            .with_overflow_policy(OverflowPolicy::DropOldest);
+        queue.push(0, vec![0; 4]);
+        queue.push(1, vec![1; 4]);
+        assert!(queue.push(2, vec![2; 6]));
+        assert_eq!(queue.oldest_pending(), Some(2));
+        assert_eq!(queue.bytes_queued(), 6);
+        assert!(!queue.push(3, vec![3; 9]));
+    }
+
+    #[test]
+    fn test_released_buffers_are_reused() {
+        let mut queue = FrameQueue::new(2);
+        let mut buffer = queue.acquire(4);
+        buffer.copy_from_slice(&[1, 2, 3, 4]);
+        let allocation = buffer.as_ptr();
+        queue.push(0, buffer);
+
+        let presented = queue.pop_ready().unwrap();
+        queue.release(presented);
+        let reused = queue.acquire(4);
+        assert_eq!(reused.as_ptr(), allocation);
+        // Recycled buffers keep their old bytes
+        assert_eq!(reused, [1, 2, 3, 4]);
+
+        // Frames the queue drops itself are recycled too
+        queue.push(1, vec![0; 8]);
+        queue.push(2, vec![0; 8]);
+        assert!(!queue.push(3, reused));
+        let recycled = queue.acquire(4);
+        assert_eq!(recycled.as_ptr(), allocation);
+    }
+
+    #[test]
+    fn test_pop_due_follows_pts() {
+        let mut queue = FrameQueue::new(4);
+        // Sparse numbering, 40ms apart
+        queue.push_with_pts(10, 0.0, vec![10]);
+        queue.push_with_pts(12, 40.0, vec![12]);
+        queue.push_with_pts(15, 80.0, vec![15]);
+        assert_eq!(queue.next_pts_ms(), Some(0.0));
+
+        assert_eq!(queue.pop_due(10.0), Some(vec![10]));
+        assert_eq!(queue.pop_due(30.0), None);
+        assert_eq!(queue.next_pts_ms(), Some(40.0));
+
+        // Running late skips straight to the newest due frame
+        assert_eq!(queue.pop_due(90.0), Some(vec![15]));
+        assert_eq!(queue.next_frame_number(), 16);
+        assert!(queue.is_empty());
+        assert!(!queue.push_with_pts(14, 100.0, vec![14]));
+    }
+
+    #[test]
+    fn test_rebase_near_u64_max() {
+        let mut queue = FrameQueue::new(4);
+        queue.reset(u64::MAX - 2);
+        for frame_no in u64::MAX - 2..=u64::MAX {
+            assert!(queue.push(frame_no, vec![(u64::MAX - frame_no) as u8]));
+        }
+        assert_eq!(queue.pop_ready(), Some(vec![2]));
+
+        queue.rebase(u64::MAX - 10);
+        assert_eq!(queue.next_frame_number(), 9);
+        assert!(queue.contains(10));
+        assert!(queue.push(11, vec![9]));
+
+        assert_eq!(queue.pop_latest(), Some(vec![9]));
+        assert_eq!(queue.next_frame_number(), 12);
+    }
+
+    #[test]
+    fn test_popping_u64_max_exhausts_queue() {
+        let mut queue = FrameQueue::new(1);
+        queue.reset(u64::MAX);
+        queue.push(u64::MAX, vec![1]);
+
+        assert_eq!(queue.pop_ready_numbered(), Some((u64::MAX, vec![1])));
+        assert!(queue.is_exhausted());
+        assert!(!queue.push(u64::MAX, vec![1]));
+        assert_eq!(queue.push_checked(u64::MAX, vec![1]), PushOutcome::Rejected);
+        assert_eq!(queue.pop_ready(), None);
+        assert_eq!(queue.pop_due(f64::INFINITY), None);
+
+        queue.rebase(1);
+        assert!(!queue.is_exhausted());
+        assert_eq!(queue.next_frame_number(), u64::MAX);
+        assert!(queue.push(u64::MAX, vec![2]));
+    }
+
+    #[test]
+    fn test_pop_ready_numbered_returns_frame_number() {
+        let mut queue = FrameQueue::new(4);
+        queue.push(1, vec![1]);
+        queue.push(0, vec![0]);
+
+        assert_eq!(queue.pop_ready_numbered(), Some((0, vec![0])));
+        assert_eq!(queue.pop_ready_numbered(), Some((1, vec![1])));
+        assert_eq!(queue.pop_ready_numbered(), None);
+    }
+
+    #[test]
+    fn test_rebase_past_next_frame_restarts_at_zero() {
+        let mut queue = FrameQueue::new(4);
+        queue.reset(3);
+        queue.push(4, vec![4]);
+
+        queue.rebase(10);
+        assert_eq!(queue.next_frame_number(), 0);
+        assert!(queue.contains(1));
     }
 }
@@ -0,0 +1,192 @@
+use crate::FrameQueue;
+use std::ops::Range;
+
+/// Decides which frame numbers to request from render workers.
+///
+/// Keeps at most `max_in_flight` frames requested or queued ahead of the
+/// queue's next displayable frame.
+pub struct FrameScheduler {
+    next_request: u64,
+    max_in_flight: usize,
+    rate_margin: f64,
+    request_credit: f64,
+    last_request_ms: Option<f64>,
+}
+
+impl FrameScheduler {
+    pub fn new(max_in_flight: usize) -> Self {
+        assert!(max_in_flight > 0, "max_in_flight must be greater than 0");
+
+        Self {
+            next_request: 0,
+            max_in_flight,
+            rate_margin: 0.25,
+            request_credit: 0.0,
+            last_request_ms: None,
+        }
+    }
+
+    /// Configure how far above the display rate the rate-limited scheduler may request
+    ///
+    /// A margin of `0.25` requests frames at 125% of the measured display fps.
+    pub fn with_rate_margin(mut self, margin: f64) -> Self {
+        self.rate_margin = margin.max(0.0);
+        self
+    }
+
+    pub fn next_request_frame(&self) -> u64 {
+        self.next_request
+    }
+
+    /// Frame numbers to request so the pipeline holds `max_in_flight` frames
+    pub fn frames_to_request(&mut self, queue: &FrameQueue) -> Range<u64> {
+        let count = self.free_slots(queue);
+        self.take(count)
+    }
+
+    /// Frame numbers to request, paced to the measured display rate
+    ///
+    /// Requests accrue at `display_fps` plus the rate margin and are still capped by
+    /// `max_in_flight`. The first call, and any call before the display rate is known
+    /// (`display_fps <= 0`), fills the pipeline like `frames_to_request`.
+    pub fn frames_to_request_rate_limited(
+        &mut self,
+        queue: &FrameQueue,
+        display_fps: f64,
+        now_ms: f64,
+    ) -> Range<u64> {
+        let max_credit = self.max_in_flight as f64;
+        let last_request_ms = self.last_request_ms.replace(now_ms);
+
+        self.request_credit = match last_request_ms {
+            Some(last_ms) if display_fps > 0.0 => {
+                let elapsed_s = (now_ms - last_ms).max(0.0) / 1000.0;
+                let accrued = elapsed_s * display_fps * (1.0 + self.rate_margin);
+                (self.request_credit + accrued).min(max_credit)
+            }
+            _ => max_credit,
+        };
+
+        let count = self.free_slots(queue).min(self.request_credit as u64);
+        self.request_credit -= count as f64;
+        self.take(count)
+    }
+
+    /// Hand back the end of the last requested range, e.g. frames whose request failed
+    ///
+    /// The next call returns them again, so the sequence has no gaps. `frames` must end
+    /// where the last range did.
+    pub fn cancel(&mut self, frames: Range<u64>) {
+        assert_eq!(
+            frames.end, self.next_request,
+            "only the end of the last requested range can be cancelled"
+        );
+        let count = frames.end.saturating_sub(frames.start);
+        self.request_credit = (self.request_credit + count as f64).min(self.max_in_flight as f64);
+        self.next_request -= count;
+    }
+
+    fn free_slots(&self, queue: &FrameQueue) -> u64 {
+        let in_flight = self.next_request.saturating_sub(queue.next_frame_number());
+        (self.max_in_flight as u64).saturating_sub(in_flight)
+    }
+
+    fn take(&mut self, count: u64) -> Range<u64> {
+        let start = self.next_request;
+        self.next_request += count;
+        start..self.next_request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fills_up_to_max_in_flight() {
+        let queue = FrameQueue::new(10);
+        let mut scheduler = FrameScheduler::new(4);
+
+        assert_eq!(scheduler.frames_to_request(&queue), 0..4);
+        assert_eq!(scheduler.frames_to_request(&queue), 4..4);
+    }
+
+    #[test]
+    fn test_requests_more_as_frames_are_consumed() {
+        let mut queue = FrameQueue::new(10);
+        let mut scheduler = FrameScheduler::new(4);
+
+        let requested = scheduler.frames_to_request(&queue);
+        for frame_no in requested {
+            queue.push(frame_no, vec![0]);
+        }
+        queue.pop_ready();
+        queue.pop_ready();
+
+        assert_eq!(scheduler.frames_to_request(&queue), 4..6);
+    }
+
+    #[test]
+    fn test_rate_limited_without_fps_fills_pipeline() {
+        let queue = FrameQueue::new(10);
+        let mut scheduler = FrameScheduler::new(4);
+
+        assert_eq!(
+            scheduler.frames_to_request_rate_limited(&queue, 0.0, 0.0),
+            0..4
+        );
+    }
+
+    #[test]
+    fn test_cancelled_frames_are_requested_again() {
+        let queue = FrameQueue::new(10);
+        let mut scheduler = FrameScheduler::new(4);
+
+        let requested = scheduler.frames_to_request_rate_limited(&queue, 60.0, 0.0);
+        assert_eq!(requested, 0..4);
+        scheduler.cancel(2..4);
+
+        assert_eq!(
+            scheduler.frames_to_request_rate_limited(&queue, 60.0, 0.0),
+            2..4
+        );
+    }
+
+    /// Simulates one second of a display loop ticking at 60Hz that only manages to
+    /// show 10 frames per second, with workers that deliver every request instantly.
+    fn simulate_slow_display(rate_limited: bool) -> u64 {
+        let mut queue = FrameQueue::new(20);
+        let mut scheduler = FrameScheduler::new(20);
+        let display_fps = 10.0;
+
+        for tick in 0..60 {
+            let now_ms = tick as f64 * 1000.0 / 60.0;
+            let requested = if rate_limited {
+                scheduler.frames_to_request_rate_limited(&queue, display_fps, now_ms)
+            } else {
+                scheduler.frames_to_request(&queue)
+            };
+            for frame_no in requested {
+                queue.push(frame_no, vec![0]);
+            }
+
+            // Every tick drains a frame, but most of them are skipped by the fps limit
+            queue.pop_ready();
+        }
+
+        scheduler.next_request_frame()
+    }
+
+    #[test]
+    fn test_slow_display_issues_fewer_requests() {
+        let unlimited = simulate_slow_display(false);
+        let rate_limited = simulate_slow_display(true);
+
+        assert_eq!(unlimited, 79);
+        assert!(
+            rate_limited < 40,
+            "expected fewer requests, got {}",
+            rate_limited
+        );
+    }
+}
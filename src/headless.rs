@@ -0,0 +1,42 @@
+use crate::{DisplayBackend, DisplayBridge, Renderer, VideoBufferError};
+use std::time::{Duration, Instant};
+
+/// Render and present `frame_count` frames through a `DisplayBridge` as fast as possible
+///
+/// Returns the time spent, so the whole render, convert and present pipeline can be
+/// measured without a window, e.g. against a `MemoryBackend`. Backend setup is not timed.
+pub fn run_headless<R: Renderer, B: DisplayBackend>(
+    renderer: &mut R,
+    backend: B,
+    width: u32,
+    height: u32,
+    frame_count: u64,
+) -> Result<Duration, VideoBufferError> {
+    let mut bridge = DisplayBridge::for_renderer(backend, width, height, renderer)?;
+
+    let start = Instant::now();
+    for _ in 0..frame_count {
+        bridge.render_frame(renderer)?;
+    }
+    Ok(start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::MemoryBackend;
+    use crate::{FnRenderer, PixelFormat};
+
+    #[test]
+    fn test_runs_every_frame() {
+        let mut frames = 0;
+        let mut renderer = FnRenderer::new(PixelFormat::Prgb8, |frame, _, _, _| {
+            frame.fill(255);
+            frames += 1;
+        });
+
+        run_headless(&mut renderer, <MemoryBackend>::new(), 4, 2, 5).unwrap();
+
+        assert_eq!(frames, 5);
+    }
+}
@@ -1,16 +1,73 @@
+mod accumulate;
 mod bridge;
 mod buffer;
+mod clock;
+mod compositing;
 mod convert;
+mod diagnostics;
+mod double_buffer;
+mod dynamic_resolution;
 mod error;
+mod fn_renderer;
 mod format;
 mod frame_queue;
+mod frame_scheduler;
+#[cfg(not(target_arch = "wasm32"))]
+mod headless;
+mod pixel_buffer;
+mod raw_file;
+mod rect;
+mod render_thread;
+mod scale;
+mod simd;
+mod stereo;
+#[cfg(feature = "futures")]
+mod stream;
+mod tile;
+mod trace;
 mod traits;
+mod transition;
+mod viewport;
+mod yuv;
 
 pub mod backends;
 
-pub use bridge::{DisplayBridge, DisplayPresenter};
-pub use buffer::TripleBuffer;
+pub use accumulate::Accumulator;
+pub use bridge::{
+    DisplayBridge, DisplayPresenter, DisplayPresenterBuilder, PresentCallback, PresentOutcome,
+    PresentTap, SkipPolicy,
+};
+pub use buffer::{TripleBuffer, TypedTripleBuffer};
+pub use clock::Clock;
+#[cfg(feature = "wasm-canvas-backend")]
+pub use clock::DateClock;
+#[cfg(not(target_arch = "wasm32"))]
+pub use clock::InstantClock;
+pub use compositing::blit_rgba;
+pub use convert::{
+    convert_premultiplied_argb_to_straight_rgba, convert_prgb_to_rgba, convert_region,
+    convert_rgb8_to_rgba8, convert_rgba16_to_rgba8, convert_rgba8_to_rgb8, convert_rgba_to_prgb,
+    convert_strided, convert_swizzle, premultiply_rgba8, unpremultiply_rgba8, Swizzle, ToneMapping,
+};
+pub use diagnostics::{BufferSnapshot, PipelineSnapshot, PresenterSnapshot, QueueSnapshot};
+pub use double_buffer::DoubleBuffer;
+pub use dynamic_resolution::{DynamicResolution, ResolutionScale};
 pub use error::VideoBufferError;
+pub use fn_renderer::FnRenderer;
 pub use format::PixelFormat;
-pub use frame_queue::FrameQueue;
-pub use traits::{DisplayBackend, Renderer};
+pub use frame_queue::{FrameQueue, OverflowPolicy, PushOutcome};
+pub use frame_scheduler::FrameScheduler;
+#[cfg(not(target_arch = "wasm32"))]
+pub use headless::run_headless;
+pub use pixel_buffer::PixelBufferMut;
+pub use raw_file::RawFrameFile;
+pub use rect::Rect;
+pub use render_thread::RenderThread;
+pub use scale::{scale_frame, ScaleMode};
+pub use stereo::StereoLayout;
+#[cfg(feature = "futures")]
+pub use stream::present_stream;
+pub use tile::TileGrid;
+pub use trace::TraceRecorder;
+pub use traits::{DisplayBackend, Renderer, SwapBuffer};
+pub use yuv::{yuv420_to_rgba8, ChromaUpsampling};
@@ -0,0 +1,159 @@
+use crate::PixelFormat;
+use std::ops::DerefMut;
+
+/// Mutable pixel-addressed view of a frame, so callers don't compute byte offsets
+///
+/// Wraps anything that derefs to the frame bytes, e.g. a `&mut [u8]` or the guard
+/// returned by `TripleBuffer::render_view`. Only formats with one byte per channel are
+/// supported.
+pub struct PixelBufferMut<D> {
+    data: D,
+    width: u32,
+    height: u32,
+    stride: usize,
+    format: PixelFormat,
+}
+
+impl<D> PixelBufferMut<D>
+where
+    D: DerefMut,
+    D::Target: AsRef<[u8]> + AsMut<[u8]>,
+{
+    /// View a tightly packed `width` x `height` frame
+    pub fn new(data: D, width: u32, height: u32, format: PixelFormat) -> Self {
+        Self::with_stride(data, width, height, format.stride(width), format)
+    }
+
+    /// View a frame whose rows are `stride` bytes apart
+    pub fn with_stride(
+        data: D,
+        width: u32,
+        height: u32,
+        stride: usize,
+        format: PixelFormat,
+    ) -> Self {
+        assert!(
+            format.channel_order().is_some(),
+            "pixel views of {:?} are not supported",
+            format
+        );
+        assert!(
+            stride >= format.stride(width),
+            "stride is smaller than a row"
+        );
+        assert!(
+            data.as_ref().len() >= stride * height as usize,
+            "buffer is too small for a {}x{} frame",
+            width,
+            height
+        );
+
+        Self {
+            data,
+            width,
+            height,
+            stride,
+            format,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    /// Bytes of pixel (`x`, `y`), in the view's format
+    pub fn pixel(&self, x: u32, y: u32) -> &[u8] {
+        let start = self.offset(x, y);
+        &self.data.as_ref()[start..start + self.format.bytes_per_pixel()]
+    }
+
+    /// Bytes of pixel (`x`, `y`) of a 4-byte format, in the view's channel order
+    pub fn pixel_mut(&mut self, x: u32, y: u32) -> &mut [u8; 4] {
+        assert_eq!(
+            self.format.bytes_per_pixel(),
+            4,
+            "{:?} pixels are not 4 bytes",
+            self.format
+        );
+        let start = self.offset(x, y);
+        let bytes = &mut self.data.as_mut()[start..start + 4];
+        bytes.try_into().unwrap()
+    }
+
+    /// Write an RGBA color to pixel (`x`, `y`), reordered for the view's format
+    ///
+    /// Alpha is dropped for formats without an alpha channel.
+    pub fn set_rgba(&mut self, x: u32, y: u32, r: u8, g: u8, b: u8, a: u8) {
+        let Some(order) = self.format.channel_order() else {
+            unreachable!("checked in the constructor");
+        };
+        let start = self.offset(x, y);
+        let pixel = &mut self.data.as_mut()[start..start + self.format.bytes_per_pixel()];
+
+        for (&offset, value) in order.rgb.iter().zip([r, g, b]) {
+            pixel[offset] = value;
+        }
+        if let Some(alpha) = order.alpha {
+            pixel[alpha] = a;
+        }
+    }
+
+    /// Consume the view, returning the wrapped data
+    pub fn into_inner(self) -> D {
+        self.data
+    }
+
+    fn offset(&self, x: u32, y: u32) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "pixel ({}, {}) is outside the {}x{} frame",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        self.format.pixel_offset(x, y, self.stride)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_rgba_follows_channel_order() {
+        let mut data = [0u8; 8];
+        let mut view = PixelBufferMut::new(&mut data[..], 2, 1, PixelFormat::Bgra8);
+
+        view.set_rgba(1, 0, 1, 2, 3, 4);
+        view.pixel_mut(0, 0)[3] = 255;
+
+        assert_eq!(view.pixel(1, 0), &[3, 2, 1, 4]);
+        assert_eq!(data, [0, 0, 0, 255, 3, 2, 1, 4]);
+    }
+
+    #[test]
+    fn test_padded_rows() {
+        let mut data = [0u8; 8];
+        let mut view = PixelBufferMut::with_stride(&mut data[..], 1, 2, 4, PixelFormat::Rgb8);
+
+        view.set_rgba(0, 1, 7, 8, 9, 255);
+
+        assert_eq!(data, [0, 0, 0, 0, 7, 8, 9, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside")]
+    fn test_out_of_bounds_pixel() {
+        let mut data = [0u8; 4];
+        PixelBufferMut::new(&mut data[..], 1, 1, PixelFormat::Rgba8).pixel_mut(1, 0);
+    }
+}
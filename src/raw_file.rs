@@ -0,0 +1,163 @@
+use crate::{DisplayBackend, DisplayPresenter, PixelFormat, VideoBufferError};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// A file of headerless frames stored back to back, all the same size and format
+///
+/// Frames are read on demand, so seeking to any frame costs a single seek and read.
+pub struct RawFrameFile {
+    file: File,
+    width: u32,
+    height: u32,
+    format: PixelFormat,
+    frame_count: u64,
+}
+
+impl RawFrameFile {
+    /// Open a raw frame file; trailing bytes that don't form a whole frame are ignored
+    pub fn open(
+        path: impl AsRef<Path>,
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+    ) -> Result<Self, VideoBufferError> {
+        let file = File::open(path)?;
+        let frame_size = format.buffer_size(width, height) as u64;
+        let frame_count = match frame_size {
+            0 => 0,
+            _ => file.metadata()?.len() / frame_size,
+        };
+
+        Ok(Self {
+            file,
+            width,
+            height,
+            format,
+            frame_count,
+        })
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    /// Read frame `index` into `frame`, resizing it to one frame
+    pub fn read_frame(&self, index: u64, frame: &mut Vec<u8>) -> Result<(), VideoBufferError> {
+        if index >= self.frame_count {
+            return Err(VideoBufferError::FrameOutOfRange {
+                index,
+                frame_count: self.frame_count,
+            });
+        }
+
+        let frame_size = self.format.buffer_size(self.width, self.height);
+        frame.resize(frame_size, 0);
+
+        // `&File` implements Read + Seek, so seeking doesn't need `&mut self`
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start(index * frame_size as u64))?;
+        file.read_exact(frame)?;
+        Ok(())
+    }
+
+    /// Seek to frame `index` and present it immediately, ignoring the frame rate limit
+    pub fn present_frame<B: DisplayBackend>(
+        &self,
+        presenter: &mut DisplayPresenter<B>,
+        index: u64,
+        now_ms: f64,
+    ) -> Result<(), VideoBufferError> {
+        let mut frame = Vec::new();
+        self.read_frame(index, &mut frame)?;
+        presenter.present_bytes(&frame, Some(index), now_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    struct RecordingBackend {
+        frames: Vec<(u64, Vec<u8>)>,
+    }
+
+    impl DisplayBackend for RecordingBackend {
+        const FORMAT: PixelFormat = PixelFormat::Rgba8;
+
+        fn init(&mut self, _width: u32, _height: u32) -> Result<(), VideoBufferError> {
+            Ok(())
+        }
+
+        fn present(&mut self, _frame: &[u8]) -> Result<(), VideoBufferError> {
+            unreachable!("raw file frames are always numbered")
+        }
+
+        fn present_numbered(
+            &mut self,
+            frame_no: u64,
+            frame: &[u8],
+        ) -> Result<(), VideoBufferError> {
+            self.frames.push((frame_no, frame.to_vec()));
+            Ok(())
+        }
+    }
+
+    /// Writes `count` 2x1 RGBA frames filled with their index, plus a partial trailing frame
+    fn write_frames(name: &str, count: u8) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("video-buffer-{}-{}.raw", name, std::process::id()));
+        let mut data: Vec<u8> = (0..count).flat_map(|i| [i; 8]).collect();
+        data.extend_from_slice(&[0xff; 3]);
+        std::fs::write(&path, data).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_seek_to_last_frame_and_present() {
+        let path = write_frames("seek", 5);
+        let file = RawFrameFile::open(&path, 2, 1, PixelFormat::Rgba8).unwrap();
+        let backend = RecordingBackend { frames: Vec::new() };
+        let mut presenter = DisplayPresenter::new(backend, 2, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(1.0);
+
+        assert_eq!(file.frame_count(), 5);
+        file.present_frame(&mut presenter, 0, 0.0).unwrap();
+        // Seeking is immediate even though the rate limit would skip this present
+        file.present_frame(&mut presenter, 4, 1.0).unwrap();
+
+        assert_eq!(
+            presenter.backend().frames,
+            vec![(0, vec![0; 8]), (4, vec![4; 8])]
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_out_of_range_index() {
+        let path = write_frames("range", 2);
+        let file = RawFrameFile::open(&path, 2, 1, PixelFormat::Rgba8).unwrap();
+
+        let result = file.read_frame(2, &mut Vec::new());
+        assert!(matches!(
+            result,
+            Err(VideoBufferError::FrameOutOfRange {
+                index: 2,
+                frame_count: 2
+            })
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_missing_file_is_io_error() {
+        let result = RawFrameFile::open("/nonexistent/frames.raw", 2, 1, PixelFormat::Rgba8);
+        assert!(matches!(result, Err(VideoBufferError::Io(_))));
+    }
+}
@@ -0,0 +1,53 @@
+/// Axis-aligned pixel rectangle within a frame
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    pub const fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Rectangle covering a whole `width` x `height` frame
+    pub const fn full(width: u32, height: u32) -> Self {
+        Self::new(0, 0, width, height)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Whether the rectangle lies entirely within a `width` x `height` frame
+    pub const fn fits_within(&self, width: u32, height: u32) -> bool {
+        self.x as u64 + self.width as u64 <= width as u64
+            && self.y as u64 + self.height as u64 <= height as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fits_within() {
+        assert!(Rect::full(4, 4).fits_within(4, 4));
+        assert!(Rect::new(1, 2, 3, 2).fits_within(4, 4));
+        assert!(!Rect::new(2, 0, 3, 1).fits_within(4, 4));
+        assert!(!Rect::new(0, u32::MAX, 1, 2).fits_within(4, 4));
+    }
+
+    #[test]
+    fn test_is_empty() {
+        assert!(Rect::new(1, 1, 0, 5).is_empty());
+        assert!(!Rect::new(1, 1, 1, 1).is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{Renderer, SwapBuffer, VideoBufferError};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Runs a renderer on its own thread, committing each frame to a shared `SwapBuffer`
+///
+/// A panic in `Renderer::render` stops the thread instead of taking the process down
+/// with it; the present thread picks it up from `check` and can shut down cleanly.
+pub struct RenderThread {
+    handle: Option<JoinHandle<()>>,
+    stop: Arc<AtomicBool>,
+    failure: Arc<Mutex<Option<String>>>,
+}
+
+impl RenderThread {
+    /// Start rendering into `buffer`, at most one frame per `frame_interval`
+    pub fn spawn<S, R>(buffer: Arc<S>, mut renderer: R, frame_interval: Duration) -> Self
+    where
+        S: SwapBuffer + Send + Sync + 'static,
+        R: Renderer + Send + 'static,
+    {
+        assert_eq!(
+            buffer.format(),
+            renderer.format(),
+            "renderer format must match the buffer format"
+        );
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let failure = Arc::new(Mutex::new(None));
+        let thread_stop = Arc::clone(&stop);
+        let thread_failure = Arc::clone(&failure);
+
+        let handle = thread::spawn(move || {
+            let mut frame_no: u64 = 0;
+            while !thread_stop.load(Ordering::Acquire) {
+                let start = Instant::now();
+                let rendered = panic::catch_unwind(AssertUnwindSafe(|| {
+                    let (width, height) = buffer.size();
+                    renderer.render(&mut buffer.render_buffer(), width, height, frame_no);
+                }));
+                if let Err(payload) = rendered {
+                    *thread_failure.lock().unwrap() = Some(panic_message(payload));
+                    return;
+                }
+                buffer.commit_render();
+                frame_no = frame_no.wrapping_add(1);
+
+                if let Some(sleep) = frame_interval.checked_sub(start.elapsed()) {
+                    thread::sleep(sleep);
+                }
+            }
+        });
+
+        Self {
+            handle: Some(handle),
+            stop,
+            failure,
+        }
+    }
+
+    /// Report whether the renderer has panicked
+    ///
+    /// Returns `VideoBufferError::RenderPanicked` with the panic message once it has.
+    pub fn check(&self) -> Result<(), VideoBufferError> {
+        match self.failure.lock().unwrap().clone() {
+            Some(message) => Err(VideoBufferError::RenderPanicked(message)),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether the thread is still rendering frames
+    pub fn is_running(&self) -> bool {
+        self.handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// Ask the thread to stop after its current frame and wait for it to exit
+    pub fn stop(mut self) -> Result<(), VideoBufferError> {
+        self.shutdown();
+        self.check()
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            // Renderer panics are caught inside the thread, so joining can't fail
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for RenderThread {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "renderer panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PixelFormat, TripleBuffer};
+
+    struct PanickingRenderer {
+        panic_at: u64,
+    }
+
+    impl Renderer for PanickingRenderer {
+        fn format(&self) -> PixelFormat {
+            PixelFormat::Rgba8
+        }
+
+        fn render(&mut self, frame: &mut [u8], _width: u32, _height: u32, frame_no: u64) {
+            if frame_no == self.panic_at {
+                panic!("renderer failed on frame {}", frame_no);
+            }
+            frame.fill(frame_no as u8);
+        }
+    }
+
+    #[test]
+    fn test_renderer_panic_is_reported() {
+        let buffer = Arc::new(TripleBuffer::new(2, 2, PixelFormat::Rgba8));
+        let thread = RenderThread::spawn(
+            Arc::clone(&buffer),
+            PanickingRenderer { panic_at: 3 },
+            Duration::from_millis(1),
+        );
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while thread.check().is_ok() {
+            assert!(Instant::now() < deadline, "panic was never reported");
+            buffer.wait_for_frame(Duration::from_millis(10));
+            buffer.commit_present();
+        }
+
+        assert_eq!(buffer.generation(), 3);
+        let result = thread.stop();
+        assert!(matches!(
+            result,
+            Err(VideoBufferError::RenderPanicked(message)) if message == "renderer failed on frame 3"
+        ));
+    }
+
+    #[test]
+    fn test_stop_joins_healthy_thread() {
+        let buffer = Arc::new(TripleBuffer::new(2, 2, PixelFormat::Rgba8));
+        let thread = RenderThread::spawn(
+            Arc::clone(&buffer),
+            PanickingRenderer { panic_at: u64::MAX },
+            Duration::from_millis(1),
+        );
+
+        assert!(buffer.wait_for_frame(Duration::from_secs(5)));
+        assert!(thread.is_running());
+        assert!(thread.stop().is_ok());
+    }
+}
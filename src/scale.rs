@@ -0,0 +1,386 @@
+use crate::PixelFormat;
+use std::sync::OnceLock;
+
+/// How frames are resampled to a different resolution
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScaleMode {
+    /// Pick the nearest source pixel. Works for every packed pixel format.
+    ///
+    /// Integer upscales (e.g. 640x360 to 1920x1080) replicate whole rows instead of
+    /// sampling every pixel.
+    Nearest,
+    /// Interpolate between the four nearest source pixels, for smooth upscaling.
+    ///
+    /// Supported for formats with 8-bit channels.
+    Bilinear,
+    /// Average every source pixel covered by each destination pixel, for downscaling.
+    ///
+    /// With `gamma_correct` the color channels are averaged in linear light instead of
+    /// directly on the sRGB bytes, which keeps fine detail from darkening.
+    Box { gamma_correct: bool },
+}
+
+/// Resample a frame of `src_size` (width, height) into a frame of `dst_size`
+pub fn scale_frame(
+    src: &[u8],
+    src_size: (u32, u32),
+    dst: &mut [u8],
+    dst_size: (u32, u32),
+    format: PixelFormat,
+    mode: ScaleMode,
+) {
+    let (src_width, src_height) = src_size;
+    let (dst_width, dst_height) = dst_size;
+    assert!(!format.is_planar(), "scaling {:?} is not supported", format);
+    assert_eq!(
+        src.len(),
+        format.buffer_size(src_width, src_height),
+        "source frame has the wrong size"
+    );
+    assert_eq!(
+        dst.len(),
+        format.buffer_size(dst_width, dst_height),
+        "destination frame has the wrong size"
+    );
+
+    let src_size = (src_width as usize, src_height as usize);
+    let dst_size = (dst_width as usize, dst_height as usize);
+    match mode {
+        ScaleMode::Nearest
+            if src_size.0 > 0
+                && src_size.1 > 0
+                && dst_size.0.is_multiple_of(src_size.0)
+                && dst_size.1.is_multiple_of(src_size.1) =>
+        {
+            scale_integer(src, src_size, dst, dst_size, format)
+        }
+        ScaleMode::Nearest => scale_nearest(src, src_size, dst, dst_size, format),
+        ScaleMode::Bilinear => scale_bilinear(src, src_size, dst, dst_size, format),
+        ScaleMode::Box { gamma_correct } => {
+            downscale_box(src, src_size, dst, dst_size, format, gamma_correct)
+        }
+    }
+}
+
+fn scale_nearest(
+    src: &[u8],
+    (src_width, src_height): (usize, usize),
+    dst: &mut [u8],
+    (dst_width, dst_height): (usize, usize),
+    format: PixelFormat,
+) {
+    let bpp = format.bytes_per_pixel();
+    for y in 0..dst_height {
+        let src_y = y * src_height / dst_height;
+        for x in 0..dst_width {
+            let src_x = x * src_width / dst_width;
+            let src_idx = (src_y * src_width + src_x) * bpp;
+            let dst_idx = (y * dst_width + x) * bpp;
+            dst[dst_idx..dst_idx + bpp].copy_from_slice(&src[src_idx..src_idx + bpp]);
+        }
+    }
+}
+
+/// Nearest-neighbor upscale by whole factors: expand each source row once, then copy it
+fn scale_integer(
+    src: &[u8],
+    (src_width, src_height): (usize, usize),
+    dst: &mut [u8],
+    (dst_width, dst_height): (usize, usize),
+    format: PixelFormat,
+) {
+    let bpp = format.bytes_per_pixel();
+    let (x_factor, y_factor) = (dst_width / src_width, dst_height / src_height);
+    let dst_stride = dst_width * bpp;
+    if dst_stride == 0 {
+        return;
+    }
+
+    let dst_rows = dst.chunks_exact_mut(dst_stride * y_factor);
+    for (src_row, dst_rows) in src.chunks_exact(src_width * bpp).zip(dst_rows) {
+        let (first, rest) = dst_rows.split_at_mut(dst_stride);
+        for (pixel, run) in src_row
+            .chunks_exact(bpp)
+            .zip(first.chunks_exact_mut(bpp * x_factor))
+        {
+            for out in run.chunks_exact_mut(bpp) {
+                out.copy_from_slice(pixel);
+            }
+        }
+        for row in rest.chunks_exact_mut(dst_stride) {
+            row.copy_from_slice(first);
+        }
+    }
+}
+
+fn scale_bilinear(
+    src: &[u8],
+    (src_width, src_height): (usize, usize),
+    dst: &mut [u8],
+    (dst_width, dst_height): (usize, usize),
+    format: PixelFormat,
+) {
+    assert!(
+        matches!(
+            format,
+            PixelFormat::Rgba8
+                | PixelFormat::Prgb8
+                | PixelFormat::Bgra8
+                | PixelFormat::Argb8
+                | PixelFormat::Abgr8
+                | PixelFormat::Rgb8
+                | PixelFormat::Bgr8
+                | PixelFormat::Gray8
+        ),
+        "bilinear scaling is not supported for {:?}",
+        format
+    );
+    let bpp = format.bytes_per_pixel();
+    let columns: Vec<_> = (0..dst_width)
+        .map(|x| sample_pair(x, src_width, dst_width))
+        .collect();
+
+    for y in 0..dst_height {
+        let (y0, y1, y_weight) = sample_pair(y, src_height, dst_height);
+        for (x, &(x0, x1, x_weight)) in columns.iter().enumerate() {
+            let at = |sx: usize, sy: usize, channel: usize| {
+                src[(sy * src_width + sx) * bpp + channel] as u32
+            };
+            for channel in 0..bpp {
+                let top = at(x0, y0, channel) * (256 - x_weight) + at(x1, y0, channel) * x_weight;
+                let bottom =
+                    at(x0, y1, channel) * (256 - x_weight) + at(x1, y1, channel) * x_weight;
+                dst[(y * dst_width + x) * bpp + channel] =
+                    ((top * (256 - y_weight) + bottom * y_weight + (1 << 15)) >> 16) as u8;
+            }
+        }
+    }
+}
+
+/// The two source pixels around the center of destination pixel `pos`, and the weight
+/// (out of 256) of the second one
+fn sample_pair(pos: usize, src_len: usize, dst_len: usize) -> (usize, usize, u32) {
+    let center = ((pos as f64 + 0.5) * src_len as f64 / dst_len as f64 - 0.5).max(0.0);
+    let first = (center as usize).min(src_len - 1);
+    let second = (first + 1).min(src_len - 1);
+    let weight = ((center - first as f64) * 256.0).round() as u32;
+    (first, second, weight.min(256))
+}
+
+fn downscale_box(
+    src: &[u8],
+    (src_width, src_height): (usize, usize),
+    dst: &mut [u8],
+    (dst_width, dst_height): (usize, usize),
+    format: PixelFormat,
+    gamma_correct: bool,
+) {
+    let alpha_channel = match format {
+        PixelFormat::Rgba8 | PixelFormat::Bgra8 => Some(3),
+        PixelFormat::Prgb8 | PixelFormat::Argb8 | PixelFormat::Abgr8 => Some(0),
+        PixelFormat::Rgb8 | PixelFormat::Bgr8 | PixelFormat::Gray8 => None,
+        _ => panic!("box scaling is not supported for {:?}", format),
+    };
+    let channels = format.bytes_per_pixel();
+    let to_linear = srgb_to_linear_table();
+
+    for y in 0..dst_height {
+        let (y0, y1) = source_span(y, src_height, dst_height);
+        for x in 0..dst_width {
+            let (x0, x1) = source_span(x, src_width, dst_width);
+
+            let mut sums = [0.0f32; 4];
+            for sy in y0..y1 {
+                for sx in x0..x1 {
+                    let idx = (sy * src_width + sx) * channels;
+                    for (channel, sum) in sums[..channels].iter_mut().enumerate() {
+                        let value = src[idx + channel];
+                        *sum += if gamma_correct && Some(channel) != alpha_channel {
+                            to_linear[value as usize]
+                        } else {
+                            value as f32 / 255.0
+                        };
+                    }
+                }
+            }
+
+            let count = ((y1 - y0) * (x1 - x0)) as f32;
+            let dst_idx = (y * dst_width + x) * channels;
+            for (channel, sum) in sums[..channels].iter().enumerate() {
+                let average = sum / count;
+                let encoded = if gamma_correct && Some(channel) != alpha_channel {
+                    linear_to_srgb(average)
+                } else {
+                    average
+                };
+                dst[dst_idx + channel] = (encoded * 255.0).round() as u8;
+            }
+        }
+    }
+}
+
+/// Source pixel range covered by destination pixel `pos`, always at least one pixel
+fn source_span(pos: usize, src_len: usize, dst_len: usize) -> (usize, usize) {
+    let start = pos * src_len / dst_len;
+    let end = ((pos + 1) * src_len).div_ceil(dst_len).max(start + 1);
+    (start, end.min(src_len))
+}
+
+fn srgb_to_linear_table() -> &'static [f32; 256] {
+    static TABLE: OnceLock<[f32; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        std::array::from_fn(|i| {
+            let c = i as f32 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        })
+    })
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Vec<u8> {
+        [
+            [0, 0, 0, 255],
+            [255, 255, 255, 255],
+            [255, 255, 255, 255],
+            [0, 0, 0, 255],
+        ]
+        .concat()
+    }
+
+    fn downscale_to_pixel(gamma_correct: bool) -> [u8; 4] {
+        let mut dst = [0u8; 4];
+        scale_frame(
+            &checkerboard(),
+            (2, 2),
+            &mut dst,
+            (1, 1),
+            PixelFormat::Rgba8,
+            ScaleMode::Box { gamma_correct },
+        );
+        dst
+    }
+
+    #[test]
+    fn test_naive_box_averages_bytes() {
+        assert_eq!(downscale_to_pixel(false), [128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn test_gamma_correct_box_averages_light() {
+        assert_eq!(downscale_to_pixel(true), [188, 188, 188, 255]);
+    }
+
+    #[test]
+    fn test_gamma_correct_keeps_prgb_alpha_linear() {
+        let src = [[0, 0, 0, 0], [255, 255, 255, 255]].concat();
+        let mut dst = [0u8; 4];
+        scale_frame(
+            &src,
+            (2, 1),
+            &mut dst,
+            (1, 1),
+            PixelFormat::Prgb8,
+            ScaleMode::Box {
+                gamma_correct: true,
+            },
+        );
+        assert_eq!(dst, [128, 188, 188, 188]);
+    }
+
+    #[test]
+    fn test_nearest_upscale() {
+        let src = [1u8, 2, 3, 4];
+        let mut dst = [0u8; 16];
+        scale_frame(
+            &src,
+            (2, 1),
+            &mut dst,
+            (4, 2),
+            PixelFormat::Rgb565,
+            ScaleMode::Nearest,
+        );
+        assert_eq!(dst, [1, 2, 1, 2, 3, 4, 3, 4, 1, 2, 1, 2, 3, 4, 3, 4]);
+    }
+
+    #[test]
+    fn test_box_uneven_ratio_covers_every_source_pixel() {
+        // 3 pixels down to 2: spans [0, 2) and [1, 3)
+        assert_eq!(source_span(0, 3, 2), (0, 2));
+        assert_eq!(source_span(1, 3, 2), (1, 3));
+    }
+
+    #[test]
+    fn test_gamma_correct_box_rgb8() {
+        let src = [[0, 0, 0], [255, 255, 255]].concat();
+        let mut dst = [0u8; 3];
+        scale_frame(
+            &src,
+            (2, 1),
+            &mut dst,
+            (1, 1),
+            PixelFormat::Rgb8,
+            ScaleMode::Box {
+                gamma_correct: true,
+            },
+        );
+        assert_eq!(dst, [188, 188, 188]);
+    }
+
+    #[test]
+    fn test_integer_upscale_replicates_pixels() {
+        let src = [1u8, 2, 3, 4, 5, 6];
+        let mut dst = [0u8; 24];
+        scale_frame(
+            &src,
+            (3, 2),
+            &mut dst,
+            (6, 4),
+            PixelFormat::Gray8,
+            ScaleMode::Nearest,
+        );
+        assert_eq!(
+            dst,
+            [
+                1, 1, 2, 2, 3, 3, //
+                1, 1, 2, 2, 3, 3, //
+                4, 4, 5, 5, 6, 6, //
+                4, 4, 5, 5, 6, 6,
+            ]
+        );
+
+        // The fast path matches sampling every pixel
+        let mut sampled = [0u8; 24];
+        scale_nearest(&src, (3, 2), &mut sampled, (6, 4), PixelFormat::Gray8);
+        assert_eq!(dst, sampled);
+    }
+
+    #[test]
+    fn test_bilinear_interpolates_between_pixels() {
+        let mut dst = [0u8; 4];
+        scale_frame(
+            &[0, 200],
+            (2, 1),
+            &mut dst,
+            (4, 1),
+            PixelFormat::Gray8,
+            ScaleMode::Bilinear,
+        );
+        // Outer pixels clamp to the edges, inner ones sit a quarter of the way across
+        assert_eq!(dst, [0, 50, 150, 200]);
+    }
+}
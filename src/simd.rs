@@ -0,0 +1,116 @@
+//! Vectorized channel shuffles for 4-byte pixels
+//!
+//! Each entry point shuffles as many whole vectors as it can and returns the number of
+//! bytes written, leaving the tail to the caller's scalar loop.
+
+/// Reorder channels like `Swizzle(pattern)` using AVX2 or SSSE3 when available
+#[cfg(target_arch = "x86_64")]
+pub(crate) fn shuffle_pixels(src: &[u8], dst: &mut [u8], pattern: [u8; 4]) -> usize {
+    if is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 support was detected at runtime
+        unsafe { x86::shuffle_avx2(src, dst, pattern) }
+    } else if is_x86_feature_detected!("ssse3") {
+        // SAFETY: SSSE3 support was detected at runtime
+        unsafe { x86::shuffle_ssse3(src, dst, pattern) }
+    } else {
+        0
+    }
+}
+
+/// Reorder channels like `Swizzle(pattern)` using NEON
+#[cfg(target_arch = "aarch64")]
+pub(crate) fn shuffle_pixels(src: &[u8], dst: &mut [u8], pattern: [u8; 4]) -> usize {
+    // SAFETY: NEON is part of the aarch64 baseline
+    unsafe { neon::shuffle(src, dst, pattern) }
+}
+
+/// No vector path on this target, the caller handles every pixel
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+pub(crate) fn shuffle_pixels(_src: &[u8], _dst: &mut [u8], _pattern: [u8; 4]) -> usize {
+    0
+}
+
+/// Byte shuffle mask applying `pattern` to every pixel of a 128-bit lane
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn lane_mask<const N: usize>(pattern: [u8; 4]) -> [u8; N] {
+    std::array::from_fn(|i| (i % 16 - i % 4) as u8 + pattern[i % 4])
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::lane_mask;
+    use std::arch::x86_64::*;
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn shuffle_avx2(src: &[u8], dst: &mut [u8], pattern: [u8; 4]) -> usize {
+        let mask: [u8; 32] = lane_mask(pattern);
+        let len = src.len().min(dst.len()) / 32 * 32;
+        unsafe {
+            let mask = _mm256_loadu_si256(mask.as_ptr().cast());
+            for offset in (0..len).step_by(32) {
+                let pixels = _mm256_loadu_si256(src.as_ptr().add(offset).cast());
+                let shuffled = _mm256_shuffle_epi8(pixels, mask);
+                _mm256_storeu_si256(dst.as_mut_ptr().add(offset).cast(), shuffled);
+            }
+        }
+        len
+    }
+
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn shuffle_ssse3(src: &[u8], dst: &mut [u8], pattern: [u8; 4]) -> usize {
+        let mask: [u8; 16] = lane_mask(pattern);
+        let len = src.len().min(dst.len()) / 16 * 16;
+        unsafe {
+            let mask = _mm_loadu_si128(mask.as_ptr().cast());
+            for offset in (0..len).step_by(16) {
+                let pixels = _mm_loadu_si128(src.as_ptr().add(offset).cast());
+                let shuffled = _mm_shuffle_epi8(pixels, mask);
+                _mm_storeu_si128(dst.as_mut_ptr().add(offset).cast(), shuffled);
+            }
+        }
+        len
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use super::lane_mask;
+    use std::arch::aarch64::*;
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn shuffle(src: &[u8], dst: &mut [u8], pattern: [u8; 4]) -> usize {
+        let mask: [u8; 16] = lane_mask(pattern);
+        let len = src.len().min(dst.len()) / 16 * 16;
+        unsafe {
+            let mask = vld1q_u8(mask.as_ptr());
+            for offset in (0..len).step_by(16) {
+                let pixels = vld1q_u8(src.as_ptr().add(offset));
+                vst1q_u8(dst.as_mut_ptr().add(offset), vqtbl1q_u8(pixels, mask));
+            }
+        }
+        len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shuffle_matches_scalar_with_tail() {
+        // 19 pixels: whole vectors plus a tail the caller must finish
+        let src: Vec<u8> = (0..76).collect();
+        let mut dst = vec![0u8; 76];
+        let done = shuffle_pixels(&src, &mut dst, [1, 2, 3, 0]);
+
+        assert_eq!(done % 16, 0);
+        assert!(done <= src.len());
+        for (src_pixel, dst_pixel) in src[..done].chunks_exact(4).zip(dst.chunks_exact(4)) {
+            assert_eq!(
+                dst_pixel,
+                [src_pixel[1], src_pixel[2], src_pixel[3], src_pixel[0]]
+            );
+        }
+        assert!(dst[done..].iter().all(|&b| b == 0));
+    }
+}
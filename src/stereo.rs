@@ -0,0 +1,155 @@
+use crate::PixelFormat;
+
+/// How left and right eye frames are combined into one presented frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StereoLayout {
+    /// Left eye in the left half, right eye in the right half, each squeezed horizontally.
+    SideBySide,
+    /// Left eye in the top half, right eye in the bottom half, each squeezed vertically.
+    TopBottom,
+    /// Full-resolution eyes alternating on every presented frame, starting with the left.
+    FrameSequential,
+}
+
+/// Pack two full-size eye frames into `dst` using a spatial layout
+///
+/// Each eye is downscaled 2:1 along the split axis with nearest-neighbor sampling.
+/// `FrameSequential` has no spatial packing and is handled by the caller.
+pub(crate) fn pack_stereo(
+    left: &[u8],
+    right: &[u8],
+    dst: &mut [u8],
+    width: u32,
+    height: u32,
+    format: PixelFormat,
+    layout: StereoLayout,
+) {
+    assert!(
+        !format.is_planar(),
+        "stereo packing {:?} is not supported",
+        format
+    );
+    let size = format.buffer_size(width, height);
+    assert_eq!(left.len(), size, "left frame has the wrong size");
+    assert_eq!(right.len(), size, "right frame has the wrong size");
+    assert_eq!(dst.len(), size, "destination frame has the wrong size");
+
+    let bpp = format.bytes_per_pixel();
+    let stride = format.stride(width);
+    let (width, height) = (width as usize, height as usize);
+
+    match layout {
+        StereoLayout::SideBySide => {
+            let half = width.div_ceil(2);
+            for y in 0..height as u32 {
+                for x in 0..width {
+                    let (eye, src_x) = if x < half {
+                        (left, x * 2)
+                    } else {
+                        (right, (x - half) * 2)
+                    };
+                    let src = format.pixel_offset(src_x.min(width - 1) as u32, y, stride);
+                    let dst_offset = format.pixel_offset(x as u32, y, stride);
+                    dst[dst_offset..dst_offset + bpp].copy_from_slice(&eye[src..src + bpp]);
+                }
+            }
+        }
+        StereoLayout::TopBottom => {
+            let half = height.div_ceil(2);
+            for y in 0..height {
+                let (eye, src_y) = if y < half {
+                    (left, y * 2)
+                } else {
+                    (right, (y - half) * 2)
+                };
+                let src = format.row_range(src_y.min(height - 1) as u32, width as u32, stride);
+                let dst_row = format.row_range(y as u32, width as u32, stride);
+                dst[dst_row].copy_from_slice(&eye[src]);
+            }
+        }
+        StereoLayout::FrameSequential => {
+            unreachable!("frame-sequential stereo is not spatially packed")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RGBA frame where each pixel encodes its coordinates and which eye it belongs to
+    fn eye_frame(width: u32, height: u32, eye: u8) -> Vec<u8> {
+        let mut frame = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                frame.extend_from_slice(&[x as u8, y as u8, eye, 255]);
+            }
+        }
+        frame
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let left = eye_frame(4, 2, 0);
+        let right = eye_frame(4, 2, 1);
+        let mut dst = vec![0u8; 32];
+
+        pack_stereo(
+            &left,
+            &right,
+            &mut dst,
+            4,
+            2,
+            PixelFormat::Rgba8,
+            StereoLayout::SideBySide,
+        );
+
+        for y in 0..2u8 {
+            let row = &dst[y as usize * 16..(y as usize + 1) * 16];
+            assert_eq!(&row[0..4], &[0, y, 0, 255]); // left x=0
+            assert_eq!(&row[4..8], &[2, y, 0, 255]); // left x=2
+            assert_eq!(&row[8..12], &[0, y, 1, 255]); // right x=0
+            assert_eq!(&row[12..16], &[2, y, 1, 255]); // right x=2
+        }
+    }
+
+    #[test]
+    fn test_top_bottom() {
+        let left = eye_frame(2, 4, 0);
+        let right = eye_frame(2, 4, 1);
+        let mut dst = vec![0u8; 32];
+
+        pack_stereo(
+            &left,
+            &right,
+            &mut dst,
+            2,
+            4,
+            PixelFormat::Rgba8,
+            StereoLayout::TopBottom,
+        );
+
+        let rows: Vec<[u8; 2]> = dst.chunks_exact(8).map(|row| [row[1], row[2]]).collect();
+        // (source y, eye) per output row
+        assert_eq!(rows, vec![[0, 0], [2, 0], [0, 1], [2, 1]]);
+    }
+
+    #[test]
+    fn test_side_by_side_odd_width() {
+        let left = eye_frame(3, 1, 0);
+        let right = eye_frame(3, 1, 1);
+        let mut dst = vec![0u8; 12];
+
+        pack_stereo(
+            &left,
+            &right,
+            &mut dst,
+            3,
+            1,
+            PixelFormat::Rgba8,
+            StereoLayout::SideBySide,
+        );
+
+        assert_eq!(dst, vec![0, 0, 0, 255, 2, 0, 0, 255, 0, 0, 1, 255]);
+    }
+}
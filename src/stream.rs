@@ -0,0 +1,124 @@
+use crate::{DisplayBackend, DisplayPresenter, FrameQueue, VideoBufferError};
+use futures::{Stream, StreamExt};
+
+/// Present numbered frames from an async stream in frame-number order
+///
+/// Frames are reordered through `queue`, and each one that becomes ready is presented
+/// at the time reported by the presenter's clock, subject to its frame rate limit and skip
+/// policy. Frames the queue rejects (already displayed, or the queue is full) are dropped.
+/// A frame still held under `SkipPolicy::HoldAsCurrent` when the stream ends is presented
+/// right away. Returns the number of frames presented.
+pub async fn present_stream<B, S>(
+    presenter: &mut DisplayPresenter<B>,
+    queue: &mut FrameQueue,
+    stream: S,
+) -> Result<u64, VideoBufferError>
+where
+    B: DisplayBackend,
+    S: Stream<Item = (u64, Vec<u8>)>,
+{
+    let mut stream = std::pin::pin!(stream);
+    let mut presented = 0;
+
+    while let Some((frame_no, frame)) = stream.next().await {
+        queue.push(frame_no, frame);
+
+        while let Some((frame_no, frame)) = queue.pop_ready_numbered() {
+            let now_ms = presenter.now_ms()?;
+            if presenter.present_numbered_frame(frame_no, &frame, now_ms)? {
+                presented += 1;
+            }
+        }
+    }
+
+    let now_ms = presenter.now_ms()?;
+    if presenter.flush_held(now_ms)? {
+        presented += 1;
+    }
+
+    Ok(presented)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::MemoryBackend;
+    use crate::{Clock, PixelFormat, SkipPolicy};
+    use futures::executor::block_on;
+    use futures::stream;
+    use std::cell::Cell;
+
+    /// Clock that moves `step_ms` forward every time it is read
+    struct SteppingClock {
+        now_ms: Cell<f64>,
+        step_ms: f64,
+    }
+
+    impl SteppingClock {
+        fn new(step_ms: f64) -> Self {
+            Self {
+                now_ms: Cell::new(0.0),
+                step_ms,
+            }
+        }
+    }
+
+    impl Clock for SteppingClock {
+        fn now_ms(&self) -> f64 {
+            self.now_ms.set(self.now_ms.get() + self.step_ms);
+            self.now_ms.get()
+        }
+    }
+
+    fn frame(frame_no: u64) -> (u64, Vec<u8>) {
+        (frame_no, vec![frame_no as u8; 4])
+    }
+
+    #[test]
+    fn test_out_of_order_stream_presents_in_order() {
+        let mut presenter = DisplayPresenter::new(<MemoryBackend>::new(), 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_clock(SteppingClock::new(16.0));
+        let mut queue = FrameQueue::new(8);
+        let frames = stream::iter(vec![frame(1), frame(0), frame(3), frame(2), frame(4)]);
+
+        let presented = block_on(present_stream(&mut presenter, &mut queue, frames));
+
+        assert_eq!(presented.unwrap(), 5);
+        assert_eq!(presenter.backend().frame_numbers(), &[0, 1, 2, 3, 4]);
+        assert_eq!(presenter.backend().last_frame(), &[4u8; 4]);
+    }
+
+    #[test]
+    fn test_stream_honors_rate_limit() {
+        // Frames arrive every 17ms, so every other one is too soon for 30fps
+        let mut presenter = DisplayPresenter::new(<MemoryBackend>::new(), 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(30.0)
+            .with_clock(SteppingClock::new(17.0));
+        let mut queue = FrameQueue::new(8);
+        let frames = stream::iter((0..6).map(frame));
+
+        let presented = block_on(present_stream(&mut presenter, &mut queue, frames));
+
+        assert_eq!(presented.unwrap(), 3);
+        assert_eq!(presenter.backend().frame_numbers(), &[1, 3, 5]);
+    }
+
+    #[test]
+    fn test_stream_end_flushes_held_frame() {
+        let mut presenter = DisplayPresenter::new(<MemoryBackend>::new(), 1, 1, PixelFormat::Rgba8)
+            .unwrap()
+            .with_max_fps(30.0)
+            .with_skip_policy(SkipPolicy::HoldAsCurrent)
+            .with_clock(SteppingClock::new(17.0));
+        let mut queue = FrameQueue::new(8);
+        let frames = stream::iter((0..5).map(frame));
+
+        let presented = block_on(present_stream(&mut presenter, &mut queue, frames));
+
+        assert_eq!(presented.unwrap(), 3);
+        assert_eq!(presenter.backend().frame_numbers(), &[1, 3, 4]);
+        assert!(!presenter.has_held_frame());
+    }
+}
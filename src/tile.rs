@@ -0,0 +1,126 @@
+use crate::{PixelFormat, Rect};
+use std::ops::Range;
+
+/// Splits a frame into a grid of tiles that workers can render independently
+///
+/// Tiles are laid out row by row; tiles in the last column and row are clipped to the
+/// frame edge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TileGrid {
+    width: u32,
+    height: u32,
+    tile_width: u32,
+    tile_height: u32,
+    format: PixelFormat,
+    stride: usize,
+}
+
+impl TileGrid {
+    pub fn new(
+        width: u32,
+        height: u32,
+        tile_width: u32,
+        tile_height: u32,
+        format: PixelFormat,
+    ) -> Self {
+        assert!(
+            tile_width > 0 && tile_height > 0,
+            "tile size must be greater than 0"
+        );
+
+        Self {
+            width,
+            height,
+            tile_width,
+            tile_height,
+            format,
+            stride: format.stride(width),
+        }
+    }
+
+    /// Use a row stride larger than the packed row size, for padded buffers
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        assert!(
+            stride >= self.format.stride(self.width),
+            "stride is smaller than a row"
+        );
+        self.stride = stride;
+        self
+    }
+
+    pub fn columns(&self) -> u32 {
+        self.width.div_ceil(self.tile_width)
+    }
+
+    pub fn rows(&self) -> u32 {
+        self.height.div_ceil(self.tile_height)
+    }
+
+    /// Each tile's rectangle and the byte range from its first to its last pixel
+    ///
+    /// Tiles never share a pixel, but tiles narrower than the frame are not contiguous:
+    /// their byte range spans the rows in between, so write through `PixelFormat::row_range`
+    /// rather than the whole range.
+    pub fn tiles(&self) -> impl Iterator<Item = (Rect, Range<usize>)> + '_ {
+        let columns = self.columns();
+        (0..self.rows() * columns).map(move |i| {
+            let x = (i % columns) * self.tile_width;
+            let y = (i / columns) * self.tile_height;
+            let rect = Rect::new(
+                x,
+                y,
+                self.tile_width.min(self.width - x),
+                self.tile_height.min(self.height - y),
+            );
+
+            let start = self.format.pixel_offset(x, y, self.stride);
+            let last_row = self
+                .format
+                .pixel_offset(x, y + rect.height - 1, self.stride);
+            (rect, start..last_row + self.format.stride(rect.width))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiles_cover_frame_exactly_once() {
+        let (width, height) = (10, 7);
+        let grid = TileGrid::new(width, height, 4, 3, PixelFormat::Rgba8);
+        let mut coverage = vec![0u8; (width * height) as usize];
+
+        for (rect, _) in grid.tiles() {
+            for y in rect.y..rect.y + rect.height {
+                for x in rect.x..rect.x + rect.width {
+                    coverage[(y * width + x) as usize] += 1;
+                }
+            }
+        }
+
+        assert_eq!(grid.tiles().count(), 9);
+        assert!(coverage.iter().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn test_tile_byte_ranges_with_padded_stride() {
+        let grid = TileGrid::new(5, 4, 3, 2, PixelFormat::Rgba8).with_stride(24);
+        let tiles: Vec<_> = grid.tiles().collect();
+
+        assert_eq!(tiles[0], (Rect::new(0, 0, 3, 2), 0..36));
+        // Clipped right-hand tile
+        assert_eq!(tiles[1], (Rect::new(3, 0, 2, 2), 12..44));
+        assert_eq!(tiles[2], (Rect::new(0, 2, 3, 2), 48..84));
+        assert_eq!(tiles[3].1.end, 92);
+    }
+
+    #[test]
+    fn test_full_width_tiles_are_disjoint_ranges() {
+        let grid = TileGrid::new(8, 8, 8, 3, PixelFormat::Rgb565);
+        let ranges: Vec<_> = grid.tiles().map(|(_, range)| range).collect();
+
+        assert_eq!(ranges, vec![0..48, 48..96, 96..128]);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::VideoBufferError;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Writes one CSV row per presented frame for offline stutter analysis
+///
+/// Rows are `time_ms,frame_no,on_time`, with `frame_no` empty for unnumbered frames.
+/// Attach it with `DisplayPresenter::with_trace` and call `finish` once done.
+pub struct TraceRecorder {
+    writer: BufWriter<File>,
+    flush_every: u32,
+    unflushed: u32,
+}
+
+impl TraceRecorder {
+    /// Create (or truncate) the trace file at `path` and write the CSV header
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, VideoBufferError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "time_ms,frame_no,on_time")?;
+
+        Ok(Self {
+            writer,
+            flush_every: 60,
+            unflushed: 0,
+        })
+    }
+
+    /// Flush to disk after every `records` rows (default 60), so a crash loses little
+    pub fn with_flush_interval(mut self, records: u32) -> Self {
+        self.flush_every = records.max(1);
+        self
+    }
+
+    /// Append one present to the trace
+    pub fn record(
+        &mut self,
+        time_ms: f64,
+        frame_no: Option<u64>,
+        on_time: bool,
+    ) -> Result<(), VideoBufferError> {
+        match frame_no {
+            Some(frame_no) => writeln!(self.writer, "{:.3},{},{}", time_ms, frame_no, on_time)?,
+            None => writeln!(self.writer, "{:.3},,{}", time_ms, on_time)?,
+        }
+
+        self.unflushed += 1;
+        if self.unflushed >= self.flush_every {
+            self.writer.flush()?;
+            self.unflushed = 0;
+        }
+        Ok(())
+    }
+
+    /// Flush any buffered rows and close the file
+    pub fn finish(mut self) -> Result<(), VideoBufferError> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_all()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::{memory, MemoryBackend};
+    use crate::{DisplayPresenter, PixelFormat};
+
+    #[test]
+    fn test_presents_are_traced() {
+        let path =
+            std::env::temp_dir().join(format!("video-buffer-trace-{}.csv", std::process::id()));
+        let trace = TraceRecorder::create(&path).unwrap();
+        let mut presenter = DisplayPresenter::new(
+            MemoryBackend::<memory::Rgba8>::new(),
+            2,
+            1,
+            PixelFormat::Rgba8,
+        )
+        .unwrap()
+        .with_max_fps(50.0)
+        .with_trace(trace);
+
+        presenter.present_frame_at(&[0; 8], 20.0).unwrap();
+        presenter.present_numbered_frame(1, &[0; 8], 40.0).unwrap();
+        // Two frame intervals since the last present: a frame was missed
+        presenter.present_numbered_frame(2, &[0; 8], 80.0).unwrap();
+        presenter.take_trace().unwrap().finish().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let rows: Vec<Vec<&str>> = contents
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').collect())
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["20.000", "", "true"],
+                vec!["40.000", "1", "true"],
+                vec!["80.000", "2", "false"],
+            ]
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
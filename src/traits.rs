@@ -1,12 +1,141 @@
-use crate::{PixelFormat, VideoBufferError};
+use crate::{PixelFormat, Rect, VideoBufferError};
+use std::sync::{Arc, MutexGuard};
 
 pub trait Renderer {
-    const FORMAT: PixelFormat;
-    fn render(&mut self, frame: &mut [u8], width: u32, height: u32);
+    /// Pixel format of the frames `render` writes
+    fn format(&self) -> PixelFormat;
+
+    /// Render frame number `frame_no` into `frame`
+    ///
+    /// Frame numbers start at 0 and increase by one per rendered frame, wrapping on overflow.
+    fn render(&mut self, frame: &mut [u8], width: u32, height: u32, frame_no: u64);
+}
+
+/// Frame slots shared between one render thread and one present thread
+///
+/// `TripleBuffer` never makes the renderer wait for the presenter; `DoubleBuffer`
+/// shows each frame sooner but can block the renderer while a frame is presented.
+/// Implement it for other schemes, e.g. a ring of N buffers, to use them with
+/// `DisplayBridge`, `DisplayPresenter` and `RenderThread`.
+pub trait SwapBuffer {
+    /// Current (width, height) of the frames
+    fn size(&self) -> (u32, u32);
+    fn format(&self) -> PixelFormat;
+
+    fn width(&self) -> u32 {
+        self.size().0
+    }
+
+    fn height(&self) -> u32 {
+        self.size().1
+    }
+
+    /// Bytes from the start of one row to the next; packed rows by default
+    fn stride(&self) -> usize {
+        self.format().stride(self.width())
+    }
+
+    /// Lock the slot the renderer draws into
+    fn render_buffer(&self) -> MutexGuard<'_, Vec<u8>>;
+    /// Hand the rendered frame over to the present side
+    fn commit_render(&self);
+
+    /// Lock the slot holding the frame to present
+    fn present_buffer(&self) -> MutexGuard<'_, Vec<u8>>;
+    /// Pick up the most recently rendered frame for the next `present_buffer`
+    fn commit_present(&self);
+}
+
+impl<S: SwapBuffer + ?Sized> SwapBuffer for Arc<S> {
+    fn size(&self) -> (u32, u32) {
+        (**self).size()
+    }
+
+    fn format(&self) -> PixelFormat {
+        (**self).format()
+    }
+
+    fn stride(&self) -> usize {
+        (**self).stride()
+    }
+
+    fn render_buffer(&self) -> MutexGuard<'_, Vec<u8>> {
+        (**self).render_buffer()
+    }
+
+    fn commit_render(&self) {
+        (**self).commit_render()
+    }
+
+    fn present_buffer(&self) -> MutexGuard<'_, Vec<u8>> {
+        (**self).present_buffer()
+    }
+
+    fn commit_present(&self) {
+        (**self).commit_present()
+    }
 }
 
 pub trait DisplayBackend {
     const FORMAT: PixelFormat;
     fn init(&mut self, width: u32, height: u32) -> Result<(), VideoBufferError>;
     fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError>;
+
+    /// Pixel layout of the real display surface, if the backend can tell
+    ///
+    /// Checked against `FORMAT` when a presenter initializes the backend. The default
+    /// `None` skips the check.
+    fn surface_format(&self) -> Option<PixelFormat> {
+        None
+    }
+
+    /// Fill the whole display surface with an RGBA color
+    ///
+    /// The default does nothing, which suits backends whose `present` overwrites the
+    /// entire surface.
+    fn clear(&mut self, _color: [u8; 4]) -> Result<(), VideoBufferError> {
+        Ok(())
+    }
+
+    /// Present a frame and block until the display has taken it (vsync)
+    ///
+    /// The default presents without waiting.
+    fn present_blocking(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        self.present(frame)
+    }
+
+    /// Present a frame along with its sequence number
+    ///
+    /// Backends that don't track frame numbers can rely on the default, which ignores it.
+    fn present_numbered(&mut self, _frame_no: u64, frame: &[u8]) -> Result<(), VideoBufferError> {
+        self.present(frame)
+    }
+
+    /// Present a frame of which only `rect` changed since the previous present
+    ///
+    /// `frame` is always the whole frame. Backends that keep their own copy of the last
+    /// frame can update just `rect`; the default presents the whole frame.
+    fn present_region(&mut self, frame: &[u8], _rect: Rect) -> Result<(), VideoBufferError> {
+        self.present(frame)
+    }
+}
+
+/// Initialize `backend` and verify its surface matches the declared `FORMAT`
+pub(crate) fn init_backend<B: DisplayBackend>(
+    backend: &mut B,
+    width: u32,
+    height: u32,
+) -> Result<(), VideoBufferError> {
+    backend.init(width, height)?;
+
+    match backend.surface_format() {
+        Some(surface_format) if surface_format != B::FORMAT => {
+            Err(VideoBufferError::InitFailed(format!(
+                "backend declares {:?} but its surface is {:?}",
+                B::FORMAT,
+                surface_format
+            )))
+        }
+        _ => Ok(()),
+    }
 }
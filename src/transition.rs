@@ -0,0 +1,240 @@
+use crate::PixelFormat;
+
+/// Fade-in/fade-out ramps applied to presented frames.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct Transition {
+    fade_in_ms: Option<f64>,
+    fade_out: Option<(f64, f64)>,
+}
+
+impl Transition {
+    pub(crate) fn set_fade_in(&mut self, duration_ms: f64) {
+        self.fade_in_ms = Some(duration_ms);
+    }
+
+    pub(crate) fn set_fade_out(&mut self, start_ms: f64, duration_ms: f64) {
+        self.fade_out = Some((start_ms, duration_ms));
+    }
+
+    /// Brightness factor in `0.0..=1.0` at `now_ms` for a stream that started at `start_ms`
+    pub(crate) fn brightness(&self, start_ms: f64, now_ms: f64) -> f64 {
+        let fade_in = match self.fade_in_ms {
+            Some(duration_ms) => ramp(now_ms - start_ms, duration_ms),
+            None => 1.0,
+        };
+        let fade_out = match self.fade_out {
+            Some((fade_start_ms, duration_ms)) => 1.0 - ramp(now_ms - fade_start_ms, duration_ms),
+            None => 1.0,
+        };
+        fade_in * fade_out
+    }
+}
+
+/// Progress through a ramp of the given duration, clamped to `0.0..=1.0`
+fn ramp(elapsed_ms: f64, duration_ms: f64) -> f64 {
+    if duration_ms <= 0.0 {
+        return if elapsed_ms >= 0.0 { 1.0 } else { 0.0 };
+    }
+    (elapsed_ms / duration_ms).clamp(0.0, 1.0)
+}
+
+/// Scale the color channels of every pixel by `factor`, leaving alpha untouched
+///
+/// Planar `I420` frames are left as they are.
+pub(crate) fn apply_brightness(buf: &mut [u8], format: PixelFormat, factor: f64) {
+    let scale = (factor.clamp(0.0, 1.0) * 256.0).round() as u32;
+    let scale_value = |c: u32| (c * scale + 128) >> 8;
+    let scale_channel = |c: u8| scale_value(c as u32) as u8;
+
+    match format {
+        PixelFormat::Rgba8 | PixelFormat::Bgra8 => {
+            for pixel in buf.chunks_exact_mut(4) {
+                for channel in &mut pixel[..3] {
+                    *channel = scale_channel(*channel);
+                }
+            }
+        }
+        PixelFormat::Prgb8 | PixelFormat::Argb8 | PixelFormat::Abgr8 => {
+            for pixel in buf.chunks_exact_mut(4) {
+                for channel in &mut pixel[1..] {
+                    *channel = scale_channel(*channel);
+                }
+            }
+        }
+        PixelFormat::Rgb8 | PixelFormat::Bgr8 | PixelFormat::Gray8 => {
+            for channel in buf.iter_mut() {
+                *channel = scale_channel(*channel);
+            }
+        }
+        PixelFormat::A2Rgb10 => {
+            for pixel in buf.chunks_exact_mut(4) {
+                let packed = u32::from_le_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+                let r = scale_value(packed & 0x3ff);
+                let g = scale_value((packed >> 10) & 0x3ff);
+                let b = scale_value((packed >> 20) & 0x3ff);
+                let packed = (packed & 0xc000_0000) | (b << 20) | (g << 10) | r;
+                pixel.copy_from_slice(&packed.to_le_bytes());
+            }
+        }
+        PixelFormat::Rgb565 => {
+            for pixel in buf.chunks_exact_mut(2) {
+                let packed = u16::from_le_bytes([pixel[0], pixel[1]]) as u32;
+                let r = scale_value((packed >> 11) & 0x1f);
+                let g = scale_value((packed >> 5) & 0x3f);
+                let b = scale_value(packed & 0x1f);
+                let packed = ((r << 11) | (g << 5) | b) as u16;
+                pixel.copy_from_slice(&packed.to_le_bytes());
+            }
+        }
+        PixelFormat::Rgba16 => {
+            for pixel in buf.chunks_exact_mut(8) {
+                for bytes in pixel[..6].chunks_exact_mut(2) {
+                    let channel = u16::from_ne_bytes([bytes[0], bytes[1]]) as u32;
+                    let scaled = scale_value(channel) as u16;
+                    bytes.copy_from_slice(&scaled.to_ne_bytes());
+                }
+            }
+        }
+        // Scaling the planes would tint rather than darken, so planar frames aren't faded
+        PixelFormat::I420 => {}
+    }
+}
+
+/// Mix two frames per channel, `weight` of the way from `a` to `b`
+pub(crate) fn blend_frames(a: &[u8], b: &[u8], dst: &mut [u8], format: PixelFormat, weight: f64) {
+    assert_eq!(a.len(), b.len(), "frames must have the same size");
+    assert_eq!(a.len(), dst.len(), "destination frame has the wrong size");
+    let weight = (weight.clamp(0.0, 1.0) * 256.0).round() as u32;
+    let mix = |x: u32, y: u32| (x * (256 - weight) + y * weight + 128) >> 8;
+    // (shift, mask) of each channel in a packed pixel
+    let mix_packed = |x: u32, y: u32, fields: &[(u32, u32)]| {
+        fields.iter().fold(0, |packed, &(shift, mask)| {
+            packed | (mix((x >> shift) & mask, (y >> shift) & mask) << shift)
+        })
+    };
+
+    match format {
+        PixelFormat::Rgba8
+        | PixelFormat::Prgb8
+        | PixelFormat::Bgra8
+        | PixelFormat::Argb8
+        | PixelFormat::Abgr8
+        | PixelFormat::Rgb8
+        | PixelFormat::Bgr8
+        | PixelFormat::Gray8
+        | PixelFormat::I420 => {
+            for ((out, &x), &y) in dst.iter_mut().zip(a).zip(b) {
+                *out = mix(x as u32, y as u32) as u8;
+            }
+        }
+        PixelFormat::Rgb565 => {
+            let pixels = a.chunks_exact(2).zip(b.chunks_exact(2));
+            for (out, (x, y)) in dst.chunks_exact_mut(2).zip(pixels) {
+                let x = u16::from_le_bytes([x[0], x[1]]) as u32;
+                let y = u16::from_le_bytes([y[0], y[1]]) as u32;
+                let packed = mix_packed(x, y, &[(11, 0x1f), (5, 0x3f), (0, 0x1f)]) as u16;
+                out.copy_from_slice(&packed.to_le_bytes());
+            }
+        }
+        PixelFormat::A2Rgb10 => {
+            let pixels = a.chunks_exact(4).zip(b.chunks_exact(4));
+            for (out, (x, y)) in dst.chunks_exact_mut(4).zip(pixels) {
+                let x = u32::from_le_bytes([x[0], x[1], x[2], x[3]]);
+                let y = u32::from_le_bytes([y[0], y[1], y[2], y[3]]);
+                let fields = [(0, 0x3ff), (10, 0x3ff), (20, 0x3ff), (30, 0x3)];
+                out.copy_from_slice(&mix_packed(x, y, &fields).to_le_bytes());
+            }
+        }
+        PixelFormat::Rgba16 => {
+            let channels = a.chunks_exact(2).zip(b.chunks_exact(2));
+            for (out, (x, y)) in dst.chunks_exact_mut(2).zip(channels) {
+                let x = u16::from_ne_bytes([x[0], x[1]]) as u32;
+                let y = u16::from_ne_bytes([y[0], y[1]]) as u32;
+                out.copy_from_slice(&(mix(x, y) as u16).to_ne_bytes());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_fades_is_full_brightness() {
+        let transition = Transition::default();
+        assert_eq!(transition.brightness(0.0, 0.0), 1.0);
+        assert_eq!(transition.brightness(0.0, 10_000.0), 1.0);
+    }
+
+    #[test]
+    fn test_fade_in_ramp() {
+        let mut transition = Transition::default();
+        transition.set_fade_in(1000.0);
+
+        assert_eq!(transition.brightness(100.0, 100.0), 0.0);
+        assert_eq!(transition.brightness(100.0, 600.0), 0.5);
+        assert_eq!(transition.brightness(100.0, 1100.0), 1.0);
+        assert_eq!(transition.brightness(100.0, 5000.0), 1.0);
+    }
+
+    #[test]
+    fn test_fade_out_ramp() {
+        let mut transition = Transition::default();
+        transition.set_fade_out(2000.0, 500.0);
+
+        assert_eq!(transition.brightness(0.0, 1000.0), 1.0);
+        assert_eq!(transition.brightness(0.0, 2250.0), 0.5);
+        assert_eq!(transition.brightness(0.0, 3000.0), 0.0);
+    }
+
+    #[test]
+    fn test_apply_brightness_keeps_alpha() {
+        let mut rgba = [255, 128, 0, 200];
+        apply_brightness(&mut rgba, PixelFormat::Rgba8, 0.5);
+        assert_eq!(rgba, [128, 64, 0, 200]);
+
+        let mut prgb = [200, 255, 128, 0];
+        apply_brightness(&mut prgb, PixelFormat::Prgb8, 0.5);
+        assert_eq!(prgb, [200, 128, 64, 0]);
+    }
+
+    #[test]
+    fn test_apply_brightness_rgb565() {
+        let mut buf = 0xffffu16.to_le_bytes();
+        apply_brightness(&mut buf, PixelFormat::Rgb565, 0.0);
+        assert_eq!(u16::from_le_bytes(buf), 0);
+
+        // Packed channels round like byte channels do
+        let mut buf = 0xffffu16.to_le_bytes();
+        apply_brightness(&mut buf, PixelFormat::Rgb565, 0.5);
+        assert_eq!(u16::from_le_bytes(buf), (16 << 11) | (32 << 5) | 16);
+    }
+
+    #[test]
+    fn test_apply_brightness_a2rgb10_rounds() {
+        let mut buf = 0xffff_ffffu32.to_le_bytes();
+        apply_brightness(&mut buf, PixelFormat::A2Rgb10, 0.5);
+        assert_eq!(
+            u32::from_le_bytes(buf),
+            0xc000_0000 | (512 << 20) | (512 << 10) | 512
+        );
+    }
+
+    #[test]
+    fn test_apply_brightness_leaves_i420_untouched() {
+        let mut buf = [200; 6];
+        apply_brightness(&mut buf, PixelFormat::I420, 0.5);
+        assert_eq!(buf, [200; 6]);
+    }
+
+    #[test]
+    fn test_blend_frames_rgb565_per_channel() {
+        let red = 0xf800u16.to_le_bytes();
+        let blue = 0x001fu16.to_le_bytes();
+        let mut out = [0u8; 2];
+        blend_frames(&red, &blue, &mut out, PixelFormat::Rgb565, 0.5);
+        // 31/2 rounds to 16 for both red and blue, nothing carries into green
+        assert_eq!(u16::from_le_bytes(out), (16 << 11) | 16);
+    }
+}
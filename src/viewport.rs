@@ -0,0 +1,81 @@
+use crate::{PixelFormat, Rect};
+
+/// Copy the `viewport` region of a `canvas_size` canvas into `dst`
+///
+/// `dst` is a tightly packed `viewport.width` x `viewport.height` frame. Parts of the
+/// viewport beyond the canvas edges are filled with zero bytes (transparent black).
+pub(crate) fn copy_viewport(
+    canvas: &[u8],
+    (canvas_width, canvas_height): (u32, u32),
+    dst: &mut [u8],
+    viewport: Rect,
+    format: PixelFormat,
+) {
+    assert!(
+        !format.is_planar(),
+        "viewports of {:?} are not supported",
+        format
+    );
+    assert_eq!(
+        canvas.len(),
+        format.buffer_size(canvas_width, canvas_height),
+        "canvas has the wrong size"
+    );
+    assert_eq!(
+        dst.len(),
+        format.buffer_size(viewport.width, viewport.height),
+        "destination frame has the wrong size"
+    );
+
+    let canvas_stride = format.stride(canvas_width);
+    let dst_stride = format.stride(viewport.width);
+    // Columns of the viewport that overlap the canvas
+    let visible_width = canvas_width.saturating_sub(viewport.x).min(viewport.width);
+
+    for y in 0..viewport.height {
+        let dst_row = &mut dst[format.row_range(y, viewport.width, dst_stride)];
+        let canvas_y = viewport.y as u64 + y as u64;
+        if canvas_y >= canvas_height as u64 || visible_width == 0 {
+            dst_row.fill(0);
+            continue;
+        }
+
+        let visible = format.stride(visible_width);
+        let src_start = format.pixel_offset(viewport.x, canvas_y as u32, canvas_stride);
+        dst_row[..visible].copy_from_slice(&canvas[src_start..src_start + visible]);
+        dst_row[visible..].fill(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewport_inside_canvas() {
+        // 4x2 Gray8 canvas numbered 0..8
+        let canvas: Vec<u8> = (0..8).collect();
+        let mut dst = [0xffu8; 4];
+        copy_viewport(
+            &canvas,
+            (4, 2),
+            &mut dst,
+            Rect::new(1, 0, 2, 2),
+            PixelFormat::Gray8,
+        );
+        assert_eq!(dst, [1, 2, 5, 6]);
+    }
+
+    #[test]
+    fn test_viewport_entirely_outside_canvas() {
+        let mut dst = [0xffu8; 4];
+        copy_viewport(
+            &[1; 4],
+            (2, 2),
+            &mut dst,
+            Rect::new(5, 5, 2, 2),
+            PixelFormat::Gray8,
+        );
+        assert_eq!(dst, [0; 4]);
+    }
+}
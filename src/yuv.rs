@@ -0,0 +1,250 @@
+/// How half-resolution chroma planes are upsampled to the luma resolution
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChromaUpsampling {
+    /// Repeat each chroma sample over its 2x2 luma block. Fast but blocky on color edges.
+    #[default]
+    Nearest,
+    /// Bilinear interpolation between center-sited chroma samples.
+    Linear,
+}
+
+/// Converts planar YUV 4:2:0 to RGBA using BT.601 limited-range coefficients
+///
+/// `u_plane` and `v_plane` are `ceil(width / 2)` x `ceil(height / 2)` samples.
+/// Alpha is set to 255.
+pub fn yuv420_to_rgba8(
+    y_plane: &[u8],
+    u_plane: &[u8],
+    v_plane: &[u8],
+    dst: &mut [u8],
+    width: u32,
+    height: u32,
+    upsampling: ChromaUpsampling,
+) {
+    let (width, height) = (width as usize, height as usize);
+    let chroma_width = width.div_ceil(2);
+    let chroma_height = height.div_ceil(2);
+
+    assert_eq!(y_plane.len(), width * height, "Y plane has the wrong size");
+    assert_eq!(
+        u_plane.len(),
+        chroma_width * chroma_height,
+        "U plane has the wrong size"
+    );
+    assert_eq!(
+        v_plane.len(),
+        chroma_width * chroma_height,
+        "V plane has the wrong size"
+    );
+    assert_eq!(
+        dst.len(),
+        width * height * 4,
+        "destination has the wrong size"
+    );
+
+    let chroma = ChromaPlane {
+        width: chroma_width,
+        height: chroma_height,
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            let luma = y_plane[y * width + x];
+            let u = chroma.sample(u_plane, x, y, upsampling);
+            let v = chroma.sample(v_plane, x, y, upsampling);
+
+            let idx = (y * width + x) * 4;
+            dst[idx..idx + 4].copy_from_slice(&yuv_to_rgba(luma, u, v));
+        }
+    }
+}
+
+/// BT.601 limited range, 8-bit fixed point
+#[inline]
+fn yuv_to_rgba(y: u8, u: u8, v: u8) -> [u8; 4] {
+    let c = 298 * (y as i32 - 16);
+    let d = u as i32 - 128;
+    let e = v as i32 - 128;
+
+    let clamp = |value: i32| ((value + 128) >> 8).clamp(0, 255) as u8;
+    [
+        clamp(c + 409 * e),
+        clamp(c - 100 * d - 208 * e),
+        clamp(c + 516 * d),
+        255,
+    ]
+}
+
+/// Converts RGBA to planar YUV 4:2:0 with BT.601 limited-range coefficients
+///
+/// The inverse of `yuv420_to_rgba8`: each chroma sample is computed from the average
+/// color of its 2x2 luma block, and alpha is ignored.
+#[cfg_attr(not(feature = "file-recorder"), allow(dead_code))]
+pub(crate) fn rgba8_to_yuv420(
+    src: &[u8],
+    y_plane: &mut [u8],
+    u_plane: &mut [u8],
+    v_plane: &mut [u8],
+    width: u32,
+    height: u32,
+) {
+    let (width, height) = (width as usize, height as usize);
+    let chroma_width = width.div_ceil(2);
+
+    assert_eq!(src.len(), width * height * 4, "source has the wrong size");
+    assert_eq!(y_plane.len(), width * height, "Y plane has the wrong size");
+    assert_eq!(
+        u_plane.len(),
+        chroma_width * height.div_ceil(2),
+        "U plane has the wrong size"
+    );
+    assert_eq!(u_plane.len(), v_plane.len(), "V plane has the wrong size");
+
+    let rgb = |x: usize, y: usize| {
+        let idx = (y * width + x) * 4;
+        [src[idx] as i32, src[idx + 1] as i32, src[idx + 2] as i32]
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            let [r, g, b] = rgb(x, y);
+            y_plane[y * width + x] = (((66 * r + 129 * g + 25 * b + 128) >> 8) + 16) as u8;
+        }
+    }
+
+    for (i, (u, v)) in u_plane.iter_mut().zip(v_plane.iter_mut()).enumerate() {
+        let (cx, cy) = (i % chroma_width * 2, i / chroma_width * 2);
+        let mut sum = [0; 3];
+        let mut count = 0;
+        for y in cy..(cy + 2).min(height) {
+            for x in cx..(cx + 2).min(width) {
+                for (total, channel) in sum.iter_mut().zip(rgb(x, y)) {
+                    *total += channel;
+                }
+                count += 1;
+            }
+        }
+        let [r, g, b] = sum.map(|total| (total + count / 2) / count);
+
+        *u = (((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128) as u8;
+        *v = (((112 * r - 94 * g - 18 * b + 128) >> 8) + 128) as u8;
+    }
+}
+
+struct ChromaPlane {
+    width: usize,
+    height: usize,
+}
+
+impl ChromaPlane {
+    /// Chroma value for luma pixel (`x`, `y`)
+    fn sample(&self, plane: &[u8], x: usize, y: usize, upsampling: ChromaUpsampling) -> u8 {
+        match upsampling {
+            ChromaUpsampling::Nearest => plane[(y / 2) * self.width + x / 2],
+            ChromaUpsampling::Linear => {
+                let (x0, x1) = neighbors(x, self.width);
+                let (y0, y1) = neighbors(y, self.height);
+                let at = |cx: usize, cy: usize| plane[cy * self.width + cx] as u32;
+
+                // Weights are 3:1 towards the nearer chroma sample on each axis
+                let row0 = 3 * at(x0, y0) + at(x1, y0);
+                let row1 = 3 * at(x0, y1) + at(x1, y1);
+                ((3 * row0 + row1 + 8) / 16) as u8
+            }
+        }
+    }
+}
+
+/// The nearer and farther chroma samples for luma coordinate `pos`
+///
+/// Chroma samples sit between each pair of luma samples, so even luma positions
+/// lean towards the previous chroma sample and odd ones towards the next.
+fn neighbors(pos: usize, len: usize) -> (usize, usize) {
+    let nearer = pos / 2;
+    let farther = if pos.is_multiple_of(2) {
+        nearer.saturating_sub(1)
+    } else {
+        (nearer + 1).min(len - 1)
+    };
+    (nearer, farther)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 4x2 gray frame whose V plane steps from low to high between the chroma columns
+    fn convert_chroma_edge(upsampling: ChromaUpsampling) -> Vec<u8> {
+        let y_plane = [128u8; 8];
+        let u_plane = [128u8; 2];
+        let v_plane = [64u8, 192];
+        let mut dst = vec![0u8; 32];
+
+        yuv420_to_rgba8(&y_plane, &u_plane, &v_plane, &mut dst, 4, 2, upsampling);
+        dst
+    }
+
+    fn red_row(rgba: &[u8]) -> Vec<u8> {
+        rgba[..16].chunks_exact(4).map(|pixel| pixel[0]).collect()
+    }
+
+    #[test]
+    fn test_nearest_keeps_hard_chroma_edge() {
+        let red = red_row(&convert_chroma_edge(ChromaUpsampling::Nearest));
+        assert_eq!(red[0], red[1]);
+        assert_eq!(red[2], red[3]);
+        assert!(red[1] < red[2]);
+    }
+
+    #[test]
+    fn test_linear_produces_intermediate_values() {
+        let nearest = red_row(&convert_chroma_edge(ChromaUpsampling::Nearest));
+        let linear = red_row(&convert_chroma_edge(ChromaUpsampling::Linear));
+
+        // Outer pixels clamp to the edge samples
+        assert_eq!(linear[0], nearest[0]);
+        assert_eq!(linear[3], nearest[3]);
+        // Inner pixels blend across the edge
+        assert!(linear[0] < linear[1] && linear[1] < linear[2] && linear[2] < linear[3]);
+    }
+
+    #[test]
+    fn test_bt601_reference_colors() {
+        assert_eq!(yuv_to_rgba(16, 128, 128), [0, 0, 0, 255]);
+        assert_eq!(yuv_to_rgba(235, 128, 128), [255, 255, 255, 255]);
+        // Limited-range red
+        assert_eq!(yuv_to_rgba(81, 90, 240), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_rgba_round_trip() {
+        // 3x1 so the last chroma sample covers a partial block
+        let src = [255, 0, 0, 255, 255, 0, 0, 255, 255, 255, 255, 255];
+        let (mut y, mut u, mut v) = ([0; 3], [0; 2], [0; 2]);
+        rgba8_to_yuv420(&src, &mut y, &mut u, &mut v, 3, 1);
+
+        assert_eq!(y, [82, 82, 235]);
+        assert_eq!((u[1], v[1]), (128, 128));
+
+        let mut dst = [0; 12];
+        yuv420_to_rgba8(&y, &u, &v, &mut dst, 3, 1, ChromaUpsampling::Nearest);
+        for (decoded, original) in dst.iter().zip(src) {
+            assert!(decoded.abs_diff(original) <= 2, "{:?} != {:?}", dst, src);
+        }
+    }
+
+    #[test]
+    fn test_odd_dimensions() {
+        let mut dst = vec![0u8; 3 * 3 * 4];
+        yuv420_to_rgba8(
+            &[235; 9],
+            &[128; 4],
+            &[128; 4],
+            &mut dst,
+            3,
+            3,
+            ChromaUpsampling::Linear,
+        );
+        assert!(dst.iter().all(|&c| c == 255));
+    }
+}
@@ -0,0 +1,125 @@
+//! Runs the full render thread → `TripleBuffer` → `DisplayPresenter` → backend
+//! pipeline headlessly across many frames.
+
+use std::sync::Arc;
+use std::thread;
+use video_buffer::{DisplayBackend, DisplayPresenter, PixelFormat, TripleBuffer, VideoBufferError};
+
+const WIDTH: u32 = 16;
+const HEIGHT: u32 = 8;
+const FRAME_COUNT: u16 = 200;
+const MARKER: u8 = 7;
+
+/// Records the frame counter encoded in each presented frame.
+struct RecordingBackend {
+    counters: Vec<u16>,
+}
+
+impl DisplayBackend for RecordingBackend {
+    const FORMAT: PixelFormat = PixelFormat::Rgba8;
+
+    fn init(&mut self, _width: u32, _height: u32) -> Result<(), VideoBufferError> {
+        Ok(())
+    }
+
+    fn present(&mut self, frame: &[u8]) -> Result<(), VideoBufferError> {
+        assert_eq!(frame.len(), PixelFormat::Rgba8.buffer_size(WIDTH, HEIGHT));
+
+        let first = &frame[..4];
+        assert!(
+            frame.chunks_exact(4).all(|pixel| pixel == first),
+            "presented frame mixes pixels from different renders"
+        );
+
+        // Buffers start zeroed until the first render lands
+        let counter = if first == [0, 0, 0, 0] {
+            0
+        } else {
+            assert_eq!(first[2], MARKER, "channels not converted to RGBA order");
+            assert_eq!(first[3], 255, "alpha not converted to RGBA order");
+            u16::from_le_bytes([first[0], first[1]])
+        };
+        self.counters.push(counter);
+        Ok(())
+    }
+}
+
+/// Writes frame `counter` as a uniform PRGB frame: A=255, R/G=counter, B=marker
+fn render_counter(frame: &mut [u8], counter: u16) {
+    let [lo, hi] = counter.to_le_bytes();
+    for pixel in frame.chunks_exact_mut(4) {
+        pixel.copy_from_slice(&[255, lo, hi, MARKER]);
+    }
+}
+
+#[test]
+fn test_threaded_pipeline_presents_monotonic_converted_frames() {
+    let buffer = Arc::new(TripleBuffer::new(WIDTH, HEIGHT, PixelFormat::Prgb8));
+    let backend = RecordingBackend {
+        counters: Vec::new(),
+    };
+    let mut presenter = DisplayPresenter::new(backend, WIDTH, HEIGHT, PixelFormat::Prgb8)
+        .unwrap()
+        .with_max_fps(500.0);
+
+    let render_buffer = Arc::clone(&buffer);
+    let render_thread = thread::spawn(move || {
+        for counter in 1..=FRAME_COUNT {
+            render_counter(&mut render_buffer.render_buffer(), counter);
+            render_buffer.commit_render();
+            thread::yield_now();
+        }
+    });
+
+    // Simulated timeline advancing 1ms per iteration, so every other present is skipped
+    let mut now_ms = 0.0;
+    let mut skipped = 0;
+    while !render_thread.is_finished() {
+        now_ms += 1.0;
+        if !presenter.present_at(&buffer, now_ms).unwrap() {
+            skipped += 1;
+        }
+        thread::yield_now();
+    }
+    render_thread.join().unwrap();
+
+    // Pick up the final frame
+    now_ms += 1000.0;
+    assert!(presenter.present_at(&buffer, now_ms).unwrap());
+
+    let counters = &presenter.backend().counters;
+    assert!(!counters.is_empty());
+    assert!(skipped > 0, "rate limiter never engaged");
+    assert!(
+        counters.windows(2).all(|pair| pair[0] <= pair[1]),
+        "presented frames went backwards: {:?}",
+        counters
+    );
+    assert_eq!(*counters.last().unwrap(), FRAME_COUNT);
+}
+
+#[test]
+fn test_presenting_faster_than_rendering_repeats_latest_frame() {
+    let buffer = TripleBuffer::new(WIDTH, HEIGHT, PixelFormat::Prgb8);
+    let backend = RecordingBackend {
+        counters: Vec::new(),
+    };
+    let mut presenter = DisplayPresenter::new(backend, WIDTH, HEIGHT, PixelFormat::Prgb8).unwrap();
+
+    let mut now_ms = 0.0;
+    for counter in 1..=3 {
+        render_counter(&mut buffer.render_buffer(), counter);
+        buffer.commit_render();
+
+        // Three display refreshes per rendered frame
+        for _ in 0..3 {
+            now_ms += 16.0;
+            presenter.present_at(&buffer, now_ms).unwrap();
+        }
+    }
+
+    assert_eq!(
+        presenter.backend().counters,
+        vec![1, 1, 1, 2, 2, 2, 3, 3, 3]
+    );
+}